@@ -1,4 +1,6 @@
-use clickhouse_analyzer::{format, parse, FormatConfig};
+use std::ops::ControlFlow;
+
+use clickhouse_analyzer::{for_each_token, format, parse, tokenize_checked, FormatConfig, SyntaxKind};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 const SMALL_QUERY: &str = "SELECT 1";
@@ -90,5 +92,30 @@ fn bench_parse_and_format(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_parse, bench_parse_and_format);
+/// Collecting tokens into a `Vec` against streaming them through a callback,
+/// which allocates nothing per token.
+fn bench_tokenize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tokenize");
+
+    group.bench_function("collect", |b| {
+        b.iter(|| tokenize_checked(black_box(LARGE_QUERY)).0.len())
+    });
+
+    group.bench_function("stream", |b| {
+        b.iter(|| {
+            let mut count = 0;
+            for_each_token(black_box(LARGE_QUERY), |token| {
+                if !matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Comment) {
+                    count += 1;
+                }
+                ControlFlow::Continue(())
+            });
+            count
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_parse_and_format, bench_tokenize);
 criterion_main!(benches);
@@ -14,6 +14,7 @@ export {
 } from "./helpers.js";
 export type {
     RawToken,
+    RawPositionedToken,
     RawTree,
     RawChild,
     RawSyntaxError,
@@ -22,9 +23,17 @@ export type {
 
 import initWasm, {
     parse_sql as wasmParseSql,
+    parse_sql_with_budget as wasmParseSqlWithBudget,
     format_sql as wasmFormatSql,
     get_tree as wasmGetTree,
+    get_tree_json as wasmGetTreeJson,
+    get_tokens as wasmGetTokens,
     get_diagnostics as wasmGetDiagnostics,
+    validate as wasmValidate,
+    validate_with_min_severity as wasmValidateWithMinSeverity,
+    highlight as wasmHighlight,
+    analyze_query as wasmAnalyzeQuery,
+    get_completions as wasmGetCompletions,
 } from "../pkg/clickhouse_analyzer.js";
 import type { RawParseResult } from "./types.js";
 import { buildParseResult, type ParseResult } from "./parse.js";
@@ -67,7 +76,21 @@ function ensureInit(): void {
 export function parse(sql: string): ParseResult {
     ensureInit();
     checkInputSize(sql);
-    const json = wasmParseSql(sql);
+    return parseResultFromJson(wasmParseSql(sql));
+}
+
+/**
+ * Like `parse`, but stop after `maxTokens` tokens (whitespace and comments
+ * included), so a UI can bail out on pathological input. A stopped parse
+ * ends with a "Token budget exceeded" error.
+ */
+export function parseWithBudget(sql: string, maxTokens: number): ParseResult {
+    ensureInit();
+    checkInputSize(sql);
+    return parseResultFromJson(wasmParseSqlWithBudget(sql, maxTokens));
+}
+
+function parseResultFromJson(json: string): ParseResult {
     let raw: RawParseResult;
     try {
         raw = JSON.parse(json);
@@ -95,6 +118,25 @@ export function getTree(sql: string): string {
     return wasmGetTree(sql);
 }
 
+/**
+ * Get the CST of parsed SQL as a JSON string (a `RawTree`).
+ */
+export function getTreeJson(sql: string): string {
+    ensureInit();
+    checkInputSize(sql);
+    return wasmGetTreeJson(sql);
+}
+
+/**
+ * Get the full token stream, whitespace included, as a JSON string with an
+ * array of `RawPositionedToken`.
+ */
+export function getTokens(sql: string): string {
+    ensureInit();
+    checkInputSize(sql);
+    return wasmGetTokens(sql);
+}
+
 /**
  * Get diagnostics (errors, warnings) as a JSON string.
  */
@@ -103,3 +145,56 @@ export function getDiagnostics(sql: string): string {
     checkInputSize(sql);
     return wasmGetDiagnostics(sql);
 }
+
+/**
+ * Parse and analyze SQL in one call. Returns a JSON string with
+ * `{ ok, errors, warnings, tables, dictionaries, columns, samples }`.
+ */
+export function validate(sql: string): string {
+    ensureInit();
+    checkInputSize(sql);
+    return wasmValidate(sql);
+}
+
+/**
+ * Like `validate`, but drops diagnostics less severe than `minSeverity`.
+ */
+export function validateWithMinSeverity(
+    sql: string,
+    minSeverity: "error" | "warning" | "hint",
+): string {
+    ensureInit();
+    checkInputSize(sql);
+    return wasmValidateWithMinSeverity(sql, minSeverity);
+}
+
+/**
+ * Get the tables and selected columns of each statement. Returns a JSON
+ * string with `{ statements: [{ kind, span, tables, columns, errors }] }`,
+ * where `errors` lists `{ message, span }` for unknown column qualifiers.
+ */
+export function analyzeQuery(sql: string): string {
+    ensureInit();
+    checkInputSize(sql);
+    return wasmAnalyzeQuery(sql);
+}
+
+/**
+ * Get completion candidates at a byte offset. Returns a JSON string with an
+ * array of `{ text, kind }`, where `kind` is `"keyword"` or `"identifier"`.
+ */
+export function getCompletions(sql: string, cursor: number): string {
+    ensureInit();
+    checkInputSize(sql);
+    return wasmGetCompletions(sql, cursor);
+}
+
+/**
+ * Categorize tokens for syntax highlighting. Returns a JSON string with an
+ * array of `{ start, end, category }` byte ranges.
+ */
+export function highlight(sql: string): string {
+    ensureInit();
+    checkInputSize(sql);
+    return wasmHighlight(sql);
+}
@@ -57,10 +57,12 @@ export const SyntaxKind = {
     LimitClause: "LimitClause",
     SettingsClause: "SettingsClause",
     FormatClause: "FormatClause",
+    IntoOutfileClause: "IntoOutfileClause",
     UnionClause: "UnionClause",
     WindowClause: "WindowClause",
     WindowDefinition: "WindowDefinition",
     WindowFrame: "WindowFrame",
+    WindowFrameBound: "WindowFrameBound",
     WindowSpec: "WindowSpec",
     QualifyClause: "QualifyClause",
     SampleClause: "SampleClause",
@@ -186,6 +188,7 @@ export const SyntaxKind = {
     TypedJsonAccessExpression: "TypedJsonAccessExpression",
     MapExpression: "MapExpression",
     QueryParameterExpression: "QueryParameterExpression",
+    PositionalParameter: "PositionalParameter",
     SubqueryExpression: "SubqueryExpression",
     LambdaExpression: "LambdaExpression",
     IntervalExpression: "IntervalExpression",
@@ -198,6 +201,7 @@ export const SyntaxKind = {
     ColumnTransformer: "ColumnTransformer",
     GroupingSetsClause: "GroupingSetsClause",
     GroupingSet: "GroupingSet",
+    GroupByAll: "GroupByAll",
     WithTotalsClause: "WithTotalsClause",
 
     // Literals
@@ -297,6 +301,7 @@ export const SyntaxKind = {
     Slash: "Slash",
     Percent: "Percent",
     Arrow: "Arrow",
+    JsonExtractArrow: "JsonExtractArrow",
     QuestionMark: "QuestionMark",
     Colon: "Colon",
     Caret: "Caret",
@@ -324,6 +329,7 @@ export const SyntaxKind = {
     ErrorSingleQuoteIsNotClosed: "ErrorSingleQuoteIsNotClosed",
     ErrorDoubleQuoteIsNotClosed: "ErrorDoubleQuoteIsNotClosed",
     ErrorBackQuoteIsNotClosed: "ErrorBackQuoteIsNotClosed",
+    ErrorHereDocIsNotClosed: "ErrorHereDocIsNotClosed",
     ErrorSingleExclamationMark: "ErrorSingleExclamationMark",
     ErrorSinglePipeMark: "ErrorSinglePipeMark",
     ErrorWrongNumber: "ErrorWrongNumber",
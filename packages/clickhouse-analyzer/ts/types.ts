@@ -7,6 +7,15 @@ export interface RawToken {
     end: number;
 }
 
+/**
+ * Token with its 1-based line and column, from `getTokens`. The column counts
+ * UTF-16 code units, like JavaScript string indices.
+ */
+export interface RawPositionedToken extends RawToken {
+    line: number;
+    column: number;
+}
+
 /** Raw tree node from the WASM parser (JSON-serialized). */
 export interface RawTree {
     kind: SyntaxKind;
@@ -24,6 +33,10 @@ export type RawChild =
 export interface RawSyntaxError {
     message: string;
     range: [number, number];
+    /** Token kind the parser required, if the error came from `expect`. */
+    expected: SyntaxKind | null;
+    /** Kind of the token at `range`, or null at end of input. */
+    found: SyntaxKind | null;
 }
 
 /** Raw parse result from the WASM parser. */
@@ -0,0 +1,116 @@
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Well-known ClickHouse aggregate functions. This is a heuristic for code
+/// that has no server metadata at hand; the live `system.functions` table
+/// remains the source of truth.
+const AGGREGATE_FUNCTIONS: &[&str] = &[
+    "any", "anyHeavy", "anyLast", "argMax", "argMin", "avg", "avgWeighted", "corr", "count",
+    "covarPop", "covarSamp", "entropy", "first_value", "groupArray", "groupArrayInsertAt",
+    "groupBitAnd", "groupBitOr", "groupBitXor", "groupBitmap", "groupConcat", "groupUniqArray",
+    "histogram", "kurtPop", "kurtSamp", "last_value", "max", "maxMap", "median", "min", "minMap",
+    "quantile", "quantileExact", "quantileTDigest", "quantileTiming", "quantiles", "retention",
+    "sequenceCount", "sequenceMatch", "simpleLinearRegression", "skewPop", "skewSamp",
+    "stddevPop", "stddevSamp", "sum", "sumMap", "sumWithOverflow", "topK", "topKWeighted", "uniq",
+    "uniqCombined", "uniqCombined64", "uniqExact", "uniqHLL12", "uniqTheta", "varPop", "varSamp",
+    "windowFunnel",
+];
+
+/// Aggregate function combinators, e.g. `sumIf`, `uniqMerge`, `countIfOrNull`.
+const COMBINATOR_SUFFIXES: &[&str] = &[
+    "If", "Array", "Map", "SimpleState", "State", "MergeState", "Merge", "ForEach", "Distinct",
+    "OrDefault", "OrNull", "Resample", "ArgMin", "ArgMax",
+];
+
+/// True if `name` looks like an aggregate function call, including
+/// combinator forms. Comparison is case-insensitive.
+pub fn is_aggregate_function(name: &str) -> bool {
+    let mut base = name;
+    loop {
+        if AGGREGATE_FUNCTIONS.iter().any(|f| f.eq_ignore_ascii_case(base)) {
+            return true;
+        }
+        let stripped = COMBINATOR_SUFFIXES.iter().find_map(|suffix| {
+            base.strip_suffix(suffix).filter(|rest| !rest.is_empty())
+        });
+        match stripped {
+            Some(rest) => base = rest,
+            None => return false,
+        }
+    }
+}
+
+/// The name of a `FunctionCall` node, e.g. `sum` in `sum(x)`.
+pub fn function_call_name<'a>(call: &SyntaxTree, source: &'a str) -> Option<&'a str> {
+    let ident = call
+        .children
+        .iter()
+        .find_map(|child| child.get_tree_with_kind(SyntaxKind::Identifier))?;
+    ident
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_token)
+        .find(|token| token.kind != SyntaxKind::Whitespace)
+        .map(|token| token.text(source))
+}
+
+/// True if a `FunctionCall` node calls an aggregate function.
+pub fn is_aggregate_call(call: &SyntaxTree, source: &str) -> bool {
+    call.kind == SyntaxKind::FunctionCall
+        && function_call_name(call, source).is_some_and(is_aggregate_function)
+}
+
+/// Names of the aggregate functions called anywhere in the tree, in order
+/// of first use and as written (`sumIf`, not `sum`). Each name appears once.
+pub fn aggregate_functions(tree: &SyntaxTree, source: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for call in tree.find_all(SyntaxKind::FunctionCall) {
+        if let Some(name) = function_call_name(call, source).filter(|n| is_aggregate_function(n)) {
+            if !names.iter().any(|seen| seen == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn plain_and_combinator_aggregates() {
+        assert!(is_aggregate_function("count"));
+        assert!(is_aggregate_function("COUNT"));
+        assert!(is_aggregate_function("sumIf"));
+        assert!(is_aggregate_function("uniqMerge"));
+        assert!(is_aggregate_function("countIfOrNull"));
+        assert!(is_aggregate_function("groupArrayState"));
+    }
+
+    #[test]
+    fn non_aggregates() {
+        assert!(!is_aggregate_function("toDate"));
+        assert!(!is_aggregate_function("If"));
+        assert!(!is_aggregate_function("lower"));
+    }
+
+    #[test]
+    fn aggregate_functions_in_query() {
+        let sql = "SELECT count(), sum(x), max(y) FROM t";
+        let result = parse(sql);
+        assert_eq!(aggregate_functions(&result.tree, sql), ["count", "sum", "max"]);
+    }
+
+    #[test]
+    fn aggregate_functions_nested_and_repeated() {
+        let sql = "SELECT sumIf(a, b), lower(c), sumIf(d, e) \
+            FROM (SELECT uniq(x) AS a FROM t) HAVING count() > 1";
+        let result = parse(sql);
+        assert_eq!(
+            aggregate_functions(&result.tree, sql),
+            ["sumIf", "uniq", "count"]
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// What a `*` token means where it appears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsteriskRole {
+    /// `SELECT *`, `count(*)`, `* EXCEPT (a)`.
+    Wildcard,
+    /// `t.*`.
+    QualifiedWildcard,
+    /// `a * b`.
+    Multiplication,
+}
+
+/// A `*` token and the role it plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsteriskRef {
+    pub role: AsteriskRole,
+    /// Byte range of the `*` token.
+    pub span: (u32, u32),
+}
+
+/// Every `*` token in the tree whose role the parser settled, in source
+/// order. A `*` inside an error node is skipped.
+pub fn asterisk_roles(tree: &SyntaxTree) -> Vec<AsteriskRef> {
+    let mut out = Vec::new();
+    collect(tree, &mut out);
+    out
+}
+
+fn collect(tree: &SyntaxTree, out: &mut Vec<AsteriskRef>) {
+    let role = match tree.kind {
+        SyntaxKind::Asterisk => Some(AsteriskRole::Wildcard),
+        SyntaxKind::QualifiedAsterisk => Some(AsteriskRole::QualifiedWildcard),
+        SyntaxKind::BinaryExpression => Some(AsteriskRole::Multiplication),
+        _ => None,
+    };
+    for child in &tree.children {
+        match child {
+            SyntaxChild::Token(token) if token.kind == SyntaxKind::Star => {
+                if let Some(role) = role {
+                    out.push(AsteriskRef {
+                        role,
+                        span: (token.start, token.end),
+                    });
+                }
+            }
+            SyntaxChild::Tree(subtree) => collect(subtree, out),
+            SyntaxChild::Token(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn roles(sql: &str) -> Vec<(AsteriskRole, &str)> {
+        let result = parse(sql);
+        assert!(result.errors.is_empty(), "{sql}: {:?}", result.errors);
+        asterisk_roles(&result.tree)
+            .into_iter()
+            .map(|a| (a.role, &sql[a.span.0 as usize..a.span.1 as usize]))
+            .collect()
+    }
+
+    #[test]
+    fn select_star_is_wildcard() {
+        assert_eq!(roles("SELECT * FROM t"), [(AsteriskRole::Wildcard, "*")]);
+        assert_eq!(
+            roles("SELECT t.*, * EXCEPT (a) FROM t"),
+            [
+                (AsteriskRole::QualifiedWildcard, "*"),
+                (AsteriskRole::Wildcard, "*"),
+            ]
+        );
+    }
+
+    #[test]
+    fn binary_star_is_multiplication() {
+        assert_eq!(
+            roles("SELECT a * b FROM t"),
+            [(AsteriskRole::Multiplication, "*")]
+        );
+    }
+
+    #[test]
+    fn count_star_is_wildcard() {
+        assert_eq!(
+            roles("SELECT count(*) * 2 FROM t"),
+            [
+                (AsteriskRole::Wildcard, "*"),
+                (AsteriskRole::Multiplication, "*"),
+            ]
+        );
+    }
+}
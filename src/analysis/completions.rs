@@ -0,0 +1,446 @@
+//! Completion candidates at a cursor, for editors without a language server.
+//!
+//! Keywords come from the clause order the SELECT parser enforces, so after
+//! `FROM t` only clauses that may follow FROM are offered. Identifiers come
+//! from the query itself, since there is no schema at hand.
+
+use crate::analysis::scope::{build_scope_at, collect_all_table_refs};
+use crate::analysis::validate::{column_path, push_unique};
+use crate::lexer::token::Token;
+use crate::lexer::tokenizer::tokenize_up_to;
+use crate::parser::grammar::select::{BODY_CLAUSES, OUTPUT_CLAUSES};
+use crate::parser::parse;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::SyntaxTree;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "lowercase"))]
+pub enum CompletionKind {
+    Keyword,
+    Identifier,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Completion {
+    pub text: String,
+    pub kind: CompletionKind,
+}
+
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "SELECT", "WITH", "INSERT INTO", "CREATE", "ALTER", "DROP", "DELETE FROM", "UPDATE",
+    "SHOW", "DESCRIBE", "EXPLAIN", "USE", "SET", "OPTIMIZE TABLE", "TRUNCATE TABLE", "SYSTEM",
+];
+
+const EXPRESSION_KEYWORDS: &[&str] =
+    &["CASE", "CAST", "NOT", "NULL", "TRUE", "FALSE", "INTERVAL"];
+
+const JOIN_KEYWORDS: &[&str] = &[
+    "JOIN", "INNER JOIN", "LEFT JOIN", "RIGHT JOIN", "FULL JOIN", "CROSS JOIN", "ARRAY JOIN",
+];
+
+/// Words that leave the cursor expecting an operand rather than a keyword.
+const OPERAND_KEYWORDS: &[&str] = &[
+    "AND", "OR", "NOT", "IN", "LIKE", "ILIKE", "IS", "BETWEEN", "CASE", "WHEN", "THEN", "ELSE",
+    "DISTINCT", "AS", "BY",
+];
+
+/// Candidates for the word being typed at byte offset `cursor`. A partial
+/// word right before the cursor filters them by case-insensitive prefix.
+pub fn completions(sql: &str, cursor: usize) -> Vec<Completion> {
+    let mut cursor = cursor.min(sql.len());
+    while !sql.is_char_boundary(cursor) {
+        cursor -= 1;
+    }
+    let mut tokens: Vec<Token> = tokenize_up_to(sql, cursor);
+    tokens.retain(|t| t.kind != SyntaxKind::EndOfStream);
+
+    // A word the cursor is in or right after is the prefix being typed, not
+    // context.
+    let mut prefix = "";
+    if let Some(last) = tokens.last() {
+        let (start, end) = (last.start as usize, last.end as usize);
+        // A line comment runs to the end of its line, so its end is inside
+        // it too.
+        let line_comment = last.kind == SyntaxKind::Comment && !sql[start..].starts_with("/*");
+        if start < cursor && cursor <= end && last.kind == SyntaxKind::BareWord {
+            prefix = &sql[start..cursor];
+            tokens.pop();
+        } else if end > cursor || (line_comment && end == cursor) {
+            // Inside a string, comment or other token: nothing to complete.
+            return Vec::new();
+        }
+    }
+    tokens.retain(|t| t.kind != SyntaxKind::Comment);
+
+    let context = Context::at_end_of(&tokens, sql);
+    let mut candidates = Vec::new();
+    let mut keywords = |words: &[&str]| {
+        for word in words {
+            let completion = Completion { text: word.to_string(), kind: CompletionKind::Keyword };
+            if !candidates.contains(&completion) {
+                candidates.push(completion);
+            }
+        }
+    };
+
+    match context {
+        Context::StatementStart => keywords(STATEMENT_KEYWORDS),
+        Context::Keyword(words) => keywords(words),
+        Context::Operand { tables, after_select } => {
+            if after_select {
+                keywords(&["DISTINCT"]);
+            }
+            if !tables {
+                keywords(EXPRESSION_KEYWORDS);
+            }
+            let tree = parse(sql).tree;
+            let names = if tables {
+                table_names(&tree, sql, cursor)
+            } else {
+                expression_names(&tree, sql, cursor)
+            };
+            candidates.extend(
+                names
+                    .into_iter()
+                    .map(|text| Completion { text, kind: CompletionKind::Identifier }),
+            );
+        }
+        Context::AfterItem(clause) => {
+            match clause {
+                Clause::Select => keywords(&["AS"]),
+                Clause::From | Clause::Join => keywords(&["AS", "FINAL", "SAMPLE"]),
+                Clause::Body(index) if BODY_CLAUSES[index] == "ORDER BY" => {
+                    keywords(&["ASC", "DESC"])
+                }
+                _ => {}
+            }
+            let condition = match clause {
+                Clause::On => true,
+                Clause::Body(index) => {
+                    matches!(BODY_CLAUSES[index], "PREWHERE" | "WHERE" | "HAVING" | "QUALIFY")
+                }
+                _ => false,
+            };
+            if condition {
+                keywords(&["AND", "OR"]);
+            }
+            if matches!(clause, Clause::From | Clause::Join | Clause::On) {
+                keywords(JOIN_KEYWORDS);
+            }
+            if clause == Clause::Join {
+                keywords(&["ON", "USING"]);
+            }
+            match clause {
+                Clause::With => keywords(&["SELECT"]),
+                Clause::Output(index) => keywords(&OUTPUT_CLAUSES[index + 1..]),
+                _ => {
+                    let next = match clause {
+                        Clause::Body(index) => index + 1,
+                        // FROM and its joins are BODY_CLAUSES[0].
+                        Clause::From | Clause::Join | Clause::On => 1,
+                        _ => 0,
+                    };
+                    keywords(&BODY_CLAUSES[next..]);
+                    keywords(&OUTPUT_CLAUSES);
+                }
+            }
+        }
+        Context::None => {}
+    }
+
+    let prefix = prefix.to_ascii_lowercase();
+    candidates.retain(|c| c.text.to_ascii_lowercase().starts_with(&prefix));
+    candidates
+}
+
+/// Where the cursor sits in the statement being typed.
+enum Context {
+    StatementStart,
+    /// Only these keywords can come next, e.g. `BY` after `ORDER`.
+    Keyword(&'static [&'static str]),
+    /// An expression, or with `tables` a table name, is expected.
+    Operand { tables: bool, after_select: bool },
+    /// An item of the clause was just completed, so a following clause can
+    /// start here.
+    AfterItem(Clause),
+    /// Nothing useful to offer, e.g. after `AS` or in a non-SELECT statement.
+    None,
+}
+
+/// The SELECT clause the cursor is in.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Clause {
+    With,
+    Select,
+    From,
+    Join,
+    /// The ON or USING of a join.
+    On,
+    /// Index into `BODY_CLAUSES`, past FROM.
+    Body(usize),
+    /// Index into `OUTPUT_CLAUSES`.
+    Output(usize),
+}
+
+impl Context {
+    fn at_end_of(tokens: &[Token], sql: &str) -> Context {
+        let Some(statement) = current_statement(tokens, sql) else {
+            // Inside parentheses that aren't a subquery: function arguments,
+            // an IN list or a tuple.
+            return Context::Operand { tables: false, after_select: false };
+        };
+        let Some(last) = statement.last() else {
+            return Context::StatementStart;
+        };
+
+        // The last clause keyword at the statement's own parenthesis depth.
+        // Clauses only count once the SELECT (or its WITH) has started.
+        let mut depth = 0usize;
+        let mut clause = None;
+        for (i, token) in statement.iter().enumerate() {
+            match token.kind {
+                SyntaxKind::OpeningRoundBracket => depth += 1,
+                SyntaxKind::ClosingRoundBracket => depth = depth.saturating_sub(1),
+                SyntaxKind::BareWord if depth == 0 => {
+                    let w = word(token, sql);
+                    clause = match w.as_str() {
+                        "WITH" if i == 0 => Some(Clause::With),
+                        "SELECT" => Some(Clause::Select),
+                        _ if clause.is_none() => None,
+                        "FROM" => Some(Clause::From),
+                        "JOIN" => Some(Clause::Join),
+                        "ON" | "USING"
+                            if matches!(clause, Some(Clause::Join | Clause::On)) =>
+                        {
+                            Some(Clause::On)
+                        }
+                        _ => clause_position(&BODY_CLAUSES[1..], &w)
+                            .map(|index| Clause::Body(index + 1))
+                            .or_else(|| clause_position(&OUTPUT_CLAUSES, &w).map(Clause::Output))
+                            .or(clause),
+                    };
+                }
+                _ => {}
+            }
+        }
+        let Some(clause) = clause else {
+            return Context::None;
+        };
+
+        let last_word = match last.kind {
+            SyntaxKind::BareWord => word(last, sql),
+            _ => String::new(),
+        };
+        if matches!(last_word.as_str(), "GROUP" | "ORDER") {
+            return Context::Keyword(&["BY"]);
+        }
+        let ends_clause_keyword = is_clause_word(&last_word);
+        let expects_operand = match last.kind {
+            SyntaxKind::BareWord => {
+                ends_clause_keyword || OPERAND_KEYWORDS.contains(&last_word.as_str())
+            }
+            SyntaxKind::Number
+            | SyntaxKind::StringToken
+            | SyntaxKind::QuotedIdentifier
+            | SyntaxKind::ClosingRoundBracket
+            | SyntaxKind::ClosingSquareBracket
+            | SyntaxKind::Star => false,
+            _ => true,
+        };
+        if !expects_operand {
+            return Context::AfterItem(clause);
+        }
+        match clause {
+            // `AS` wants a new name, and output clauses take setting names,
+            // file names or formats, none of which we know.
+            _ if last_word == "AS" => Context::None,
+            Clause::Output(_) | Clause::With => Context::None,
+            Clause::From | Clause::Join => Context::Operand { tables: true, after_select: false },
+            _ => Context::Operand { tables: false, after_select: last_word == "SELECT" },
+        }
+    }
+}
+
+/// Index of the clause in `clauses` that starts with `word`.
+fn clause_position(clauses: &[&str], word: &str) -> Option<usize> {
+    clauses.iter().position(|c| c.split(' ').next() == Some(word))
+}
+
+/// The tokens of the innermost statement containing the end of `tokens`:
+/// those after the last top-level `;`, or after the `(` opening an
+/// unfinished subquery. `None` inside parentheses that hold no subquery.
+fn current_statement<'a>(tokens: &'a [Token], sql: &str) -> Option<&'a [Token]> {
+    let mut open: Vec<usize> = Vec::new();
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.kind {
+            SyntaxKind::OpeningRoundBracket => open.push(i),
+            SyntaxKind::ClosingRoundBracket => {
+                open.pop();
+            }
+            SyntaxKind::Semicolon if open.is_empty() => start = i + 1,
+            _ => {}
+        }
+    }
+    match open.last() {
+        None => Some(&tokens[start..]),
+        Some(&paren) => {
+            let inner = &tokens[paren + 1..];
+            let is_subquery = inner.first().is_some_and(|t| {
+                t.kind == SyntaxKind::BareWord && matches!(word(t, sql).as_str(), "SELECT" | "WITH")
+            });
+            is_subquery.then_some(inner)
+        }
+    }
+}
+
+fn word(token: &Token, sql: &str) -> String {
+    token.text(sql).to_ascii_uppercase()
+}
+
+fn is_clause_word(word: &str) -> bool {
+    matches!(word, "SELECT" | "WITH" | "JOIN" | "ON" | "USING")
+        || clause_position(&BODY_CLAUSES, word).is_some()
+        || clause_position(&OUTPUT_CLAUSES, word).is_some()
+}
+
+/// Tables referenced anywhere in the query and CTEs in scope at `cursor`.
+fn table_names(tree: &SyntaxTree, sql: &str, cursor: usize) -> Vec<String> {
+    let mut names = Vec::new();
+    if let Some(scope) = build_scope_at(tree, sql, cursor as u32) {
+        for cte in scope.ctes {
+            push_unique(&mut names, cte.name);
+        }
+    }
+    for table_ref in collect_all_table_refs(tree, sql) {
+        if table_ref.range.0 as usize <= cursor && cursor <= table_ref.range.1 as usize {
+            continue;
+        }
+        let name = match table_ref.database {
+            Some(db) => format!("{db}.{}", table_ref.table),
+            None => table_ref.table,
+        };
+        push_unique(&mut names, name);
+    }
+    names
+}
+
+/// Columns referenced anywhere in the query and aliases in scope at
+/// `cursor`, leaving out the word being typed.
+fn expression_names(tree: &SyntaxTree, sql: &str, cursor: usize) -> Vec<String> {
+    let mut names = Vec::new();
+    if let Some(scope) = build_scope_at(tree, sql, cursor as u32) {
+        for alias in scope.column_aliases {
+            push_unique(&mut names, alias.name);
+        }
+    }
+    for column in tree.find_all(SyntaxKind::ColumnReference) {
+        if column.start as usize <= cursor && cursor <= column.end as usize {
+            continue;
+        }
+        if let Some(name) = column_path(column, sql) {
+            push_unique(&mut names, name);
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(sql: &str) -> Vec<String> {
+        let cursor = sql.find('|').unwrap();
+        let sql = sql.replace('|', "");
+        completions(&sql, cursor).into_iter().map(|c| c.text).collect()
+    }
+
+    #[test]
+    fn clauses_after_select_list() {
+        let candidates = texts("SELECT x |");
+        assert_eq!(candidates[..3], ["AS", "FROM", "PREWHERE"]);
+        assert!(candidates.contains(&"SETTINGS".to_string()));
+        assert!(!candidates.contains(&"JOIN".to_string()));
+    }
+
+    #[test]
+    fn clauses_after_from() {
+        let candidates = texts("SELECT x FROM t |");
+        for expected in ["WHERE", "JOIN", "LEFT JOIN", "GROUP BY", "ORDER BY", "LIMIT"] {
+            assert!(candidates.contains(&expected.to_string()), "missing {expected}");
+        }
+        assert!(!candidates.contains(&"FROM".to_string()));
+    }
+
+    #[test]
+    fn only_later_clauses_after_where() {
+        let candidates = texts("SELECT x FROM t WHERE a = 1 |");
+        assert_eq!(candidates[..2], ["AND", "OR"]);
+        assert!(candidates.contains(&"GROUP BY".to_string()));
+        assert!(!candidates.contains(&"WHERE".to_string()));
+        assert!(!candidates.contains(&"PREWHERE".to_string()));
+    }
+
+    #[test]
+    fn partial_word_filters() {
+        assert_eq!(texts("SELECT x FROM t WHERE a = 1 gr|"), ["GROUP BY"]);
+        assert_eq!(texts("SELECT x FROM t o|"), ["ORDER BY"]);
+        assert_eq!(texts("SELECT x FROM t ORDER |"), ["BY"]);
+        assert_eq!(texts("sel|"), ["SELECT"]);
+    }
+
+    #[test]
+    fn identifiers_in_operand_position() {
+        let completions = {
+            let sql = "SELECT amount, a|  FROM orders WHERE amount > 0 AND account_id = 1";
+            let cursor = sql.find('|').unwrap();
+            completions(&sql.replace('|', ""), cursor)
+        };
+        assert_eq!(
+            completions,
+            [
+                Completion { text: "amount".to_string(), kind: CompletionKind::Identifier },
+                Completion { text: "account_id".to_string(), kind: CompletionKind::Identifier },
+            ]
+        );
+    }
+
+    #[test]
+    fn tables_after_from() {
+        assert_eq!(
+            texts("WITH c AS (SELECT 1) SELECT * FROM db.t JOIN |"),
+            ["c", "db.t"]
+        );
+    }
+
+    #[test]
+    fn subquery_is_its_own_statement() {
+        let candidates = texts("SELECT * FROM (SELECT a |");
+        assert_eq!(candidates[..2], ["AS", "FROM"]);
+    }
+
+    #[test]
+    fn nothing_inside_a_string() {
+        assert!(texts("SELECT 'ab|c'").is_empty());
+    }
+
+    #[test]
+    fn nothing_inside_a_comment() {
+        assert!(texts("SELECT x -- c|").is_empty());
+        assert!(texts("SELECT x -- c|omment\nFROM t").is_empty());
+        assert!(texts("SELECT x /* c| */").is_empty());
+        assert!(texts("SELECT x -- c\n|").contains(&"FROM".to_string()));
+        assert!(texts("SELECT x /* c */ |").contains(&"FROM".to_string()));
+    }
+
+    #[test]
+    fn cursor_inside_a_multibyte_character() {
+        let sql = "SELECT 'é' AS ñ, `ü` FROM tà ";
+        let inside = sql.find('à').unwrap() + 1;
+        assert!(!sql.is_char_boundary(inside));
+        // Same as a cursor right before `à`: `t` is the prefix.
+        assert_eq!(completions(sql, inside), completions(sql, inside - 1));
+        assert!(texts("SELECT `ñx` FROM t WHERE |").contains(&"ñx".to_string()));
+    }
+}
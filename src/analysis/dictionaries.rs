@@ -0,0 +1,59 @@
+use crate::analysis::aggregates::function_call_name;
+use crate::analysis::literals::string_value;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Dictionaries read through `dictGet` family calls (`dictGet`,
+/// `dictGetString`, `dictGetOrDefault`, ...), deduplicated, in source order.
+/// Only a string literal first argument names a dictionary; a computed name
+/// can't be resolved statically and is skipped.
+pub fn dictionary_names(tree: &SyntaxTree, source: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for call in tree.find_all(SyntaxKind::FunctionCall) {
+        if !function_call_name(call, source).is_some_and(|name| name.starts_with("dictGet")) {
+            continue;
+        }
+        if let Some(name) = first_string_argument(call, source) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// The unquoted text of the first argument if it is a string literal.
+fn first_string_argument(call: &SyntaxTree, source: &str) -> Option<String> {
+    let args = call
+        .children
+        .iter()
+        .find_map(|child| child.get_tree_with_kind(SyntaxKind::ExpressionList))?;
+    let first = args.children.iter().find_map(SyntaxChild::as_tree)?;
+    let literal = first
+        .children
+        .iter()
+        .find_map(|child| child.get_tree_with_kind(SyntaxKind::StringLiteral))?;
+    string_value(literal, source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn collects_dict_get_dictionaries() {
+        let sql = "SELECT dictGet('my_dict', 'attr', id) FROM t";
+        let result = parse(sql);
+        assert_eq!(dictionary_names(&result.tree, sql), ["my_dict"]);
+    }
+
+    #[test]
+    fn dict_get_variants_deduplicated() {
+        let sql = "SELECT dictGetString('db.users', 'name', uid), \
+            dictGetOrDefault('geo', 'city', ip, ''), dictGet('db.users', 'age', uid), \
+            dictGet(dict_name, 'x', 1), get('other') FROM t";
+        let result = parse(sql);
+        assert_eq!(dictionary_names(&result.tree, sql), ["db.users", "geo"]);
+    }
+}
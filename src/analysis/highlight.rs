@@ -0,0 +1,142 @@
+use crate::lexer::tokenizer::is_lex_error;
+use crate::parser::keyword::Keyword;
+use crate::parser::parse;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Coarse token categories for syntax highlighting in a web editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "lowercase"))]
+pub enum HighlightCategory {
+    Keyword,
+    Identifier,
+    String,
+    Number,
+    Comment,
+    Operator,
+    Error,
+}
+
+/// One highlighted token, by byte range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HighlightSpan {
+    pub start: usize,
+    pub end: usize,
+    pub category: HighlightCategory,
+}
+
+/// Categorize every token of `sql` except whitespace, in source order.
+///
+/// A bare word counts as a keyword only where the parser didn't place it as
+/// a name, so `date` in `SELECT date FROM t` is an identifier.
+pub fn highlight(sql: &str) -> Vec<HighlightSpan> {
+    let result = parse(sql);
+    let mut spans = Vec::new();
+    collect(&result.tree, sql, &mut spans);
+    spans
+}
+
+fn collect(tree: &SyntaxTree, source: &str, spans: &mut Vec<HighlightSpan>) {
+    for child in &tree.children {
+        match child {
+            SyntaxChild::Tree(subtree) => collect(subtree, source, spans),
+            SyntaxChild::Token(token) => {
+                let category = match token.kind {
+                    SyntaxKind::BareWord => classify_bareword(token.text(source), tree.kind),
+                    kind => match category(kind) {
+                        Some(category) => category,
+                        None => continue,
+                    },
+                };
+                spans.push(HighlightSpan {
+                    start: token.start as usize,
+                    end: token.end as usize,
+                    category,
+                });
+            }
+        }
+    }
+}
+
+/// The category of a token by kind alone, or `None` for whitespace.
+fn category(kind: SyntaxKind) -> Option<HighlightCategory> {
+    let category = match kind {
+        SyntaxKind::Whitespace | SyntaxKind::EndOfStream => return None,
+        SyntaxKind::Comment => HighlightCategory::Comment,
+        SyntaxKind::Number => HighlightCategory::Number,
+        SyntaxKind::StringToken | SyntaxKind::HereDoc => HighlightCategory::String,
+        SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier => HighlightCategory::Identifier,
+        kind if is_lex_error(kind) => HighlightCategory::Error,
+        // Brackets, punctuation and operators.
+        _ => HighlightCategory::Operator,
+    };
+    Some(category)
+}
+
+fn classify_bareword(text: &str, parent: SyntaxKind) -> HighlightCategory {
+    let is_name = match parent {
+        SyntaxKind::ColumnReference
+        | SyntaxKind::Identifier
+        | SyntaxKind::QualifiedName
+        | SyntaxKind::TableIdentifier => true,
+        // Alias nodes also hold the `AS` keyword.
+        SyntaxKind::ColumnAlias | SyntaxKind::TableAlias => !text.eq_ignore_ascii_case("AS"),
+        _ => false,
+    };
+    if !is_name && Keyword::from_text(text).is_some() {
+        HighlightCategory::Keyword
+    } else {
+        HighlightCategory::Identifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::{expect, Expect};
+
+    fn check(sql: &str, expected: Expect) {
+        let actual: String = highlight(sql)
+            .iter()
+            .map(|span| format!("{:?} {:?}\n", &sql[span.start..span.end], span.category))
+            .collect();
+        expected.assert_eq(&actual);
+    }
+
+    #[test]
+    fn categorizes_sample_query() {
+        check(
+            "SELECT date, count() AS n FROM t WHERE s = 'x' AND n > 1 -- done",
+            expect![[r#"
+                "SELECT" Keyword
+                "date" Identifier
+                "," Operator
+                "count" Identifier
+                "(" Operator
+                ")" Operator
+                "AS" Keyword
+                "n" Identifier
+                "FROM" Keyword
+                "t" Identifier
+                "WHERE" Keyword
+                "s" Identifier
+                "=" Operator
+                "'x'" String
+                "AND" Keyword
+                "n" Identifier
+                ">" Operator
+                "1" Number
+                "-- done" Comment
+            "#]],
+        );
+    }
+
+    #[test]
+    fn lex_errors() {
+        check("SELECT 'open", expect![[r#"
+            "SELECT" Keyword
+            "'open" Error
+        "#]]);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// A column name as written in the query, not yet resolved against any
+/// table. Function names, aliases and table names are not identifiers here.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentifierRef<'a> {
+    /// Source text, e.g. `b` or `t.b`, quotes included.
+    pub text: &'a str,
+    /// True if the name has a qualifier (`t.b`, `db.t.b`).
+    pub qualified: bool,
+    /// Byte range of the name, excluding trailing trivia.
+    pub span: (u32, u32),
+}
+
+/// Every `ColumnReference` and `QualifiedName` in the tree, in source order.
+pub fn identifiers<'a>(tree: &SyntaxTree, source: &'a str) -> Vec<IdentifierRef<'a>> {
+    let mut out = Vec::new();
+    collect(tree, source, &mut out);
+    out
+}
+
+fn collect<'a>(tree: &SyntaxTree, source: &'a str, out: &mut Vec<IdentifierRef<'a>>) {
+    if matches!(tree.kind, SyntaxKind::ColumnReference | SyntaxKind::QualifiedName) {
+        let text = source[tree.start as usize..tree.end as usize].trim_end();
+        out.push(IdentifierRef {
+            text,
+            qualified: tree
+                .children
+                .iter()
+                .any(|child| child.get_token_with_kind(SyntaxKind::Dot).is_some()),
+            span: (tree.start, tree.start + text.len() as u32),
+        });
+        return;
+    }
+    for subtree in tree.children.iter().filter_map(SyntaxChild::as_tree) {
+        collect(subtree, source, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn columns_but_not_functions_or_aliases() {
+        let sql = "SELECT a, t.b, f(c) AS d FROM t";
+        let result = parse(sql);
+        let found: Vec<(&str, bool, (u32, u32))> = identifiers(&result.tree, sql)
+            .into_iter()
+            .map(|i| (i.text, i.qualified, i.span))
+            .collect();
+        assert_eq!(
+            found,
+            [("a", false, (7, 8)), ("t.b", true, (10, 13)), ("c", false, (17, 18))]
+        );
+    }
+
+    #[test]
+    fn identifiers_in_every_clause() {
+        let sql = "SELECT `x` FROM t WHERE db.t.y > 0 ORDER BY z";
+        let result = parse(sql);
+        let texts: Vec<&str> = identifiers(&result.tree, sql).iter().map(|i| i.text).collect();
+        assert_eq!(texts, ["`x`", "db.t.y", "z"]);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// The elements of a literal `IN` set, as source text. The tuple form
+/// `x IN (1, 2)` and the array form `x IN [1, 2]` give the same result;
+/// a tuple element of a multi-column set (`(a, b) IN ((1, 2), (3, 4))`)
+/// comes back whole, e.g. `(1, 2)`.
+///
+/// Returns `None` for anything but an `InExpression` and for sets that are
+/// not written out in the query: subqueries, table names and columns.
+pub fn in_set_values<'a>(in_expr: &SyntaxTree, source: &'a str) -> Option<Vec<&'a str>> {
+    if in_expr.kind != SyntaxKind::InExpression {
+        return None;
+    }
+    // Everything after the IN keyword is the set.
+    let in_keyword = in_expr.children.iter().position(|child| {
+        child
+            .as_token()
+            .is_some_and(|token| token.text(source).eq_ignore_ascii_case("IN"))
+    })?;
+    let set = &in_expr.children[in_keyword + 1..];
+
+    let elements: Vec<&SyntaxTree> = match set.iter().find(|child| !child.is_trivia())? {
+        SyntaxChild::Token(token) if token.kind == SyntaxKind::OpeningRoundBracket => {
+            set.iter().filter_map(SyntaxChild::as_tree).collect()
+        }
+        SyntaxChild::Tree(tree)
+            if matches!(tree.kind, SyntaxKind::ArrayExpression | SyntaxKind::TupleExpression) =>
+        {
+            tree.children.iter().filter_map(SyntaxChild::as_tree).collect()
+        }
+        _ => return None,
+    };
+    if elements
+        .iter()
+        .any(|element| element.kind == SyntaxKind::SubqueryExpression)
+    {
+        return None;
+    }
+    Some(
+        elements
+            .into_iter()
+            .map(|element| {
+                let (start, end) = element.significant_span().unwrap_or(element.span());
+                &source[start as usize..end as usize]
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn values(sql: &str) -> Option<Vec<String>> {
+        let result = parse(sql);
+        let in_expr = result.tree.find_first(SyntaxKind::InExpression)?;
+        in_set_values(in_expr, sql).map(|values| values.into_iter().map(String::from).collect())
+    }
+
+    #[test]
+    fn tuple_and_array_forms_agree() {
+        let expected = Some(vec!["1".to_string(), "'a'".to_string(), "3".to_string()]);
+        assert_eq!(values("SELECT 1 WHERE x IN (1, 'a', 3)"), expected);
+        assert_eq!(values("SELECT 1 WHERE x IN [1, 'a', 3]"), expected);
+        assert_eq!(values("SELECT 1 WHERE x NOT IN (1, 'a', 3)"), expected);
+    }
+
+    #[test]
+    fn single_value_and_tuples() {
+        assert_eq!(values("SELECT 1 WHERE x IN (1)"), Some(vec!["1".to_string()]));
+        assert_eq!(
+            values("SELECT 1 WHERE (a, b) IN ((1, 2), (3, 4))"),
+            Some(vec!["(1, 2)".to_string(), "(3, 4)".to_string()])
+        );
+    }
+
+    #[test]
+    fn sets_not_written_in_the_query() {
+        assert_eq!(values("SELECT 1 WHERE x IN (SELECT id FROM t)"), None);
+        assert_eq!(values("SELECT 1 WHERE x IN t"), None);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::analysis::scope::normalize_identifier;
+use crate::analysis::validate::column_path;
+use crate::lexer::token::Token;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// An equality between a column on each side of a join: one column of a
+/// `JOIN ... USING` list, expanded to the equality it stands for (`JOIN b
+/// USING (x)` after `FROM a` means `a.x = b.x`), or one `a.x = b.y` of an
+/// `ON` condition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinKey {
+    /// The column on the left side, qualified by the alias or table name of
+    /// the table expression before the join, e.g. `a.x`. Unqualified when
+    /// that side has no name (an unaliased subquery).
+    pub left: String,
+    /// The same column on the joined side, e.g. `b.x`.
+    pub right: String,
+    /// Byte range of the column in the USING list, or of the equality in the
+    /// ON condition, excluding trailing trivia.
+    pub span: (u32, u32),
+}
+
+/// The keys of every `USING` join in the tree, in source order. For a chain
+/// of joins the left side is the table expression written just before the
+/// join, although ClickHouse looks the column up in all the tables joined
+/// so far.
+pub fn using_join_keys(tree: &SyntaxTree, source: &str) -> Vec<JoinKey> {
+    let mut keys = Vec::new();
+    for select in tree.find_all(SyntaxKind::SelectStatement) {
+        let mut previous: Option<Option<String>> = None;
+        for clause in select.children.iter().filter_map(SyntaxChild::as_tree) {
+            match clause.kind {
+                SyntaxKind::FromClause => previous = Some(side_name(clause, source)),
+                SyntaxKind::JoinClause => {
+                    let right = side_name(clause, source);
+                    if let Some(left) = &previous {
+                        push_using_keys(
+                            &mut keys,
+                            clause,
+                            left.as_deref(),
+                            right.as_deref(),
+                            source,
+                        );
+                    }
+                    previous = Some(right);
+                }
+                _ => {}
+            }
+        }
+    }
+    keys
+}
+
+fn push_using_keys(
+    keys: &mut Vec<JoinKey>,
+    join: &SyntaxTree,
+    left: Option<&str>,
+    right: Option<&str>,
+    source: &str,
+) {
+    let Some(constraint) = join_constraint(join) else {
+        return;
+    };
+    let after_using = constraint.children.iter().skip_while(|child| {
+        !matches!(child, SyntaxChild::Token(token)
+            if token.text(source).eq_ignore_ascii_case("USING"))
+    });
+    for column in
+        after_using.filter_map(|child| child.get_tree_with_kind(SyntaxKind::ColumnReference))
+    {
+        let Some(token) = last_name_token(column) else {
+            continue;
+        };
+        let name = normalize_identifier(token, source);
+        let qualify = |side: Option<&str>| match side {
+            Some(side) => format!("{side}.{name}"),
+            None => name.clone(),
+        };
+        keys.push(JoinKey {
+            left: qualify(left),
+            right: qualify(right),
+            span: (token.start, token.end),
+        });
+    }
+}
+
+/// The equi-join keys of every `ON` join in the tree, in source order:
+/// each `column = column` of the condition, looking through `AND` and
+/// parentheses. Other conditions, and anything under `OR`, are ignored.
+/// Columns are returned as written, e.g. `a.id`.
+pub fn on_join_keys(tree: &SyntaxTree, source: &str) -> Vec<JoinKey> {
+    let mut keys = Vec::new();
+    for join in tree.find_all(SyntaxKind::JoinClause) {
+        let condition = join_constraint(join)
+            .into_iter()
+            .flat_map(|constraint| constraint.children.iter())
+            .skip_while(|child| {
+                !matches!(child, SyntaxChild::Token(token)
+                    if token.text(source).eq_ignore_ascii_case("ON"))
+            })
+            .find_map(SyntaxChild::as_tree);
+        if let Some(condition) = condition {
+            push_on_keys(&mut keys, condition, source);
+        }
+    }
+    keys
+}
+
+fn push_on_keys(keys: &mut Vec<JoinKey>, expr: &SyntaxTree, source: &str) {
+    match expr.kind {
+        SyntaxKind::Expression => {
+            if let Some(inner) = expr.children.iter().find_map(SyntaxChild::as_tree) {
+                push_on_keys(keys, inner, source);
+            }
+        }
+        SyntaxKind::BinaryExpression => {
+            let operator = expr
+                .children
+                .iter()
+                .filter_map(SyntaxChild::as_token)
+                .find(|token| !matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Comment));
+            let operands: Vec<&SyntaxTree> =
+                expr.children.iter().filter_map(SyntaxChild::as_tree).collect();
+            let [lhs, rhs] = operands[..] else {
+                return;
+            };
+            match operator {
+                Some(token) if token.text(source).eq_ignore_ascii_case("AND") => {
+                    push_on_keys(keys, lhs, source);
+                    push_on_keys(keys, rhs, source);
+                }
+                Some(token) if token.kind == SyntaxKind::Equals => {
+                    if let (Some(left), Some(right)) =
+                        (column_path(lhs, source), column_path(rhs, source))
+                    {
+                        keys.push(JoinKey {
+                            left,
+                            right,
+                            span: expr.significant_span().unwrap_or(expr.span()),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The `ON` or `USING` part of a JOIN clause.
+fn join_constraint(join: &SyntaxTree) -> Option<&SyntaxTree> {
+    join.children
+        .iter()
+        .find_map(|child| child.get_tree_with_kind(SyntaxKind::JoinConstraint))
+}
+
+/// The name a table expression is referred to by: its alias if it has one,
+/// otherwise the table name.
+fn side_name(clause: &SyntaxTree, source: &str) -> Option<String> {
+    let tree = |kind| {
+        clause
+            .children
+            .iter()
+            .find_map(|child| child.get_tree_with_kind(kind))
+    };
+    let named = tree(SyntaxKind::TableAlias).or_else(|| tree(SyntaxKind::TableIdentifier))?;
+    last_name_token(named).map(|token| normalize_identifier(token, source))
+}
+
+fn last_name_token(tree: &SyntaxTree) -> Option<&Token> {
+    tree.children
+        .iter()
+        .rev()
+        .filter_map(SyntaxChild::as_token)
+        .find(|token| matches!(token.kind, SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn keys(sql: &str) -> Vec<(String, String)> {
+        let result = parse(sql);
+        using_join_keys(&result.tree, sql)
+            .into_iter()
+            .map(|key| (key.left, key.right))
+            .collect()
+    }
+
+    #[test]
+    fn expands_using_columns() {
+        assert_eq!(
+            keys("SELECT * FROM db.a AS l JOIN b USING (x, y)"),
+            [
+                ("l.x".to_string(), "b.x".to_string()),
+                ("l.y".to_string(), "b.y".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn chained_joins_and_unnamed_sides() {
+        assert_eq!(
+            keys(
+                "SELECT * FROM (SELECT 1 AS id) JOIN numbers(3) AS n USING id \
+                LEFT JOIN `q t` USING (id) JOIN c ON c.id = n.id"
+            ),
+            [
+                ("id".to_string(), "n.id".to_string()),
+                ("n.id".to_string(), "q t.id".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn on_condition_equalities() {
+        let sql = "SELECT * FROM a JOIN b ON a.id = b.id AND a.k = b.k";
+        let result = parse(sql);
+        let keys = on_join_keys(&result.tree, sql);
+        let pairs: Vec<(&str, &str)> = keys
+            .iter()
+            .map(|key| (key.left.as_str(), key.right.as_str()))
+            .collect();
+        assert_eq!(pairs, [("a.id", "b.id"), ("a.k", "b.k")]);
+        let (start, end) = keys[1].span;
+        assert_eq!(&sql[start as usize..end as usize], "a.k = b.k");
+    }
+
+    #[test]
+    fn on_condition_skips_non_equi_conditions() {
+        let sql = "SELECT * FROM a LEFT JOIN b ON (a.id = b.id) AND a.x > 1 AND b.y = 2 \
+            JOIN c ON a.p = c.p OR a.q = c.q JOIN d USING (z)";
+        let result = parse(sql);
+        let pairs: Vec<(String, String)> = on_join_keys(&result.tree, sql)
+            .into_iter()
+            .map(|key| (key.left, key.right))
+            .collect();
+        assert_eq!(pairs, [("a.id".to_string(), "b.id".to_string())]);
+    }
+}
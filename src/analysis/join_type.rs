@@ -0,0 +1,88 @@
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// The join type of a `JoinClause`, with redundant words normalized away:
+/// `LEFT OUTER JOIN` and `LEFT JOIN` are both `Left`, and `JOIN` and
+/// `INNER JOIN` are both `Inner`. Strictness (`ANY`, `ALL`, `ASOF`, `SEMI`,
+/// `ANTI`) and `GLOBAL` don't change the type. The tree keeps the tokens as
+/// written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Cross,
+    /// `NATURAL JOIN`, matched on all common column names.
+    Natural,
+}
+
+/// The join type of a `JoinClause` node, or `None` for any other node.
+pub fn join_type(join: &SyntaxTree, source: &str) -> Option<JoinType> {
+    if join.kind != SyntaxKind::JoinClause {
+        return None;
+    }
+    let words = join
+        .children
+        .iter()
+        .find_map(|child| child.get_tree_with_kind(SyntaxKind::JoinType))?
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_token)
+        .map(|token| token.text(source))
+        .take_while(|word| !word.eq_ignore_ascii_case("JOIN"));
+    for word in words {
+        let join_type = match word.to_ascii_uppercase().as_str() {
+            "LEFT" => JoinType::Left,
+            "RIGHT" => JoinType::Right,
+            "FULL" => JoinType::Full,
+            "CROSS" => JoinType::Cross,
+            "NATURAL" => JoinType::Natural,
+            _ => continue,
+        };
+        return Some(join_type);
+    }
+    Some(JoinType::Inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn join_types(sql: &str) -> Vec<Option<JoinType>> {
+        let result = parse(sql);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        result
+            .tree
+            .find_all(SyntaxKind::JoinClause)
+            .into_iter()
+            .map(|join| join_type(join, sql))
+            .collect()
+    }
+
+    #[test]
+    fn outer_and_inner_are_normalized() {
+        assert_eq!(
+            join_types("SELECT * FROM a LEFT OUTER JOIN b ON a.x = b.x LEFT JOIN c USING x"),
+            [Some(JoinType::Left), Some(JoinType::Left)]
+        );
+        assert_eq!(
+            join_types("SELECT * FROM a INNER JOIN b USING x JOIN c USING x"),
+            [Some(JoinType::Inner), Some(JoinType::Inner)]
+        );
+        assert_eq!(
+            join_types("SELECT * FROM a FULL OUTER JOIN b USING x RIGHT OUTER JOIN c USING x"),
+            [Some(JoinType::Full), Some(JoinType::Right)]
+        );
+    }
+
+    #[test]
+    fn strictness_and_global_are_ignored() {
+        assert_eq!(
+            join_types("SELECT * FROM a GLOBAL ANY LEFT JOIN b USING x ALL INNER JOIN c USING x \
+                CROSS JOIN d"),
+            [Some(JoinType::Left), Some(JoinType::Inner), Some(JoinType::Cross)]
+        );
+    }
+}
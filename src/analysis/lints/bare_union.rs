@@ -0,0 +1,82 @@
+use crate::diagnostics::{Diagnostic, Severity, Suggestion};
+use crate::lexer::token::Token;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Flag `UNION` written without `ALL` or `DISTINCT`. ClickHouse resolves the
+/// bare form through the `union_default_mode` setting, so the same query can
+/// deduplicate, keep duplicates, or fail depending on the server.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    for union in tree.find_all(SyntaxKind::UnionClause) {
+        let mut keywords = union
+            .children
+            .iter()
+            .filter_map(SyntaxChild::as_token)
+            .filter(|token| token.kind == SyntaxKind::BareWord);
+        let Some(operator) = keywords.next() else {
+            continue;
+        };
+        if !operator.text(source).eq_ignore_ascii_case("UNION") {
+            continue;
+        }
+        if keywords.next().is_some_and(|t| is_quantifier(t, source)) {
+            continue;
+        }
+
+        diagnostics.push(Diagnostic {
+            message: "UNION without ALL or DISTINCT depends on the union_default_mode setting"
+                .to_string(),
+            range: (operator.start as usize, operator.end as usize),
+            severity: Severity::Warning,
+            code: Some("bare-union"),
+            suggestion: Some(Suggestion {
+                message: "Write UNION ALL or UNION DISTINCT".to_string(),
+                replacement: None,
+            }),
+            related: Vec::new(),
+        });
+    }
+}
+
+fn is_quantifier(token: &Token, source: &str) -> bool {
+    let text = token.text(source);
+    text.eq_ignore_ascii_case("ALL") || text.eq_ignore_ascii_case("DISTINCT")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::lints::check_lint;
+    use expect_test::{expect, Expect};
+
+    fn check(input: &str, expected: Expect) {
+        check_lint("bare-union", input, expected);
+    }
+
+    #[test]
+    fn flags_bare_union() {
+        check(
+            "SELECT 1 UNION SELECT 2 UNION ALL SELECT 3",
+            expect![[r#"
+                9..14: UNION without ALL or DISTINCT depends on the union_default_mode setting
+            "#]],
+        );
+    }
+
+    #[test]
+    fn explicit_forms_are_fine() {
+        check(
+            "SELECT 1 UNION ALL SELECT 2 UNION DISTINCT SELECT 3 EXCEPT SELECT 4",
+            expect![[""]],
+        );
+    }
+
+    #[test]
+    fn flags_bare_union_in_subquery() {
+        check(
+            "SELECT * FROM (SELECT 1 union SELECT 2)",
+            expect![[r#"
+                24..29: UNION without ALL or DISTINCT depends on the union_default_mode setting
+            "#]],
+        );
+    }
+}
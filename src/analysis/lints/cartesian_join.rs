@@ -0,0 +1,88 @@
+use crate::diagnostics::{Diagnostic, Severity, Suggestion};
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Join kinds that take no constraint on purpose.
+const UNCONSTRAINED_JOINS: &[&str] = &["CROSS", "PASTE"];
+
+/// Flag a JOIN with neither ON nor USING: every row pairs with every row of
+/// the other side, which is rarely what the author meant. `CROSS JOIN` and
+/// `PASTE JOIN` say so explicitly and are skipped. Comma joins
+/// (`FROM a, b`) are not a JOIN clause and are not flagged either.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    for join in tree.find_all(SyntaxKind::JoinClause) {
+        // A half-written join is already a parse error.
+        if join.find_first(SyntaxKind::Error).is_some() {
+            continue;
+        }
+
+        let child = |kind| {
+            join.children
+                .iter()
+                .find_map(|child| child.get_tree_with_kind(kind))
+        };
+        if child(SyntaxKind::JoinConstraint).is_some() {
+            continue;
+        }
+        let unconstrained = child(SyntaxKind::JoinType).is_some_and(|join_type| {
+            join_type
+                .children
+                .iter()
+                .filter_map(SyntaxChild::as_token)
+                .any(|token| {
+                    UNCONSTRAINED_JOINS
+                        .iter()
+                        .any(|word| token.text(source).eq_ignore_ascii_case(word))
+                })
+        });
+        if unconstrained {
+            continue;
+        }
+
+        let (start, end) = join.significant_span().unwrap_or(join.span());
+        diagnostics.push(Diagnostic {
+            message: "JOIN without ON or USING produces a cartesian product".to_string(),
+            range: (start as usize, end as usize),
+            severity: Severity::Warning,
+            code: Some("cartesian-join"),
+            suggestion: Some(Suggestion {
+                message: "Add a join condition, or write CROSS JOIN if this is intended"
+                    .to_string(),
+                replacement: None,
+            }),
+            related: Vec::new(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::lints::check_lint;
+    use expect_test::{expect, Expect};
+
+    fn check(input: &str, expected: Expect) {
+        check_lint("cartesian-join", input, expected);
+    }
+
+    #[test]
+    fn flags_join_without_condition() {
+        check(
+            "SELECT * FROM a INNER JOIN b WHERE a.x = 1",
+            expect![[r#"
+                16..28: JOIN without ON or USING produces a cartesian product
+            "#]],
+        );
+    }
+
+    #[test]
+    fn join_with_condition_is_fine() {
+        check("SELECT * FROM a JOIN b ON a.id = b.id", expect![[""]]);
+        check("SELECT * FROM a LEFT JOIN b USING (id)", expect![[""]]);
+    }
+
+    #[test]
+    fn explicit_cross_join_is_fine() {
+        check("SELECT * FROM a CROSS JOIN b", expect![[""]]);
+        check("SELECT * FROM a, b", expect![[""]]);
+    }
+}
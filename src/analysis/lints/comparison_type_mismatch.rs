@@ -0,0 +1,188 @@
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::lexer::token::Token;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Flag comparisons between a `CAST` and a literal that can't convert to the
+/// cast type: `CAST(x AS String) = 123`, `x::Int32 = 'abc'`,
+/// `x::Date = 'yesterday'`.
+///
+/// Only the cast side's type is known without a schema, so a plain column
+/// compared with a literal is never flagged. `Nullable(...)` and
+/// `LowCardinality(...)` wrappers are looked through.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    for comparison in tree.find_all(SyntaxKind::BinaryExpression) {
+        let mut operands = comparison.children.iter().filter_map(SyntaxChild::as_tree);
+        let (Some(lhs), Some(rhs)) = (operands.next(), operands.next()) else {
+            continue;
+        };
+        let is_comparison = comparison
+            .children
+            .iter()
+            .filter_map(SyntaxChild::as_token)
+            .any(|token| is_comparison_operator(token.kind));
+        if !is_comparison {
+            continue;
+        }
+
+        let (cast, literal) = match (lhs.kind, rhs.kind) {
+            (SyntaxKind::CastExpression, _) => (lhs, rhs),
+            (_, SyntaxKind::CastExpression) => (rhs, lhs),
+            _ => continue,
+        };
+        let Some(type_name) = cast_type(cast, source) else {
+            continue;
+        };
+        let Some(token) = literal_token(literal) else {
+            continue;
+        };
+        if converts(type_category(&type_name), literal.kind, token.text(source)) {
+            continue;
+        }
+
+        let (start, end) = comparison.significant_span().unwrap_or(comparison.span());
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "`{}` can't be compared with a value of type {type_name}",
+                token.text(source)
+            ),
+            range: (start as usize, end as usize),
+            severity: Severity::Warning,
+            code: Some("comparison-type-mismatch"),
+            suggestion: None,
+            related: Vec::new(),
+        });
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TypeCategory {
+    Number,
+    String,
+    Date,
+    Other,
+}
+
+fn is_comparison_operator(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::Equals
+            | SyntaxKind::NotEquals
+            | SyntaxKind::Less
+            | SyntaxKind::Greater
+            | SyntaxKind::LessOrEquals
+            | SyntaxKind::GreaterOrEquals
+    )
+}
+
+/// The target type of `CAST(x AS T)`, `x::T` or `CAST(x, 'T')`, as written.
+fn cast_type(cast: &SyntaxTree, source: &str) -> Option<String> {
+    if let Some(data_type) = cast
+        .children
+        .iter()
+        .find_map(|child| child.get_tree_with_kind(SyntaxKind::DataType))
+    {
+        let text = &source[data_type.start as usize..data_type.end as usize];
+        return Some(text.trim().to_string());
+    }
+    let literal = cast
+        .children
+        .iter()
+        .rev()
+        .find_map(|child| child.get_tree_with_kind(SyntaxKind::StringLiteral))?;
+    let text = literal_token(literal)?.text(source);
+    Some(text.trim_matches('\'').to_string())
+}
+
+fn type_category(type_name: &str) -> TypeCategory {
+    let mut name = type_name.trim();
+    while let Some(inner) = ["Nullable(", "LowCardinality("]
+        .iter()
+        .find_map(|wrapper| name.strip_prefix(wrapper))
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        name = inner.trim();
+    }
+    let base = name.split('(').next().unwrap_or(name).trim().to_ascii_lowercase();
+    if ["int", "uint", "float", "decimal"].iter().any(|p| base.starts_with(p))
+        || ["bigint", "smallint", "tinyint", "double", "real"].contains(&base.as_str())
+    {
+        TypeCategory::Number
+    } else if ["string", "fixedstring", "varchar", "char", "text"].contains(&base.as_str()) {
+        TypeCategory::String
+    } else if base.starts_with("date") {
+        TypeCategory::Date
+    } else {
+        TypeCategory::Other
+    }
+}
+
+fn literal_token(literal: &SyntaxTree) -> Option<&Token> {
+    if !matches!(literal.kind, SyntaxKind::NumberLiteral | SyntaxKind::StringLiteral) {
+        return None;
+    }
+    literal
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_token)
+        .find(|token| token.kind != SyntaxKind::Whitespace)
+}
+
+/// True if ClickHouse can convert the literal to a type of this category.
+fn converts(category: TypeCategory, literal: SyntaxKind, text: &str) -> bool {
+    let string = (literal == SyntaxKind::StringLiteral).then(|| text.trim_matches('\''));
+    match (category, string) {
+        (TypeCategory::String, None) => false,
+        (TypeCategory::Number, Some(value)) => value.trim().parse::<f64>().is_ok(),
+        (TypeCategory::Date, Some(value)) => looks_like_date(value),
+        _ => true,
+    }
+}
+
+/// `YYYY-MM-DD...` or a Unix timestamp.
+fn looks_like_date(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    let digits = |range: std::ops::Range<usize>| {
+        bytes.get(range).is_some_and(|b| b.iter().all(u8::is_ascii_digit))
+    };
+    let all_digits = !bytes.is_empty() && bytes.iter().all(u8::is_ascii_digit);
+    all_digits
+        || (digits(0..4)
+            && bytes.get(4) == Some(&b'-')
+            && digits(5..7)
+            && bytes.get(7) == Some(&b'-')
+            && digits(8..10))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::lints::check_lint;
+    use expect_test::{expect, Expect};
+
+    fn check(input: &str, expected: Expect) {
+        check_lint("comparison-type-mismatch", input, expected);
+    }
+
+    #[test]
+    fn flags_mismatched_literals() {
+        check(
+            "SELECT * FROM t WHERE CAST(a AS String) = 123 OR 'abc' < b::Nullable(Int32) \
+             OR c::Date = 'yesterday' OR CAST(d, 'UInt8') != 'x'",
+            expect![[r#"
+                22..45: `123` can't be compared with a value of type String
+                49..75: `'abc'` can't be compared with a value of type Nullable(Int32)
+                79..100: `'yesterday'` can't be compared with a value of type Date
+                104..127: `'x'` can't be compared with a value of type UInt8
+            "#]],
+        );
+    }
+
+    #[test]
+    fn convertible_literals_are_fine() {
+        check(
+            "SELECT * FROM t WHERE CAST(a AS String) = '123' AND b::Int32 = '42' \
+             AND c::DateTime >= '2024-01-01 00:00:00' AND d::Date = 19000 AND e = 'x'",
+            expect![[""]],
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::diagnostics::{Diagnostic, Severity, Suggestion};
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Flag `DISTINCT ON (...)` whose expressions don't lead the ORDER BY.
+/// ClickHouse keeps the first row of each group in result order, so unless
+/// ORDER BY starts with the DISTINCT ON keys, which row survives is arbitrary.
+///
+/// Expressions are compared token by token, ignoring whitespace and
+/// comments; the leading ORDER BY items may list the keys in any order. A SELECT without
+/// ORDER BY is left alone.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    for select in tree.find_all(SyntaxKind::SelectStatement) {
+        let clause = |kind| {
+            select
+                .children
+                .iter()
+                .find_map(|child| child.get_tree_with_kind(kind))
+        };
+        let (Some(select_clause), Some(order_by)) =
+            (clause(SyntaxKind::SelectClause), clause(SyntaxKind::OrderByClause))
+        else {
+            continue;
+        };
+        let Some((keys, range)) = distinct_on_keys(select_clause, source) else {
+            continue;
+        };
+
+        let leading: Vec<Vec<&str>> = order_by
+            .children
+            .iter()
+            .filter_map(|child| child.get_tree_with_kind(SyntaxKind::OrderByItem))
+            .take(keys.len())
+            .filter_map(|item| item.children.iter().find_map(SyntaxChild::as_tree))
+            .map(|expr| expression_tokens(expr, source))
+            .collect();
+        if keys.iter().all(|key| leading.contains(&expression_tokens(key, source))) {
+            continue;
+        }
+
+        let key_list = keys
+            .iter()
+            .map(|key| {
+                let (start, end) = key.significant_span().unwrap_or(key.span());
+                &source[start as usize..end as usize]
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        diagnostics.push(Diagnostic {
+            message: "DISTINCT ON expressions are not a prefix of ORDER BY, so which row is kept per group is arbitrary".to_string(),
+            range,
+            severity: Severity::Warning,
+            code: Some("distinct-on-order-by"),
+            suggestion: Some(Suggestion {
+                message: format!("Start ORDER BY with {}", key_list),
+                replacement: None,
+            }),
+            related: Vec::new(),
+        });
+    }
+}
+
+/// The `DISTINCT ON (...)` expressions of a SELECT clause, and the range from
+/// `DISTINCT` to the closing parenthesis.
+fn distinct_on_keys<'a>(
+    select_clause: &'a SyntaxTree,
+    source: &str,
+) -> Option<(Vec<&'a SyntaxTree>, (usize, usize))> {
+    let mut children = select_clause.children.iter().filter(|child| {
+        !matches!(
+            child,
+            SyntaxChild::Token(t) if matches!(t.kind, SyntaxKind::Whitespace | SyntaxKind::Comment)
+        )
+    });
+    let distinct = children.find_map(|child| {
+        child.as_token().filter(|t| {
+            t.kind == SyntaxKind::BareWord && t.text(source).eq_ignore_ascii_case("DISTINCT")
+        })
+    })?;
+    let on = children.next()?.as_token()?;
+    if !on.text(source).eq_ignore_ascii_case("ON") {
+        return None;
+    }
+
+    let mut keys = Vec::new();
+    for child in children {
+        match child {
+            SyntaxChild::Tree(expr) => keys.push(expr),
+            SyntaxChild::Token(t) if t.kind == SyntaxKind::ClosingRoundBracket => {
+                return Some((keys, (distinct.start as usize, t.end as usize)));
+            }
+            SyntaxChild::Token(_) => {}
+        }
+    }
+    None
+}
+
+/// Texts of an expression's tokens, without whitespace and comments.
+fn expression_tokens<'a>(expr: &SyntaxTree, source: &'a str) -> Vec<&'a str> {
+    expr.tokens_in_order()
+        .into_iter()
+        .filter(|t| !matches!(t.kind, SyntaxKind::Whitespace | SyntaxKind::Comment))
+        .map(|t| t.text(source))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::lints::check_lint;
+    use expect_test::{expect, Expect};
+
+    fn check(input: &str, expected: Expect) {
+        check_lint("distinct-on-order-by", input, expected);
+    }
+
+    #[test]
+    fn keys_leading_order_by_are_fine() {
+        check(
+            "SELECT DISTINCT ON (a, b) a, b, c FROM t ORDER BY b, a DESC, c",
+            expect![[""]],
+        );
+        check(
+            "SELECT DISTINCT ON (toDate(ts)) * FROM t ORDER BY toDate( ts ), ts DESC",
+            expect![[""]],
+        );
+        check(
+            "SELECT DISTINCT ON (a+b) * FROM t ORDER BY a /* sum */ + b",
+            expect![[""]],
+        );
+    }
+
+    #[test]
+    fn flags_keys_not_leading_order_by() {
+        check(
+            "SELECT DISTINCT ON (a, b) a, b, c FROM t ORDER BY a, c, b",
+            expect![[r#"
+                7..25: DISTINCT ON expressions are not a prefix of ORDER BY, so which row is kept per group is arbitrary
+            "#]],
+        );
+    }
+
+    #[test]
+    fn skips_plain_distinct_and_missing_order_by() {
+        check("SELECT DISTINCT a FROM t ORDER BY b", expect![[""]]);
+        check("SELECT DISTINCT ON (a) a, b FROM t", expect![[""]]);
+    }
+}
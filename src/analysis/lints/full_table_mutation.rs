@@ -0,0 +1,137 @@
+use crate::diagnostics::{Diagnostic, Severity, Suggestion};
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Flag `ALTER TABLE ... DELETE/UPDATE` and `DELETE FROM` whose WHERE is a
+/// constant that is always true (`WHERE 1`, `WHERE true`, `WHERE 1 = 1`):
+/// the mutation rewrites every row, often a leftover from testing.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    let mut mutations = Vec::new();
+    for kind in [SyntaxKind::AlterDeleteWhere, SyntaxKind::AlterUpdateWhere] {
+        for command in tree.find_all(kind) {
+            // The condition is the last child node, after `WHERE`.
+            if let Some(condition) = command.children.iter().rev().find_map(SyntaxChild::as_tree) {
+                mutations.push((command, condition));
+            }
+        }
+    }
+    for statement in tree.find_all(SyntaxKind::DeleteStatement) {
+        let condition = statement
+            .children
+            .iter()
+            .find_map(|child| child.get_tree_with_kind(SyntaxKind::WhereClause))
+            .and_then(|clause| clause.children.iter().find_map(SyntaxChild::as_tree));
+        if let Some(condition) = condition {
+            mutations.push((statement, condition));
+        }
+    }
+    mutations.sort_by_key(|(_, condition)| condition.start);
+
+    for (mutation, condition) in mutations {
+        if !is_always_true(condition, source) {
+            continue;
+        }
+        let verb = if mutation.kind == SyntaxKind::AlterUpdateWhere {
+            "UPDATE"
+        } else {
+            "DELETE"
+        };
+        let text = source[condition.start as usize..condition.end as usize].trim_end();
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "`WHERE {text}` matches every row, so this {verb} affects the whole table"
+            ),
+            range: (
+                condition.start as usize,
+                condition.start as usize + text.len(),
+            ),
+            severity: Severity::Warning,
+            code: Some("full-table-mutation"),
+            suggestion: Some(Suggestion {
+                message: "Narrow the condition, or use TRUNCATE to empty the table".to_string(),
+                replacement: None,
+            }),
+            related: Vec::new(),
+        });
+    }
+}
+
+/// A literal that is truthy, or `x = x` between identical literals, possibly
+/// in parentheses. Anything referring to a column is not constant.
+fn is_always_true(expr: &SyntaxTree, source: &str) -> bool {
+    let operands: Vec<&SyntaxTree> = expr
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_tree)
+        .collect();
+    match expr.kind {
+        SyntaxKind::NumberLiteral => literal_text(expr, source)
+            .parse::<f64>()
+            .is_ok_and(|value| value != 0.0),
+        SyntaxKind::BooleanLiteral => literal_text(expr, source).eq_ignore_ascii_case("true"),
+        SyntaxKind::Expression => {
+            matches!(operands.as_slice(), [inner] if is_always_true(inner, source))
+        }
+        SyntaxKind::BinaryExpression => {
+            let is_equality = expr.children.iter().any(|child| {
+                matches!(child, SyntaxChild::Token(token)
+                    if matches!(token.text(source), "=" | "=="))
+            });
+            match operands.as_slice() {
+                [lhs, rhs] if is_equality => {
+                    is_literal(lhs.kind)
+                        && lhs.kind == rhs.kind
+                        && literal_text(lhs, source) == literal_text(rhs, source)
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn is_literal(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::NumberLiteral | SyntaxKind::StringLiteral | SyntaxKind::BooleanLiteral
+    )
+}
+
+fn literal_text<'a>(literal: &SyntaxTree, source: &'a str) -> &'a str {
+    let (start, end) = literal.significant_span().unwrap_or(literal.span());
+    &source[start as usize..end as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::lints::check_lint;
+    use expect_test::{expect, Expect};
+
+    fn check(input: &str, expected: Expect) {
+        check_lint("full-table-mutation", input, expected);
+    }
+
+    #[test]
+    fn flags_constant_true_delete() {
+        check("ALTER TABLE t DELETE WHERE 1", expect![[r#"
+            27..28: `WHERE 1` matches every row, so this DELETE affects the whole table
+        "#]]);
+        check("DELETE FROM t WHERE (true)", expect![[r#"
+            20..26: `WHERE (true)` matches every row, so this DELETE affects the whole table
+        "#]]);
+    }
+
+    #[test]
+    fn flags_constant_true_update() {
+        check("ALTER TABLE t UPDATE x = 0 WHERE 1 = 1", expect![[r#"
+            33..38: `WHERE 1 = 1` matches every row, so this UPDATE affects the whole table
+        "#]]);
+    }
+
+    #[test]
+    fn filtered_mutation_is_fine() {
+        check("ALTER TABLE t DELETE WHERE id = 5", expect![[""]]);
+        check("ALTER TABLE t DELETE WHERE 0", expect![[""]]);
+        check("DELETE FROM t WHERE 1 = 2", expect![[""]]);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::analysis::aggregates::is_aggregate_call;
+use crate::analysis::scope::{build_scope, normalize_identifier};
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::lexer::token::Token;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Flag columns in HAVING that ClickHouse can't resolve after aggregation:
+/// a bare name must be a SELECT/WITH alias or appear in GROUP BY, anything
+/// else has to sit inside an aggregate call (`HAVING count() > 5`).
+///
+/// Without a schema this is conservative: any column mentioned anywhere in
+/// GROUP BY counts as a key, lambda bodies are skipped, and `GROUP BY ALL`
+/// disables the check.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    for select in tree.find_all(SyntaxKind::SelectStatement) {
+        let clause = |kind| {
+            select
+                .children
+                .iter()
+                .find_map(|child| child.get_tree_with_kind(kind))
+        };
+        let Some(having) = clause(SyntaxKind::HavingClause) else {
+            continue;
+        };
+
+        let mut known: Vec<String> = build_scope(select, source)
+            .column_aliases
+            .into_iter()
+            .map(|alias| alias.name)
+            .collect();
+        if let Some(group_by) = clause(SyntaxKind::GroupByClause) {
+            if group_by.find_first(SyntaxKind::GroupByAll).is_some() {
+                continue;
+            }
+            for column in group_by.find_all(SyntaxKind::ColumnReference) {
+                known.extend(column_name(column, source));
+            }
+        }
+
+        let mut unknown = Vec::new();
+        collect_unresolved(having, source, &known, &mut unknown);
+        for (name, range) in unknown {
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "`{name}` in HAVING is not a SELECT alias, a GROUP BY key, or inside an aggregate function"
+                ),
+                range,
+                severity: Severity::Warning,
+                code: Some("having-unknown-column"),
+                suggestion: None,
+                related: Vec::new(),
+            });
+        }
+    }
+}
+
+/// The last name part of a column reference: `a` for both `a` and `t.a`.
+fn column_name(column: &SyntaxTree, source: &str) -> Option<String> {
+    identifier_tokens(column)
+        .next_back()
+        .map(|token| normalize_identifier(token, source))
+}
+
+fn identifier_tokens(column: &SyntaxTree) -> impl DoubleEndedIterator<Item = &Token> {
+    column
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_token)
+        .filter(|token| matches!(token.kind, SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier))
+}
+
+fn collect_unresolved(
+    tree: &SyntaxTree,
+    source: &str,
+    known: &[String],
+    out: &mut Vec<(String, (usize, usize))>,
+) {
+    for child in &tree.children {
+        let SyntaxChild::Tree(subtree) = child else {
+            continue;
+        };
+        match subtree.kind {
+            SyntaxKind::SubqueryExpression | SyntaxKind::LambdaExpression => {}
+            SyntaxKind::FunctionCall if is_aggregate_call(subtree, source) => {}
+            SyntaxKind::ColumnReference => {
+                let Some(name) = column_name(subtree, source) else {
+                    continue;
+                };
+                if !known.contains(&name) {
+                    let mut tokens = identifier_tokens(subtree);
+                    let first = tokens.next();
+                    let last = tokens.next_back().or(first);
+                    if let (Some(first), Some(last)) = (first, last) {
+                        out.push((name, (first.start as usize, last.end as usize)));
+                    }
+                }
+            }
+            _ => collect_unresolved(subtree, source, known, out),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::lints::check_lint;
+    use expect_test::{expect, Expect};
+
+    fn check(input: &str, expected: Expect) {
+        check_lint("having-unknown-column", input, expected);
+    }
+
+    #[test]
+    fn select_alias_resolves() {
+        check(
+            "SELECT a, sum(x) AS total FROM t GROUP BY a HAVING total > 10",
+            expect![[""]],
+        );
+    }
+
+    #[test]
+    fn aggregates_and_group_keys_resolve() {
+        check(
+            "SELECT a FROM t GROUP BY t.a, toDate(ts) HAVING count() > 5 AND a > 0 AND toDate(ts) > '2024-01-01'",
+            expect![[""]],
+        );
+        check(
+            "WITH 10 AS threshold SELECT count() FROM t HAVING max(x) > threshold",
+            expect![[""]],
+        );
+    }
+
+    #[test]
+    fn flags_unknown_name() {
+        check(
+            "SELECT a, sum(x) AS total FROM t GROUP BY a HAVING totl > 10 AND arrayExists(v -> v > 1, arr)",
+            expect![[r#"
+                51..55: `totl` in HAVING is not a SELECT alias, a GROUP BY key, or inside an aggregate function
+                89..92: `arr` in HAVING is not a SELECT alias, a GROUP BY key, or inside an aggregate function
+            "#]],
+        );
+    }
+
+    #[test]
+    fn group_by_all_is_skipped() {
+        check("SELECT a, count() FROM t GROUP BY ALL HAVING a > 1", expect![[""]]);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::analysis::aggregates::is_aggregate_call;
+use crate::diagnostics::{Diagnostic, Severity, Suggestion};
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Flag SELECTs that have a LIMIT but no ORDER BY: which rows come back is
+/// up to the storage engine and can change between runs.
+///
+/// A limited subquery feeding an aggregating query (`SELECT count() FROM
+/// (SELECT * FROM t LIMIT 100)`) is a deliberate sampling idiom and is skipped.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    walk(diagnostics, tree, source, false);
+}
+
+fn walk(
+    diagnostics: &mut Vec<Diagnostic>,
+    tree: &SyntaxTree,
+    source: &str,
+    enclosing_aggregates: bool,
+) {
+    let mut enclosing_aggregates = enclosing_aggregates;
+    if tree.kind == SyntaxKind::SelectStatement {
+        if !enclosing_aggregates {
+            check_select(diagnostics, tree);
+        }
+        enclosing_aggregates = select_aggregates(tree, source);
+    }
+
+    for subtree in tree.children.iter().filter_map(SyntaxChild::as_tree) {
+        walk(diagnostics, subtree, source, enclosing_aggregates);
+    }
+}
+
+fn check_select(diagnostics: &mut Vec<Diagnostic>, select: &SyntaxTree) {
+    let clause = |kind| {
+        select
+            .children
+            .iter()
+            .find_map(|child| child.get_tree_with_kind(kind))
+    };
+    let Some(limit) = clause(SyntaxKind::LimitClause) else {
+        return;
+    };
+    if clause(SyntaxKind::OrderByClause).is_some() {
+        return;
+    }
+
+    // Point at the LIMIT keyword rather than the whole clause.
+    let range = match limit.children.first() {
+        Some(SyntaxChild::Token(token)) => (token.start as usize, token.end as usize),
+        _ => (limit.start as usize, limit.end as usize),
+    };
+    diagnostics.push(Diagnostic {
+        message: "LIMIT without ORDER BY returns an arbitrary subset of rows".to_string(),
+        range,
+        severity: Severity::Hint,
+        code: Some("limit-without-order-by"),
+        suggestion: Some(Suggestion {
+            message: "Add an ORDER BY clause to make the result deterministic".to_string(),
+            replacement: None,
+        }),
+        related: Vec::new(),
+    });
+}
+
+/// True if the SELECT groups or calls an aggregate function in its own
+/// SELECT list (not counting nested subqueries).
+fn select_aggregates(select: &SyntaxTree, source: &str) -> bool {
+    select.children.iter().any(|child| match child {
+        SyntaxChild::Tree(clause) => match clause.kind {
+            SyntaxKind::GroupByClause => true,
+            SyntaxKind::SelectClause => contains_aggregate_call(clause, source),
+            _ => false,
+        },
+        SyntaxChild::Token(_) => false,
+    })
+}
+
+fn contains_aggregate_call(tree: &SyntaxTree, source: &str) -> bool {
+    if is_aggregate_call(tree, source) {
+        return true;
+    }
+    tree.children.iter().any(|child| match child {
+        SyntaxChild::Tree(subtree) if subtree.kind != SyntaxKind::SubqueryExpression => {
+            contains_aggregate_call(subtree, source)
+        }
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::lints::{check_lint, run_lints};
+    use crate::parser::parse;
+    use expect_test::{expect, Expect};
+
+    fn check(input: &str, expected: Expect) {
+        check_lint("limit-without-order-by", input, expected);
+    }
+
+    #[test]
+    fn flags_limit_without_order_by() {
+        check(
+            "SELECT a FROM t LIMIT 10",
+            expect![[r#"
+                16..21: LIMIT without ORDER BY returns an arbitrary subset of rows
+            "#]],
+        );
+    }
+
+    #[test]
+    fn limit_with_order_by_is_fine() {
+        check("SELECT a FROM t ORDER BY a LIMIT 10", expect![[""]]);
+    }
+
+    #[test]
+    fn flags_limited_subquery_in_plain_select() {
+        check(
+            "SELECT a FROM (SELECT a FROM t LIMIT 5) ORDER BY a",
+            expect![[r#"
+                31..36: LIMIT without ORDER BY returns an arbitrary subset of rows
+            "#]],
+        );
+    }
+
+    #[test]
+    fn skips_limited_subquery_under_aggregation() {
+        check("SELECT count() FROM (SELECT * FROM t LIMIT 100)", expect![[""]]);
+        check("SELECT a FROM (SELECT a FROM t LIMIT 5) GROUP BY a", expect![[""]]);
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let result = parse("SELECT a FROM t LIMIT 10");
+        assert!(run_lints(&result.tree, &result.source, &Default::default()).is_empty());
+    }
+}
@@ -0,0 +1,232 @@
+//! Opt-in checks on syntactically valid queries.
+//!
+//! Unlike the passes in `diagnostics`, which explain parse errors and always
+//! run, each lint here flags a pattern that is legal but often a mistake, and
+//! only runs when enabled in [`LintOptions`] or, by name, in a [`LintConfig`].
+
+mod bare_union;
+mod cartesian_join;
+mod comparison_type_mismatch;
+mod distinct_on_order_by;
+mod full_table_mutation;
+mod having_unknown_column;
+mod limit_without_order_by;
+mod unknown_identifier;
+
+use std::collections::HashMap;
+
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::parser::syntax_tree::SyntaxTree;
+
+type Check = fn(&mut Vec<Diagnostic>, &SyntaxTree, &str);
+type Flag = fn(&LintOptions) -> bool;
+
+/// Every lint by name, in the order they run, with the [`LintOptions`] flag
+/// that enables it. A lint's name is also the `code` of the diagnostics it
+/// reports.
+const LINTS: &[(&str, Check, Flag)] = &[
+    ("limit-without-order-by", limit_without_order_by::check, |o| o.limit_without_order_by),
+    ("bare-union", bare_union::check, |o| o.bare_union),
+    ("having-unknown-column", having_unknown_column::check, |o| o.having_unknown_column),
+    ("cartesian-join", cartesian_join::check, |o| o.cartesian_join),
+    ("unknown-identifier", unknown_identifier::check, |o| o.unknown_identifier),
+    ("distinct-on-order-by", distinct_on_order_by::check, |o| o.distinct_on_order_by),
+    ("comparison-type-mismatch", comparison_type_mismatch::check, |o| o.comparison_type_mismatch),
+    ("full-table-mutation", full_table_mutation::check, |o| o.full_table_mutation),
+];
+
+/// Names of all available lints, for use with [`LintConfig`].
+pub fn lint_names() -> impl Iterator<Item = &'static str> {
+    LINTS.iter().map(|(name, _, _)| *name)
+}
+
+/// Which lints to run. All lints are disabled by default.
+#[derive(Debug, Clone, Default)]
+pub struct LintOptions {
+    /// Flag `LIMIT` without `ORDER BY` (nondeterministic result set).
+    pub limit_without_order_by: bool,
+    /// Flag `UNION` without `ALL` or `DISTINCT` (meaning depends on
+    /// `union_default_mode`).
+    pub bare_union: bool,
+    /// Flag HAVING columns that are neither aliases, GROUP BY keys, nor
+    /// aggregate arguments.
+    pub having_unknown_column: bool,
+    /// Flag joins without `ON` or `USING` other than `CROSS JOIN`.
+    pub cartesian_join: bool,
+    /// Flag names in a SELECT without FROM that match no WITH constant or
+    /// alias.
+    pub unknown_identifier: bool,
+    /// Flag `DISTINCT ON` keys that don't lead the ORDER BY.
+    pub distinct_on_order_by: bool,
+    /// Flag comparisons of a `CAST` with a literal that can't convert to the
+    /// cast type.
+    pub comparison_type_mismatch: bool,
+    /// Flag DELETE and UPDATE mutations whose WHERE is always true.
+    pub full_table_mutation: bool,
+}
+
+/// How one lint is configured in a [`LintConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LintRule {
+    pub enabled: bool,
+    /// Report the lint's diagnostics with this severity instead of its own.
+    pub severity: Option<Severity>,
+}
+
+/// Lint settings keyed by lint name (see [`lint_names`]). Lints without an
+/// entry are disabled, and entries that name no lint are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct LintConfig {
+    pub rules: HashMap<String, LintRule>,
+}
+
+impl LintConfig {
+    pub fn enable(&mut self, name: &str) -> &mut Self {
+        self.rules.entry(name.to_string()).or_default().enabled = true;
+        self
+    }
+
+    pub fn disable(&mut self, name: &str) -> &mut Self {
+        self.rules.entry(name.to_string()).or_default().enabled = false;
+        self
+    }
+
+    /// Override the severity of a lint's diagnostics. Doesn't enable it.
+    pub fn set_severity(&mut self, name: &str, severity: Severity) -> &mut Self {
+        self.rules.entry(name.to_string()).or_default().severity = Some(severity);
+        self
+    }
+
+    fn rule(&self, name: &str) -> LintRule {
+        self.rules.get(name).copied().unwrap_or_default()
+    }
+}
+
+impl From<&LintOptions> for LintConfig {
+    fn from(options: &LintOptions) -> Self {
+        let mut config = LintConfig::default();
+        for (name, _, _) in LINTS.iter().filter(|(_, _, flag)| flag(options)) {
+            config.rules.insert(name.to_string(), LintRule { enabled: true, severity: None });
+        }
+        config
+    }
+}
+
+/// Run the enabled lints over a parsed tree.
+pub fn run_lints(tree: &SyntaxTree, source: &str, options: &LintOptions) -> Vec<Diagnostic> {
+    run_configured_lints(tree, source, &LintConfig::from(options))
+}
+
+/// Run the lints enabled in `config`, applying its severity overrides.
+pub fn run_configured_lints(
+    tree: &SyntaxTree,
+    source: &str,
+    config: &LintConfig,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for (name, check, _) in LINTS {
+        let rule = config.rule(name);
+        if !rule.enabled {
+            continue;
+        }
+        let first = diagnostics.len();
+        check(&mut diagnostics, tree, source);
+        if let Some(severity) = rule.severity {
+            for diagnostic in &mut diagnostics[first..] {
+                diagnostic.severity = severity;
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Run only the lint `name` over `input` and compare its diagnostics, one
+/// `start..end: message` line each, with `expected`.
+#[cfg(test)]
+fn check_lint(name: &str, input: &str, expected: expect_test::Expect) {
+    let result = crate::parser::parse(input);
+    let mut config = LintConfig::default();
+    config.enable(name);
+    let actual: String = run_configured_lints(&result.tree, &result.source, &config)
+        .iter()
+        .map(|d| format!("{}..{}: {}\n", d.range.0, d.range.1, d.message))
+        .collect();
+    expected.assert_eq(&actual);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn codes(sql: &str, config: &LintConfig) -> Vec<&'static str> {
+        let result = parse(sql);
+        run_configured_lints(&result.tree, &result.source, config)
+            .iter()
+            .filter_map(|d| d.code)
+            .collect()
+    }
+
+    const SQL: &str = "SELECT a FROM t, u UNION SELECT b FROM v LIMIT 1";
+
+    #[test]
+    fn only_enabled_lints_fire() {
+        let mut config = LintConfig::default();
+        assert!(codes(SQL, &config).is_empty());
+
+        config.enable("bare-union").enable("limit-without-order-by");
+        assert_eq!(codes(SQL, &config), ["limit-without-order-by", "bare-union"]);
+
+        config.disable("limit-without-order-by").enable("no-such-lint");
+        assert_eq!(codes(SQL, &config), ["bare-union"]);
+    }
+
+    #[test]
+    fn severity_override() {
+        let mut config = LintConfig::default();
+        config.enable("bare-union").set_severity("bare-union", Severity::Error);
+        let result = parse(SQL);
+        let diagnostics = run_configured_lints(&result.tree, &result.source, &config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn names_match_diagnostic_codes() {
+        // A query that triggers each lint.
+        let triggers = [
+            ("limit-without-order-by", "SELECT a FROM t LIMIT 10"),
+            ("bare-union", "SELECT 1 UNION SELECT 2"),
+            ("having-unknown-column", "SELECT a FROM t GROUP BY a HAVING b > 1"),
+            ("cartesian-join", "SELECT * FROM a INNER JOIN b"),
+            ("unknown-identifier", "WITH 10 AS threshold SELECT threshhold"),
+            ("distinct-on-order-by", "SELECT DISTINCT ON (a) a, b FROM t ORDER BY b"),
+            ("comparison-type-mismatch", "SELECT * FROM t WHERE CAST(a AS String) = 123"),
+            ("full-table-mutation", "ALTER TABLE t DELETE WHERE 1"),
+        ];
+        assert_eq!(lint_names().collect::<Vec<_>>(), triggers.map(|(name, _)| name));
+        for (name, sql) in triggers {
+            let mut config = LintConfig::default();
+            config.enable(name);
+            assert_eq!(codes(sql, &config), [name], "{sql}");
+        }
+    }
+
+    #[test]
+    fn every_option_enables_its_lint() {
+        let options = LintOptions {
+            limit_without_order_by: true,
+            bare_union: true,
+            having_unknown_column: true,
+            cartesian_join: true,
+            unknown_identifier: true,
+            distinct_on_order_by: true,
+            comparison_type_mismatch: true,
+            full_table_mutation: true,
+        };
+        let config = LintConfig::from(&options);
+        assert!(lint_names().all(|name| config.rule(name).enabled));
+        let config = LintConfig::from(&LintOptions { bare_union: true, ..Default::default() });
+        assert_eq!(config.rules.keys().collect::<Vec<_>>(), ["bare-union"]);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::analysis::aggregates::function_call_name;
+use crate::analysis::scope::{build_scope, leading_with_clause, normalize_identifier};
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::lexer::token::Token;
+use crate::parser::interval_unit::IntervalUnit;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Flag bare names in a SELECT without FROM that are neither a `WITH` constant
+/// nor a SELECT alias (`WITH 10 AS limit SELECT limt * 2`). With no table in
+/// scope, there's nothing else such a name could refer to.
+///
+/// Constants from enclosing queries are visible in subqueries. A subquery
+/// under a query with FROM is skipped, since it may see that table's columns.
+/// Qualified names and lambda parameters are never flagged.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    walk(diagnostics, tree, source, &[]);
+}
+
+fn walk(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str, outer: &[String]) {
+    if tree.kind != SyntaxKind::SelectStatement {
+        // The first branch's WITH applies to every branch of a UNION.
+        let mut outer = outer.to_vec();
+        let with = match tree.kind {
+            SyntaxKind::UnionClause => leading_with_clause(tree),
+            _ => None,
+        };
+        if let Some(with) = with {
+            outer.extend(build_scope(with, source).column_aliases.into_iter().map(|a| a.name));
+        }
+        for subtree in tree.children.iter().filter_map(SyntaxChild::as_tree) {
+            walk(diagnostics, subtree, source, &outer);
+        }
+        return;
+    }
+
+    let has_from = tree
+        .children
+        .iter()
+        .any(|child| child.get_tree_with_kind(SyntaxKind::FromClause).is_some());
+    if has_from {
+        return;
+    }
+
+    let mut known = outer.to_vec();
+    known.extend(
+        build_scope(tree, source)
+            .column_aliases
+            .into_iter()
+            .map(|alias| alias.name),
+    );
+
+    for clause in tree.children.iter().filter_map(SyntaxChild::as_tree) {
+        if clause.kind != SyntaxKind::WithClause {
+            check_clause(diagnostics, clause, source, &known);
+        }
+    }
+}
+
+fn check_clause(
+    diagnostics: &mut Vec<Diagnostic>,
+    tree: &SyntaxTree,
+    source: &str,
+    known: &[String],
+) {
+    check_trees(diagnostics, tree.children.iter().filter_map(SyntaxChild::as_tree), source, known);
+}
+
+fn check_trees<'a>(
+    diagnostics: &mut Vec<Diagnostic>,
+    trees: impl Iterator<Item = &'a SyntaxTree>,
+    source: &str,
+    known: &[String],
+) {
+    for subtree in trees {
+        match subtree.kind {
+            SyntaxKind::LambdaExpression => {}
+            SyntaxKind::SubqueryExpression => walk(diagnostics, subtree, source, known),
+            SyntaxKind::ColumnReference => check_column(diagnostics, subtree, source, known),
+            SyntaxKind::FunctionCall if takes_unit_argument(subtree, source) => {
+                check_unit_call(diagnostics, subtree, source, known)
+            }
+            _ => check_clause(diagnostics, subtree, source, known),
+        }
+    }
+}
+
+/// Functions whose first argument may be a bare interval unit, as in
+/// `date_diff(day, a, b)`. ClickHouse matches these names case-insensitively.
+const UNIT_FUNCTIONS: &[&str] = &[
+    "date_diff", "datediff", "timestamp_diff", "timestampdiff", "date_add", "dateadd",
+    "timestamp_add", "timestampadd", "date_sub", "datesub", "timestamp_sub", "timestampsub",
+];
+
+fn takes_unit_argument(call: &SyntaxTree, source: &str) -> bool {
+    function_call_name(call, source)
+        .is_some_and(|name| UNIT_FUNCTIONS.iter().any(|f| name.eq_ignore_ascii_case(f)))
+}
+
+/// Check a unit-taking call's arguments, skipping a leading interval unit:
+/// `day` in `date_diff(day, a, b)` is a keyword, not a column.
+fn check_unit_call(
+    diagnostics: &mut Vec<Diagnostic>,
+    call: &SyntaxTree,
+    source: &str,
+    known: &[String],
+) {
+    for child in call.children.iter().filter_map(SyntaxChild::as_tree) {
+        if child.kind != SyntaxKind::ExpressionList {
+            check_trees(diagnostics, std::iter::once(child), source, known);
+            continue;
+        }
+        let mut args = child.children.iter().filter_map(SyntaxChild::as_tree).peekable();
+        if args.peek().is_some_and(|arg| is_interval_unit(arg, source)) {
+            args.next();
+        }
+        check_trees(diagnostics, args, source, known);
+    }
+}
+
+fn is_interval_unit(arg: &SyntaxTree, source: &str) -> bool {
+    arg.significant_span().is_some_and(|(start, end)| {
+        IntervalUnit::from_str(&source[start as usize..end as usize]).is_some()
+    })
+}
+
+fn check_column(
+    diagnostics: &mut Vec<Diagnostic>,
+    column: &SyntaxTree,
+    source: &str,
+    known: &[String],
+) {
+    let mut parts = column
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_token)
+        .filter(|token| matches!(token.kind, SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier));
+    let (Some(token), None) = (parts.next(), parts.next()) else {
+        return;
+    };
+    let name = normalize_identifier(token, source);
+    if known.contains(&name) {
+        return;
+    }
+    diagnostics.push(unknown_identifier(name, token));
+}
+
+fn unknown_identifier(name: String, token: &Token) -> Diagnostic {
+    Diagnostic {
+        message: format!(
+            "Unknown identifier `{name}`: the query has no FROM clause and no WITH constant or alias with this name"
+        ),
+        range: (token.start as usize, token.end as usize),
+        severity: Severity::Warning,
+        code: Some("unknown-identifier"),
+        suggestion: None,
+        related: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::lints::check_lint;
+    use expect_test::{expect, Expect};
+
+    fn check(input: &str, expected: Expect) {
+        check_lint("unknown-identifier", input, expected);
+    }
+
+    #[test]
+    fn with_constant_resolves() {
+        check(
+            "WITH 10 AS threshold, threshold * 2 AS double SELECT threshold + double AS total, total",
+            expect![[""]],
+        );
+        check(
+            "WITH 1 AS x SELECT (SELECT x + 1), arrayMap(v -> v + x, [1, 2])",
+            expect![[""]],
+        );
+    }
+
+    #[test]
+    fn with_constant_applies_to_union_branches() {
+        check(
+            "WITH 1 AS k SELECT k UNION ALL SELECT k + 1 UNION ALL SELECT j",
+            expect![[r#"
+                61..62: Unknown identifier `j`: the query has no FROM clause and no WITH constant or alias with this name
+            "#]],
+        );
+    }
+
+    #[test]
+    fn interval_unit_arguments_are_not_names() {
+        check("SELECT date_diff(day, now(), today()), DATEADD(HOUR, 1, now())", expect![[""]]);
+        check(
+            "SELECT date_diff(dy, a, now())",
+            expect![[r#"
+                17..19: Unknown identifier `dy`: the query has no FROM clause and no WITH constant or alias with this name
+                21..22: Unknown identifier `a`: the query has no FROM clause and no WITH constant or alias with this name
+            "#]],
+        );
+    }
+
+    #[test]
+    fn flags_unresolved_name() {
+        check(
+            "WITH 10 AS threshold SELECT threshhold WHERE t.x = 1",
+            expect![[r#"
+                28..38: Unknown identifier `threshhold`: the query has no FROM clause and no WITH constant or alias with this name
+            "#]],
+        );
+    }
+
+    #[test]
+    fn queries_with_from_are_skipped() {
+        check("WITH 10 AS threshold SELECT a FROM t WHERE a > threshold", expect![[""]]);
+        check("SELECT a FROM t WHERE a IN (SELECT b)", expect![[""]]);
+    }
+}
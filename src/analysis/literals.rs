@@ -0,0 +1,99 @@
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// A literal value in the query, e.g. for secret scanning or rewriting
+/// constants into parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiteralRef<'a> {
+    /// `StringLiteral`, `NumberLiteral`, `DateLiteral`, `BooleanLiteral` or
+    /// `NullLiteral`.
+    pub kind: SyntaxKind,
+    /// Source text of the literal, quotes included for strings.
+    pub text: &'a str,
+    /// Byte range of the literal, excluding trailing trivia.
+    pub span: (u32, u32),
+}
+
+/// Every literal node in the tree, in source order. A sign is not part of a
+/// number literal: `-1` yields `1`.
+pub fn literals<'a>(tree: &SyntaxTree, source: &'a str) -> Vec<LiteralRef<'a>> {
+    let mut out = Vec::new();
+    collect(tree, source, &mut out);
+    out
+}
+
+/// True for the node kinds `literals` reports.
+pub(crate) fn is_literal(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::StringLiteral
+            | SyntaxKind::NumberLiteral
+            | SyntaxKind::DateLiteral
+            | SyntaxKind::BooleanLiteral
+            | SyntaxKind::NullLiteral
+    )
+}
+
+/// The value of the string token directly under `tree`, such as a
+/// `StringLiteral` or a `ColumnComment`: quotes removed, `''` and `\'`
+/// unescaped.
+pub(crate) fn string_value(tree: &SyntaxTree, source: &str) -> Option<String> {
+    let token = tree
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_token)
+        .find(|token| token.kind == SyntaxKind::StringToken)?;
+    let inner = token.text(source).strip_prefix('\'')?.strip_suffix('\'')?;
+    Some(inner.replace("''", "'").replace("\\'", "'"))
+}
+
+fn collect<'a>(tree: &SyntaxTree, source: &'a str, out: &mut Vec<LiteralRef<'a>>) {
+    if is_literal(tree.kind) {
+        if let Some(span) = tree.significant_span() {
+            out.push(LiteralRef {
+                kind: tree.kind,
+                text: &source[span.0 as usize..span.1 as usize],
+                span,
+            });
+        }
+        return;
+    }
+    for subtree in tree.children.iter().filter_map(SyntaxChild::as_tree) {
+        collect(subtree, source, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn collects_literals_with_spans() {
+        let sql = "SELECT 'secret', -1.5, true, NULL, [2], {'k': 3} FROM t WHERE id = 0x1F";
+        let result = parse(sql);
+        let found: Vec<(SyntaxKind, &str, (u32, u32))> = literals(&result.tree, sql)
+            .into_iter()
+            .map(|l| (l.kind, l.text, l.span))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (SyntaxKind::StringLiteral, "'secret'", (7, 15)),
+                (SyntaxKind::NumberLiteral, "1.5", (18, 21)),
+                (SyntaxKind::BooleanLiteral, "true", (23, 27)),
+                (SyntaxKind::NullLiteral, "NULL", (29, 33)),
+                (SyntaxKind::NumberLiteral, "2", (36, 37)),
+                (SyntaxKind::StringLiteral, "'k'", (41, 44)),
+                (SyntaxKind::NumberLiteral, "3", (46, 47)),
+                (SyntaxKind::NumberLiteral, "0x1F", (67, 71)),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_literals() {
+        let result = parse("SELECT a FROM t");
+        assert!(literals(&result.tree, &result.source).is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+use crate::lexer::token::Token;
+use crate::parser::syntax_kind::SyntaxKind;
+
+/// A `{macro}` reference inside a string literal, e.g. `{shard}` in a
+/// ReplicatedMergeTree path. The server substitutes these from its
+/// `<macros>` config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroRef<'a> {
+    /// The macro name without braces, e.g. `shard`.
+    pub name: &'a str,
+    /// Byte range of the reference in the source, braces included.
+    pub span: (u32, u32),
+}
+
+/// The `{name}` references in a string literal token, in order. Other
+/// tokens have none. Braces around anything but a plain identifier, such
+/// as `{}` or `{a b}`, are not macros and are skipped.
+pub fn macro_references<'a>(token: &Token, source: &'a str) -> Vec<MacroRef<'a>> {
+    if token.kind != SyntaxKind::StringToken {
+        return Vec::new();
+    }
+    let text = token.text(source);
+    let mut out = Vec::new();
+    let mut rest = 0;
+    while let Some(open) = text[rest..].find('{').map(|i| rest + i) {
+        let Some(close) = text[open..].find('}').map(|i| open + i) else {
+            break;
+        };
+        let name = &text[open + 1..close];
+        if is_macro_name(name) {
+            out.push(MacroRef {
+                name,
+                span: (token.start + open as u32, token.start + close as u32 + 1),
+            });
+            rest = close + 1;
+        } else {
+            rest = open + 1;
+        }
+    }
+    out
+}
+
+fn is_macro_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenizer::tokenize_with_whitespace;
+
+    fn macros_in(sql: &str) -> Vec<(&str, (u32, u32))> {
+        tokenize_with_whitespace(sql)
+            .iter()
+            .flat_map(|token| macro_references(token, sql))
+            .map(|m| (m.name, m.span))
+            .collect()
+    }
+
+    #[test]
+    fn extracts_macros_from_replicated_path() {
+        let sql = "ENGINE = ReplicatedMergeTree('/clickhouse/tables/{shard}/t', '{replica}')";
+        assert_eq!(macros_in(sql), [("shard", (49, 56)), ("replica", (62, 71))]);
+    }
+
+    #[test]
+    fn ignores_non_macro_braces_and_other_tokens() {
+        assert!(macros_in("SELECT '{}', '{a b}', {x: UInt8}, `{y}`").is_empty());
+        assert_eq!(macros_in("SELECT '{{z}'"), [("z", (9, 12))]);
+    }
+}
@@ -1,2 +1,24 @@
+pub mod aggregates;
+pub mod asterisks;
+pub mod completions;
 pub mod cursor_context;
+pub mod dictionaries;
+pub mod highlight;
+pub mod identifiers;
+pub mod in_set;
+pub mod join_keys;
+pub mod join_type;
+pub mod lints;
+pub mod literals;
+pub mod macros;
+pub mod output_columns;
+pub mod parameters;
+pub mod read_only;
+pub mod references;
+pub mod sample;
 pub mod scope;
+pub mod statement_summary;
+pub mod structural_hash;
+pub mod table_columns;
+pub mod tokens;
+pub mod validate;
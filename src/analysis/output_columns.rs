@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use crate::analysis::scope::{build_scope, normalize_identifier, TableRef};
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Column names per table, keyed by `table` or `database.table`.
+pub type Schema = HashMap<String, Vec<String>>;
+
+/// One column of a SELECT's result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputColumn {
+    /// The alias, the column name, or the expression text as written.
+    pub name: String,
+    /// Byte range of the SELECT item that produces the column.
+    pub span: (u32, u32),
+    /// True if the column came from expanding `*` or `t.*` with the schema.
+    pub expanded: bool,
+}
+
+/// The result columns of a `SelectStatement`, in order.
+///
+/// With a schema, `*` expands to the columns of every FROM/JOIN table and
+/// `t.*` to those of `t`, honouring `* EXCEPT (...)`. A wildcard stays a single
+/// unexpanded entry when there is no schema or it can't be expanded fully:
+/// a table is missing from the schema, or FROM reads a subquery or a table
+/// function.
+pub fn output_columns(
+    select: &SyntaxTree,
+    source: &str,
+    schema: Option<&Schema>,
+) -> Vec<OutputColumn> {
+    let Some(items) = select
+        .children
+        .iter()
+        .find_map(|child| child.get_tree_with_kind(SyntaxKind::SelectClause))
+        .and_then(|clause| {
+            clause
+                .children
+                .iter()
+                .find_map(|child| child.get_tree_with_kind(SyntaxKind::ColumnList))
+        })
+    else {
+        return Vec::new();
+    };
+    let tables = schema.and_then(|schema| resolve_tables(select, source, schema));
+
+    let mut columns = Vec::new();
+    let mut nodes = items.children.iter().filter_map(SyntaxChild::as_tree).peekable();
+    while let Some(item) = nodes.next() {
+        let alias = nodes.next_if(|node| node.kind == SyntaxKind::ColumnAlias);
+        let span = item.significant_span().unwrap_or(item.span());
+        // `expr AS (a, b)` names each element of a tuple result.
+        if let Some(list) = alias.and_then(|alias| {
+            alias
+                .children
+                .iter()
+                .find_map(|child| child.get_tree_with_kind(SyntaxKind::IdentifierList))
+        }) {
+            columns.extend(name_parts(list, source).into_iter().map(|name| OutputColumn {
+                name,
+                span,
+                expanded: false,
+            }));
+            continue;
+        }
+        let alias = alias.and_then(|alias| alias_name(alias, source));
+        if alias.is_none() {
+            if let Some(expanded) = expand(item, source, tables.as_deref()) {
+                columns.extend(expanded.into_iter().map(|name| OutputColumn {
+                    name,
+                    span,
+                    expanded: true,
+                }));
+                continue;
+            }
+        }
+        let name = alias.unwrap_or_else(|| item_name(item, source));
+        columns.push(OutputColumn {
+            name,
+            span,
+            expanded: false,
+        });
+    }
+    columns
+}
+
+/// Each FROM/JOIN table with its schema columns, or `None` if any source
+/// can't be resolved.
+fn resolve_tables<'s>(
+    select: &SyntaxTree,
+    source: &str,
+    schema: &'s Schema,
+) -> Option<Vec<(TableRef, &'s [String])>> {
+    let reads_other_sources = select
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_tree)
+        .filter(|clause| matches!(clause.kind, SyntaxKind::FromClause | SyntaxKind::JoinClause))
+        .any(|clause| {
+            clause.find_first(SyntaxKind::SubqueryExpression).is_some()
+                || clause.find_first(SyntaxKind::TableFunction).is_some()
+        });
+    if reads_other_sources {
+        return None;
+    }
+    build_scope(select, source)
+        .table_refs
+        .into_iter()
+        .map(|table| {
+            let qualified = table.database.as_ref().map(|db| format!("{db}.{}", table.table));
+            let columns = qualified
+                .and_then(|name| schema.get(&name))
+                .or_else(|| schema.get(&table.table))?;
+            Some((table, columns.as_slice()))
+        })
+        .collect()
+}
+
+/// The columns a wildcard item stands for, or `None` if `item` isn't a
+/// wildcard or can't be expanded.
+fn expand(
+    item: &SyntaxTree,
+    source: &str,
+    tables: Option<&[(TableRef, &[String])]>,
+) -> Option<Vec<String>> {
+    let tables = tables?;
+    match item.kind {
+        SyntaxKind::Asterisk => Some(
+            tables
+                .iter()
+                .flat_map(|(_, columns)| columns.iter().cloned())
+                .collect(),
+        ),
+        SyntaxKind::QualifiedAsterisk => {
+            let qualifier = item
+                .children
+                .iter()
+                .find_map(|child| child.get_tree_with_kind(SyntaxKind::ColumnReference))?;
+            let parts = name_parts(qualifier, source);
+            let (_, columns) = tables.iter().find(|(table, _)| match parts.as_slice() {
+                [name] => table.alias.as_ref() == Some(name) || &table.table == name,
+                [db, name] => table.database.as_ref() == Some(db) && &table.table == name,
+                _ => false,
+            })?;
+            Some(columns.to_vec())
+        }
+        SyntaxKind::ColumnTransformer => {
+            let mut children = item.children.iter().filter(|child| {
+                !matches!(child, SyntaxChild::Token(t) if t.kind == SyntaxKind::Whitespace)
+            });
+            let wildcard = children.next()?.as_tree()?;
+            let keyword = children.next()?.as_token()?;
+            if !keyword.text(source).eq_ignore_ascii_case("EXCEPT") {
+                return None;
+            }
+            let except: Vec<String> = children
+                .filter_map(SyntaxChild::as_tree)
+                .flat_map(|list| list.find_all(SyntaxKind::ColumnReference))
+                .filter_map(|column| name_parts(column, source).pop())
+                .collect();
+            let mut columns = expand(wildcard, source, Some(tables))?;
+            columns.retain(|column| !except.contains(column));
+            Some(columns)
+        }
+        _ => None,
+    }
+}
+
+fn item_name(item: &SyntaxTree, source: &str) -> String {
+    if item.kind == SyntaxKind::ColumnReference {
+        if let Some(name) = name_parts(item, source).pop() {
+            return name;
+        }
+    }
+    let (start, end) = item.significant_span().unwrap_or(item.span());
+    source[start as usize..end as usize].to_string()
+}
+
+fn alias_name(alias: &SyntaxTree, source: &str) -> Option<String> {
+    alias
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_token)
+        .rfind(|token| matches!(token.kind, SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier))
+        .map(|token| normalize_identifier(token, source))
+}
+
+fn name_parts(column: &SyntaxTree, source: &str) -> Vec<String> {
+    column
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_token)
+        .filter(|token| matches!(token.kind, SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier))
+        .map(|token| normalize_identifier(token, source))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn names(sql: &str, schema: Option<&Schema>) -> Vec<String> {
+        let result = parse(sql);
+        assert!(result.errors.is_empty(), "{sql}: {:?}", result.errors);
+        let select = result.tree.find_first(SyntaxKind::SelectStatement).unwrap();
+        output_columns(select, sql, schema)
+            .into_iter()
+            .map(|column| column.name)
+            .collect()
+    }
+
+    fn schema() -> Schema {
+        Schema::from([
+            ("db.events".to_string(), vec!["id".to_string(), "ts".to_string()]),
+            ("users".to_string(), vec!["id".to_string(), "name".to_string()]),
+        ])
+    }
+
+    #[test]
+    fn named_items() {
+        assert_eq!(
+            names("SELECT a, t.b, c + 1, d AS x FROM t", None),
+            ["a", "b", "c + 1", "x"]
+        );
+    }
+
+    #[test]
+    fn tuple_alias_list_names_each_element() {
+        assert_eq!(names("SELECT f(x) AS (lo, hi), y FROM t", None), ["lo", "hi", "y"]);
+    }
+
+    #[test]
+    fn wildcard_stays_without_schema() {
+        assert_eq!(names("SELECT *, u.* FROM users AS u", None), ["*", "u.*"]);
+    }
+
+    #[test]
+    fn schema_expands_wildcards() {
+        let schema = schema();
+        assert_eq!(
+            names("SELECT * FROM db.events JOIN users ON 1", Some(&schema)),
+            ["id", "ts", "id", "name"]
+        );
+        assert_eq!(
+            names("SELECT u.*, e.ts FROM db.events AS e, users AS u", Some(&schema)),
+            ["id", "name", "ts"]
+        );
+        assert_eq!(
+            names("SELECT * EXCEPT (id), 1 FROM users", Some(&schema)),
+            ["name", "1"]
+        );
+    }
+
+    #[test]
+    fn unresolvable_wildcards_stay() {
+        let schema = schema();
+        assert_eq!(names("SELECT * FROM events", Some(&schema)), ["*"]);
+        assert_eq!(
+            names("SELECT * FROM users, (SELECT 1 AS one)", Some(&schema)),
+            ["*"]
+        );
+        assert_eq!(
+            names("SELECT * FROM users JOIN numbers(3) AS n ON 1", Some(&schema)),
+            ["*"]
+        );
+    }
+
+    #[test]
+    fn expanded_columns_point_at_wildcard() {
+        let sql = "SELECT x, * FROM users";
+        let result = parse(sql);
+        let select = result.tree.find_first(SyntaxKind::SelectStatement).unwrap();
+        let columns = output_columns(select, sql, Some(&schema()));
+        let spans: Vec<(&str, &str, bool)> = columns
+            .iter()
+            .map(|c| (c.name.as_str(), &sql[c.span.0 as usize..c.span.1 as usize], c.expanded))
+            .collect();
+        assert_eq!(
+            spans,
+            [("x", "x", false), ("id", "*", true), ("name", "*", true)]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// A placeholder a client binds a value to before running the query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Parameter {
+    /// `id` for `{id:UInt64}` and `1` for `$1`; `None` for `?`.
+    pub name: Option<String>,
+    /// The declared type of a `{name:Type}` parameter, as written.
+    pub ty: Option<String>,
+    /// Byte range of the parameter, excluding trailing trivia.
+    pub span: (u32, u32),
+}
+
+/// Every query parameter in the tree in source order: named `{name:Type}`,
+/// numbered `$1` and positional `?`. A name used more than once appears
+/// each time.
+pub fn parameters(tree: &SyntaxTree, source: &str) -> Vec<Parameter> {
+    let mut out = Vec::new();
+    collect(tree, source, &mut out);
+    out
+}
+
+fn collect(tree: &SyntaxTree, source: &str, out: &mut Vec<Parameter>) {
+    match tree.kind {
+        SyntaxKind::QueryParameterExpression | SyntaxKind::PositionalParameter => {
+            let Some(span) = tree.significant_span() else {
+                return;
+            };
+            let name = tree
+                .tokens_in_order()
+                .into_iter()
+                .find(|token| matches!(token.kind, SyntaxKind::BareWord | SyntaxKind::Number))
+                .map(|token| token.text(source).to_string());
+            let ty = tree
+                .children
+                .iter()
+                .find_map(|child| child.get_tree_with_kind(SyntaxKind::DataType))
+                .and_then(|ty| ty.significant_span())
+                .map(|(start, end)| source[start as usize..end as usize].to_string());
+            out.push(Parameter {
+                name,
+                ty,
+                span,
+            });
+        }
+        _ => {
+            for subtree in tree.children.iter().filter_map(SyntaxChild::as_tree) {
+                collect(subtree, source, out);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn named_and_positional_parameters() {
+        let sql = "SELECT {id:UInt64}, ? FROM t WHERE ts > {from: DateTime64(3)} AND k = $1 \
+            LIMIT {n:UInt8}";
+        let result = parse(sql);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let params = parameters(&result.tree, sql);
+        let summary: Vec<(Option<&str>, Option<&str>, &str)> = params
+            .iter()
+            .map(|p| {
+                let text = &sql[p.span.0 as usize..p.span.1 as usize];
+                (p.name.as_deref(), p.ty.as_deref(), text)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Some("id"), Some("UInt64"), "{id:UInt64}"),
+                (None, None, "?"),
+                (Some("from"), Some("DateTime64(3)"), "{from: DateTime64(3)}"),
+                (Some("1"), None, "$1"),
+                (Some("n"), Some("UInt8"), "{n:UInt8}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_parameters() {
+        let sql = "SELECT a ? b : c, $$x$$ FROM t";
+        assert!(parameters(&parse(sql).tree, sql).is_empty());
+    }
+}
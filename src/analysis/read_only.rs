@@ -0,0 +1,74 @@
+use crate::parser::diagnostic::Parse;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::SyntaxChild;
+
+/// True if a top-level statement node only reads: a SELECT (with or without
+/// WITH), a UNION of SELECTs, SHOW, DESCRIBE or EXPLAIN. Everything else,
+/// including error nodes, is treated as potentially writing.
+pub fn is_read_only_statement(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::SelectStatement
+            | SyntaxKind::UnionClause
+            | SyntaxKind::ShowStatement
+            | SyntaxKind::DescribeStatement
+            | SyntaxKind::ExplainStatement
+    )
+}
+
+/// True if every top-level statement of a parsed script is read-only, e.g.
+/// to gate which scripts a user may run. Any INSERT, CREATE, ALTER, DROP or
+/// `INTO OUTFILE` makes the script not read-only. So does any syntax error,
+/// so the check fails closed. A script with no statements is read-only.
+pub fn is_read_only(parse: &Parse) -> bool {
+    let tree = &parse.tree;
+    if !parse.errors.is_empty()
+        || tree.find_first(SyntaxKind::Error).is_some()
+        || tree.find_first(SyntaxKind::IntoOutfileClause).is_some()
+    {
+        return false;
+    }
+    tree.children
+        .iter()
+        .filter_map(SyntaxChild::as_tree)
+        .all(|statement| is_read_only_statement(statement.kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn read_only(sql: &str) -> bool {
+        is_read_only(&parse(sql))
+    }
+
+    #[test]
+    fn read_only_script() {
+        assert!(read_only(
+            "SELECT 1; WITH 2 AS x SELECT x; SELECT 1 UNION ALL SELECT 2; \
+             SHOW TABLES; DESCRIBE t; EXPLAIN SELECT * FROM t"
+        ));
+        assert!(read_only(""));
+    }
+
+    #[test]
+    fn mixed_script() {
+        assert!(!read_only("SELECT 1; INSERT INTO t VALUES (1)"));
+        assert!(!read_only("CREATE TABLE t (a UInt8) ENGINE = Memory; SELECT * FROM t"));
+        assert!(!read_only("SELECT 1; ALTER TABLE t DELETE WHERE 1"));
+        assert!(!read_only("DROP TABLE t"));
+    }
+
+    #[test]
+    fn unparsable_statement_is_not_read_only() {
+        assert!(!read_only("SELECT 1; FROBNICATE t"));
+        assert!(!read_only("SELECT 1 DROP TABLE t"));
+        assert!(!read_only("SELECT (1 DROP TABLE t)"));
+    }
+
+    #[test]
+    fn into_outfile_is_not_read_only() {
+        assert!(!read_only("SELECT 1 INTO OUTFILE 'x'"));
+    }
+}
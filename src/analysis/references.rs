@@ -0,0 +1,234 @@
+//! What each statement reads: its tables and the columns it selects, and
+//! selected columns qualified by a table that isn't there.
+
+use crate::analysis::scope::{build_scope, collect_all_table_refs, normalize_identifier};
+use crate::analysis::validate::{column_path, push_unique};
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Tables and selected columns of every top-level statement.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AnalysisResult {
+    /// One entry per statement, in source order. Error nodes between
+    /// statements are skipped.
+    pub statements: Vec<StatementReferences>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StatementReferences {
+    /// The statement node's kind, e.g. `SelectStatement`.
+    pub kind: SyntaxKind,
+    /// Byte range of the statement.
+    pub span: (u32, u32),
+    /// Tables in FROM and JOIN clauses as `db.table` or `table`,
+    /// deduplicated, in source order. Includes tables inside subqueries.
+    pub tables: Vec<String>,
+    /// Column references in SELECT lists as written (`a`, `t.a`), unquoted,
+    /// deduplicated, in source order. Includes subqueries' SELECT lists.
+    pub columns: Vec<String>,
+    /// Selected columns whose qualifier names no table or alias in scope.
+    pub errors: Vec<ReferenceError>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ReferenceError {
+    pub message: String,
+    /// Byte range of the offending qualifier.
+    pub span: (u32, u32),
+}
+
+pub fn analyze(tree: &SyntaxTree, source: &str) -> AnalysisResult {
+    let statements = tree
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_tree)
+        .filter(|statement| statement.kind != SyntaxKind::Error)
+        .map(|statement| statement_references(statement, source))
+        .collect();
+    AnalysisResult { statements }
+}
+
+fn statement_references(statement: &SyntaxTree, source: &str) -> StatementReferences {
+    let mut tables = Vec::new();
+    for table_ref in collect_all_table_refs(statement, source) {
+        let name = match table_ref.database {
+            Some(db) => format!("{db}.{}", table_ref.table),
+            None => table_ref.table,
+        };
+        push_unique(&mut tables, name);
+    }
+
+    let mut columns = Vec::new();
+    for select in statement.find_all(SyntaxKind::SelectClause) {
+        for column in select.find_all(SyntaxKind::ColumnReference) {
+            if let Some(name) = column_path(column, source) {
+                push_unique(&mut columns, name);
+            }
+        }
+    }
+
+    let mut errors = Vec::new();
+    check_qualifiers(statement, source, false, &mut Vec::new(), &mut errors);
+
+    StatementReferences {
+        kind: statement.kind,
+        span: (statement.start, statement.end),
+        tables,
+        columns,
+        errors,
+    }
+}
+
+/// Check the qualifier of each qualified column in a SELECT list against the
+/// tables and aliases of its own and enclosing SELECTs. `t.col` must name a
+/// table or alias `t`; `db.t.col` may be read either as a database-qualified
+/// column or as `t.col` with a subcolumn, so either part may match.
+/// Unqualified columns need a schema to resolve and are left alone. Without
+/// one, a Nested column read as `n.x` is also reported.
+fn check_qualifiers(
+    tree: &SyntaxTree,
+    source: &str,
+    in_select_list: bool,
+    known: &mut Vec<String>,
+    errors: &mut Vec<ReferenceError>,
+) {
+    match tree.kind {
+        SyntaxKind::SelectStatement => {
+            let outer_len = known.len();
+            let scope = build_scope(tree, source);
+            known.extend(scope.table_refs.into_iter().map(|table_ref| table_ref.table));
+            known.extend(scope.table_aliases.into_iter().map(|alias| alias.name));
+            // `ARRAY JOIN arr AS el` makes `el.x` valid for a tuple or Nested
+            // `arr`.
+            for clause in tree.children.iter().filter_map(SyntaxChild::as_tree) {
+                if clause.kind == SyntaxKind::ArrayJoinClause {
+                    let aliases = build_scope(clause, source).column_aliases;
+                    known.extend(aliases.into_iter().map(|alias| alias.name));
+                }
+            }
+            for child in tree.children.iter().filter_map(SyntaxChild::as_tree) {
+                check_qualifiers(child, source, false, known, errors);
+            }
+            known.truncate(outer_len);
+            return;
+        }
+        // Lambda parameters shadow table names: `x -> x.a`.
+        SyntaxKind::LambdaExpression => return,
+        SyntaxKind::ColumnReference if in_select_list => {
+            let parts: Vec<_> = tree
+                .children
+                .iter()
+                .filter_map(SyntaxChild::as_token)
+                .filter(|t| matches!(t.kind, SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier))
+                .collect();
+            if parts.len() < 2 {
+                return;
+            }
+            let candidates = &parts[..if parts.len() == 2 { 1 } else { 2 }];
+            let is_known = candidates
+                .iter()
+                .any(|part| known.contains(&normalize_identifier(part, source)));
+            if !is_known {
+                let qualifier = candidates[candidates.len() - 1];
+                errors.push(ReferenceError {
+                    message: format!(
+                        "Unknown table or alias `{}`",
+                        normalize_identifier(qualifier, source)
+                    ),
+                    span: (qualifier.start, qualifier.end),
+                });
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let in_select_list = in_select_list || tree.kind == SyntaxKind::SelectClause;
+    for child in tree.children.iter().filter_map(SyntaxChild::as_tree) {
+        check_qualifiers(child, source, in_select_list, known, errors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn tables_and_selected_columns() {
+        let sql = "SELECT a, t.b, count(c) FROM db.t JOIN u ON t.id = u.id WHERE d > 0";
+        let result = parse(sql);
+        let analysis = analyze(&result.tree, sql);
+        assert_eq!(analysis.statements.len(), 1);
+        let statement = &analysis.statements[0];
+        assert_eq!(statement.kind, SyntaxKind::SelectStatement);
+        assert_eq!(statement.tables, ["db.t", "u"]);
+        // `t.id`, `u.id` and `d` aren't selected.
+        assert_eq!(statement.columns, ["a", "t.b", "c"]);
+    }
+
+    #[test]
+    fn one_entry_per_statement() {
+        let sql = "SELECT a FROM t; SELECT x, y FROM (SELECT x, y, z FROM u) JOIN v USING (x);";
+        let result = parse(sql);
+        let analysis = analyze(&result.tree, sql);
+        assert_eq!(analysis.statements.len(), 2);
+        assert_eq!(analysis.statements[0].tables, ["t"]);
+        assert_eq!(analysis.statements[0].columns, ["a"]);
+        assert_eq!(analysis.statements[1].tables, ["u", "v"]);
+        assert_eq!(analysis.statements[1].columns, ["x", "y", "z"]);
+        assert_eq!(&sql[analysis.statements[0].span.0 as usize..][..6], "SELECT");
+    }
+
+    #[test]
+    fn unknown_qualifier() {
+        let sql = "SELECT a.x FROM b";
+        let result = parse(sql);
+        let errors = &analyze(&result.tree, sql).statements[0].errors;
+        assert_eq!(
+            errors,
+            &[ReferenceError {
+                message: "Unknown table or alias `a`".to_string(),
+                span: (7, 8),
+            }]
+        );
+    }
+
+    #[test]
+    fn known_qualifiers() {
+        let sql = "SELECT b.x, db.b.y, s.z, n.number, c.w, `b`.v, arrayMap(p -> p.q, arr), \
+            (SELECT b.u FROM d) \
+            FROM db.b, (SELECT 1 AS z) AS s, numbers(3) n JOIN e AS c ON c.id = b.id";
+        let result = parse(sql);
+        assert_eq!(analyze(&result.tree, sql).statements[0].errors, []);
+    }
+
+    #[test]
+    fn array_join_aliases_qualify_columns() {
+        let sql = "SELECT el.x, n.y FROM t ARRAY JOIN arr AS el LEFT ARRAY JOIN nested n";
+        let result = parse(sql);
+        assert_eq!(analyze(&result.tree, sql).statements[0].errors, []);
+    }
+
+    #[test]
+    fn cte_names_qualify_columns() {
+        let sql = "WITH 1 AS k, c AS (SELECT 2 AS y) SELECT c.y, k FROM c";
+        let result = parse(sql);
+        let statement = &analyze(&result.tree, sql).statements[0];
+        assert_eq!(statement.tables, ["c"]);
+        assert_eq!(statement.errors, []);
+    }
+
+    #[test]
+    fn qualifier_scoped_to_its_select() {
+        // `t` is bound only inside the subquery.
+        let sql = "SELECT t.x FROM (SELECT t.x FROM t) AS s";
+        let result = parse(sql);
+        let errors = &analyze(&result.tree, sql).statements[0].errors;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span, (7, 8));
+    }
+}
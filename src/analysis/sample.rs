@@ -0,0 +1,138 @@
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// What a `SAMPLE` size or offset written as a literal asks for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SampleSize {
+    /// `SAMPLE 0.1`: a share of the data. `SAMPLE 1` is the whole table.
+    Ratio(f64),
+    /// `SAMPLE 1/10`: a share written as a fraction.
+    Fraction { numerator: u64, denominator: u64 },
+    /// `SAMPLE 10000`: roughly this many rows.
+    Rows(u64),
+}
+
+impl SampleSize {
+    /// True if ClickHouse accepts this value as a SAMPLE size: a ratio in
+    /// `(0, 1]` or a row count.
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            SampleSize::Ratio(ratio) => ratio > 0.0 && ratio <= 1.0,
+            SampleSize::Fraction {
+                numerator,
+                denominator,
+            } => numerator > 0 && denominator > 0 && numerator <= denominator,
+            SampleSize::Rows(rows) => rows > 0,
+        }
+    }
+}
+
+/// A `SAMPLE` clause with its size and optional `OFFSET` classified. Either
+/// is `None` when it isn't a plain number or fraction, e.g. a query
+/// parameter.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Sample {
+    pub size: Option<SampleSize>,
+    /// Byte range of the size expression, excluding trailing trivia.
+    pub size_span: (u32, u32),
+    pub offset: Option<SampleSize>,
+}
+
+/// Every `SAMPLE` clause in the tree, in source order.
+pub fn samples(tree: &SyntaxTree, source: &str) -> Vec<Sample> {
+    tree.find_all(SyntaxKind::SampleClause)
+        .into_iter()
+        .filter_map(|clause| sample(clause, source))
+        .collect()
+}
+
+fn sample(clause: &SyntaxTree, source: &str) -> Option<Sample> {
+    // SampleClause: 'SAMPLE' size ['OFFSET' offset]
+    let mut values = clause.children.iter().filter_map(SyntaxChild::as_tree);
+    let size = values.next()?;
+    Some(Sample {
+        size: classify(size, source),
+        size_span: size.significant_span().unwrap_or((size.start, size.start)),
+        offset: values.next().and_then(|offset| classify(offset, source)),
+    })
+}
+
+/// Classify a literal `n`, `0.5` or `a/b`. Anything else is `None`.
+pub fn classify(expr: &SyntaxTree, source: &str) -> Option<SampleSize> {
+    match expr.kind {
+        SyntaxKind::NumberLiteral => {
+            let text = number_text(expr, source)?;
+            match text.parse::<u64>() {
+                Ok(0) => Some(SampleSize::Ratio(0.0)),
+                Ok(1) => Some(SampleSize::Ratio(1.0)),
+                Ok(rows) => Some(SampleSize::Rows(rows)),
+                Err(_) => text.parse::<f64>().ok().map(SampleSize::Ratio),
+            }
+        }
+        SyntaxKind::BinaryExpression => {
+            let mut children = expr.children.iter().filter(|child| !child.is_trivia());
+            let numerator = children.next()?.get_tree_with_kind(SyntaxKind::NumberLiteral)?;
+            children.next()?.get_token_with_kind(SyntaxKind::Slash)?;
+            let denominator = children.next()?.get_tree_with_kind(SyntaxKind::NumberLiteral)?;
+            Some(SampleSize::Fraction {
+                numerator: number_text(numerator, source)?.parse().ok()?,
+                denominator: number_text(denominator, source)?.parse().ok()?,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The digits of a number literal, without `_` separators.
+fn number_text(literal: &SyntaxTree, source: &str) -> Option<String> {
+    let token = literal.children.iter().find(|child| !child.is_trivia())?.as_token()?;
+    Some(token.text(source).replace('_', ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn sample_of(sql: &str) -> Sample {
+        let result = parse(sql);
+        samples(&result.tree, &result.source).remove(0)
+    }
+
+    #[test]
+    fn classifies_ratio_fraction_and_rows() {
+        assert_eq!(sample_of("SELECT a FROM t SAMPLE 0.1").size, Some(SampleSize::Ratio(0.1)));
+        assert_eq!(sample_of("SELECT a FROM t SAMPLE 1").size, Some(SampleSize::Ratio(1.0)));
+        assert_eq!(
+            sample_of("SELECT a FROM t SAMPLE 1/10").size,
+            Some(SampleSize::Fraction {
+                numerator: 1,
+                denominator: 10
+            })
+        );
+        assert_eq!(sample_of("SELECT a FROM t SAMPLE 10000").size, Some(SampleSize::Rows(10000)));
+    }
+
+    #[test]
+    fn classifies_offset() {
+        let sample = sample_of("SELECT a FROM t SAMPLE 1/10 OFFSET 0.5");
+        assert_eq!(sample.offset, Some(SampleSize::Ratio(0.5)));
+        assert_eq!(sample.size_span, (23, 27));
+    }
+
+    #[test]
+    fn non_literal_size_is_unclassified() {
+        assert_eq!(sample_of("SELECT a FROM t SAMPLE {n:Float64}").size, None);
+    }
+
+    #[test]
+    fn validity() {
+        assert!(!SampleSize::Ratio(0.0).is_valid());
+        assert!(!SampleSize::Ratio(1.5).is_valid());
+        assert!(!SampleSize::Fraction { numerator: 1, denominator: 0 }.is_valid());
+        assert!(!SampleSize::Fraction { numerator: 3, denominator: 2 }.is_valid());
+        assert!(SampleSize::Rows(2).is_valid());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::lexer::token::Token;
 use crate::parser::syntax_kind::SyntaxKind;
 use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
 
@@ -26,7 +27,12 @@ pub struct QueryScope {
     pub ctes: Vec<NameBinding>,
     pub table_aliases: Vec<NameBinding>,
     pub column_aliases: Vec<NameBinding>,
+    /// `WITH expr AS name` constants. `definition_range` is the range of
+    /// `expr`. These names are also in `column_aliases`.
+    pub constants: Vec<NameBinding>,
     pub table_refs: Vec<TableRef>,
+    /// `WITH RECURSIVE`: each CTE is also in scope within its own body.
+    pub recursive: bool,
 }
 
 /// Build a scope from the CST of a statement.
@@ -40,12 +46,16 @@ fn collect_scope(tree: &SyntaxTree, source: &str, scope: &mut QueryScope) {
     match tree.kind {
         SyntaxKind::WithClause => {
             collect_ctes(tree, source, scope);
+            // `WITH expr AS name` binds a column alias for the whole query.
+            collect_column_aliases(tree, source, scope);
+            collect_constants(tree, source, scope);
             return; // Don't recurse further into WITH
         }
         SyntaxKind::FromClause | SyntaxKind::JoinClause => {
             collect_table_refs(tree, source, scope);
         }
-        SyntaxKind::SelectClause => {
+        // `ARRAY JOIN arr AS el` binds `el` like a SELECT alias.
+        SyntaxKind::SelectClause | SyntaxKind::ArrayJoinClause => {
             collect_column_aliases(tree, source, scope);
         }
         _ => {}
@@ -62,10 +72,93 @@ fn collect_scope(tree: &SyntaxTree, source: &str, scope: &mut QueryScope) {
     }
 }
 
+/// Every table referenced anywhere in the tree, including inside subqueries
+/// (IN-subqueries, scalar subqueries, derived tables), in source order.
+pub fn collect_all_table_refs(tree: &SyntaxTree, source: &str) -> Vec<TableRef> {
+    let mut refs: Vec<TableRef> = tree
+        .find_all(SyntaxKind::SelectStatement)
+        .into_iter()
+        .flat_map(|stmt| build_scope(stmt, source).table_refs)
+        .collect();
+    refs.sort_by_key(|r| r.range.0);
+    refs
+}
+
+/// Build the scope visible at a byte offset: the innermost statement's scope,
+/// plus any recursive CTE whose own body contains the offset.
+///
+/// In `WITH ... SELECT ... UNION ALL SELECT ...` the WITH clause is parsed as
+/// part of the first SELECT, as in ClickHouse's own AST, but it applies to
+/// every branch of the set operation, so later branches see it too.
+pub fn build_scope_at(tree: &SyntaxTree, source: &str, offset: u32) -> Option<QueryScope> {
+    let mut statements = Vec::new();
+    collect_enclosing_statements(tree, offset, &mut statements);
+    let (innermost, outer) = statements.split_last()?;
+
+    let mut scope = build_scope(innermost, source);
+    if let Some(with) = union_with_clause(tree, innermost) {
+        collect_scope(with, source, &mut scope);
+    }
+    for stmt in outer {
+        let outer_scope = build_scope(stmt, source);
+        if !outer_scope.recursive {
+            continue;
+        }
+        scope.ctes.extend(outer_scope.ctes.into_iter().filter(|cte| {
+            cte.definition_range.0 <= offset && offset <= cte.definition_range.1
+        }));
+    }
+    Some(scope)
+}
+
+/// The WITH clause leading the set operation that `branch` is a later
+/// branch of, if any.
+fn union_with_clause<'a>(tree: &'a SyntaxTree, branch: &SyntaxTree) -> Option<&'a SyntaxTree> {
+    let union = tree.find_all(SyntaxKind::UnionClause).into_iter().find(|union| {
+        union_branches(union).any(|b| std::ptr::eq(b, branch))
+    })?;
+    if union_branches(union).next().is_some_and(|first| std::ptr::eq(first, branch)) {
+        return None; // The WITH is already part of the branch's own scope.
+    }
+    leading_with_clause(union)
+}
+
+/// The WITH clause of a set operation's first SELECT, which ClickHouse
+/// applies to all of its branches.
+pub(crate) fn leading_with_clause(union: &SyntaxTree) -> Option<&SyntaxTree> {
+    union_branches(union)
+        .next()?
+        .children
+        .iter()
+        .find_map(|child| child.get_tree_with_kind(SyntaxKind::WithClause))
+}
+
+/// The SELECTs combined by a set operation, flattening `a UNION b UNION c`.
+fn union_branches(union: &SyntaxTree) -> Box<dyn Iterator<Item = &SyntaxTree> + '_> {
+    Box::new(
+        union
+            .children
+            .iter()
+            .filter_map(SyntaxChild::as_tree)
+            .flat_map(|child| match child.kind {
+                SyntaxKind::UnionClause => union_branches(child),
+                SyntaxKind::SelectStatement => Box::new(std::iter::once(child)),
+                _ => Box::new(std::iter::empty()),
+            }),
+    )
+}
+
 /// Extract CTEs from a WITH clause.
 /// CST: WithClause → ColumnList → WithExpressionItem
 fn collect_ctes(tree: &SyntaxTree, source: &str, scope: &mut QueryScope) {
     for child in &tree.children {
+        if let SyntaxChild::Token(token) = child {
+            if token.kind == SyntaxKind::BareWord
+                && token.text(source).eq_ignore_ascii_case("RECURSIVE")
+            {
+                scope.recursive = true;
+            }
+        }
         if let SyntaxChild::Tree(subtree) = child {
             if subtree.kind == SyntaxKind::WithExpressionItem {
                 if let Some(binding) = extract_cte(subtree, source) {
@@ -79,15 +172,34 @@ fn collect_ctes(tree: &SyntaxTree, source: &str, scope: &mut QueryScope) {
     }
 }
 
-/// Strip surrounding quotes (backticks or double-quotes) from an identifier.
-fn unquote(s: &str) -> &str {
-    if (s.starts_with('`') && s.ends_with('`'))
-        || (s.starts_with('"') && s.ends_with('"'))
-    {
-        &s[1..s.len() - 1]
-    } else {
-        s
+/// The lookup key for an identifier token: quotes stripped and escapes
+/// resolved, case preserved. ClickHouse identifiers are case-sensitive, so
+/// `` `MyTable` `` and `MyTable` are the same name but `mytable` is not.
+pub fn normalize_identifier(token: &Token, source: &str) -> String {
+    let text = token.text(source);
+    if token.kind != SyntaxKind::QuotedIdentifier || text.len() < 2 {
+        return text.to_string();
+    }
+    let quote = text.chars().next().unwrap();
+    let inner = &text[1..text.len() - 1];
+
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            // Backslash escape: \` -> `, \\ -> \
+            if let Some(next) = chars.next() {
+                out.push(next);
+            }
+        } else if c == quote && chars.peek() == Some(&quote) {
+            // Doubled quote: `a``b` -> a`b
+            chars.next();
+            out.push(c);
+        } else {
+            out.push(c);
+        }
     }
+    out
 }
 
 /// Extract CTE name from a WITH expression item.
@@ -106,9 +218,8 @@ fn extract_cte(tree: &SyntaxTree, source: &str) -> Option<NameBinding> {
                     });
                 }
             } else if token.kind == SyntaxKind::QuotedIdentifier {
-                let text = unquote(token.text(source));
                 return Some(NameBinding {
-                    name: text.to_string(),
+                    name: normalize_identifier(token, source),
                     range: (token.start, token.end),
                     definition_range: (tree.start, tree.end),
                 });
@@ -134,18 +245,23 @@ fn collect_table_refs(tree: &SyntaxTree, source: &str, scope: &mut QueryScope) {
                     last_table_ref = extract_table_identifier(subtree, source);
                 }
                 SyntaxKind::TableAlias => {
-                    // Attach alias to the most recent table ref
-                    if let Some(ref mut tref) = last_table_ref {
-                        if let Some((alias_name, alias_token)) =
-                            extract_alias_name(subtree, source)
-                        {
+                    if let Some((alias_name, alias_token)) = extract_alias_name(subtree, source) {
+                        // Attach alias to the most recent table ref, if the
+                        // alias doesn't name a subquery or table function
+                        if let Some(ref mut tref) = last_table_ref {
                             tref.alias = Some(alias_name.clone());
-                            scope.table_aliases.push(NameBinding {
-                                name: alias_name,
-                                range: (alias_token.start, alias_token.end),
-                                definition_range: (subtree.start, subtree.end),
-                            });
                         }
+                        scope.table_aliases.push(NameBinding {
+                            name: alias_name,
+                            range: (alias_token.start, alias_token.end),
+                            definition_range: (subtree.start, subtree.end),
+                        });
+                    }
+                }
+                SyntaxKind::SubqueryExpression | SyntaxKind::TableFunction => {
+                    // A following alias belongs to this, not the previous table
+                    if let Some(tref) = last_table_ref.take() {
+                        scope.table_refs.push(tref);
                     }
                 }
                 SyntaxKind::TableExpression => {
@@ -175,7 +291,7 @@ fn collect_table_refs(tree: &SyntaxTree, source: &str, scope: &mut QueryScope) {
 fn extract_alias_name<'a>(
     tree: &'a SyntaxTree,
     source: &'a str,
-) -> Option<(String, &'a crate::Token)> {
+) -> Option<(String, &'a Token)> {
     let mut last_ident = None;
     for child in &tree.children {
         if let SyntaxChild::Token(token) = child {
@@ -185,8 +301,7 @@ fn extract_alias_name<'a>(
                     last_ident = Some((text.to_string(), token));
                 }
             } else if token.kind == SyntaxKind::QuotedIdentifier {
-                let text = unquote(token.text(source));
-                last_ident = Some((text.to_string(), token));
+                last_ident = Some((normalize_identifier(token, source), token));
             }
         }
     }
@@ -198,7 +313,7 @@ fn extract_table_identifier(tree: &SyntaxTree, source: &str) -> Option<TableRef>
     for child in &tree.children {
         if let SyntaxChild::Token(token) = child {
             if token.kind == SyntaxKind::BareWord || token.kind == SyntaxKind::QuotedIdentifier {
-                parts.push(token.text(source).to_string());
+                parts.push(normalize_identifier(token, source));
             }
         }
     }
@@ -227,7 +342,28 @@ fn collect_column_aliases(tree: &SyntaxTree, source: &str, scope: &mut QueryScop
         if let SyntaxChild::Tree(subtree) = child {
             match subtree.kind {
                 SyntaxKind::ColumnAlias => {
-                    if let Some((name, token)) = extract_alias_name(subtree, source) {
+                    // `expr AS (a, b)` binds every name in the list.
+                    let list = subtree
+                        .children
+                        .iter()
+                        .find_map(|child| child.get_tree_with_kind(SyntaxKind::IdentifierList));
+                    if let Some(list) = list {
+                        let names = list.children.iter().filter_map(SyntaxChild::as_token).filter(
+                            |token| {
+                                matches!(
+                                    token.kind,
+                                    SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier
+                                )
+                            },
+                        );
+                        for token in names {
+                            scope.column_aliases.push(NameBinding {
+                                name: normalize_identifier(token, source),
+                                range: (token.start, token.end),
+                                definition_range: (subtree.start, subtree.end),
+                            });
+                        }
+                    } else if let Some((name, token)) = extract_alias_name(subtree, source) {
                         scope.column_aliases.push(NameBinding {
                             name,
                             range: (token.start, token.end),
@@ -244,18 +380,45 @@ fn collect_column_aliases(tree: &SyntaxTree, source: &str, scope: &mut QueryScop
     }
 }
 
-/// Find the enclosing statement node for a given byte offset.
-pub fn find_enclosing_statement(tree: &SyntaxTree, offset: u32) -> Option<&SyntaxTree> {
-    if tree.start > tree.end {
-        return None;
+/// Extract `expr AS name` items from a WITH clause.
+/// CST: WithClause → ColumnList → expr, ColumnAlias
+fn collect_constants(tree: &SyntaxTree, source: &str, scope: &mut QueryScope) {
+    let Some(items) = tree
+        .children
+        .iter()
+        .find_map(|child| child.get_tree_with_kind(SyntaxKind::ColumnList))
+    else {
+        return;
+    };
+    let mut value: Option<&SyntaxTree> = None;
+    for subtree in items.children.iter().filter_map(SyntaxChild::as_tree) {
+        if subtree.kind != SyntaxKind::ColumnAlias {
+            value = Some(subtree);
+            continue;
+        }
+        let expr = value.take();
+        let (Some(expr), Some((name, token))) = (expr, extract_alias_name(subtree, source)) else {
+            continue;
+        };
+        scope.constants.push(NameBinding {
+            name,
+            range: (token.start, token.end),
+            definition_range: (expr.start, expr.end),
+        });
     }
-    if offset < tree.start || offset > tree.end {
-        return None;
+}
+
+impl QueryScope {
+    /// The `WITH expr AS name` constant a bare reference to `name` resolves
+    /// to, if any. Names are case-sensitive, like ClickHouse identifiers.
+    pub fn resolve_constant(&self, name: &str) -> Option<&NameBinding> {
+        self.constants.iter().find(|constant| constant.name == name)
     }
+}
 
-    // Check if this is a statement node
-    let is_statement = matches!(
-        tree.kind,
+fn is_statement(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
         SyntaxKind::SelectStatement
             | SyntaxKind::InsertStatement
             | SyntaxKind::CreateStatement
@@ -266,7 +429,41 @@ pub fn find_enclosing_statement(tree: &SyntaxTree, offset: u32) -> Option<&Synta
             | SyntaxKind::ShowStatement
             | SyntaxKind::ExplainStatement
             | SyntaxKind::DescribeStatement
-    );
+    )
+}
+
+/// Collect every statement node containing the offset, outermost first.
+fn collect_enclosing_statements<'a>(
+    tree: &'a SyntaxTree,
+    offset: u32,
+    out: &mut Vec<&'a SyntaxTree>,
+) {
+    if tree.start > tree.end || offset < tree.start || offset > tree.end {
+        return;
+    }
+    if is_statement(tree.kind) {
+        out.push(tree);
+    }
+    for child in &tree.children {
+        if let SyntaxChild::Tree(subtree) = child {
+            if subtree.start <= offset && offset <= subtree.end {
+                collect_enclosing_statements(subtree, offset, out);
+                return;
+            }
+        }
+    }
+}
+
+/// Find the enclosing statement node for a given byte offset.
+pub fn find_enclosing_statement(tree: &SyntaxTree, offset: u32) -> Option<&SyntaxTree> {
+    if tree.start > tree.end {
+        return None;
+    }
+    if offset < tree.start || offset > tree.end {
+        return None;
+    }
+
+    let is_statement = is_statement(tree.kind);
 
     // Try to find a deeper statement first
     for child in &tree.children {
@@ -298,6 +495,69 @@ mod tests {
         assert_eq!(scope.ctes[0].name, "cte");
     }
 
+    #[test]
+    fn recursive_cte_in_scope_within_own_body() {
+        let sql = "WITH RECURSIVE x AS (SELECT 1 AS id UNION ALL SELECT id + 1 FROM x WHERE id < 5) SELECT * FROM x";
+        let parse = parser::parse(sql);
+        let scope = build_scope(&parse.tree, &parse.source);
+        assert!(scope.recursive);
+        assert_eq!(scope.ctes.len(), 1);
+
+        // `x` inside the CTE body resolves to the CTE itself.
+        let inner = sql.find("FROM x").unwrap() as u32 + 5;
+        let scope = build_scope_at(&parse.tree, &parse.source, inner).unwrap();
+        assert!(scope.ctes.iter().any(|c| c.name == "x"));
+    }
+
+    #[test]
+    fn non_recursive_cte_not_in_scope_within_own_body() {
+        let sql = "WITH x AS (SELECT 1 FROM x) SELECT * FROM x";
+        let parse = parser::parse(sql);
+        let inner = sql.find("FROM x").unwrap() as u32 + 5;
+        let scope = build_scope_at(&parse.tree, &parse.source, inner).unwrap();
+        assert!(!scope.recursive);
+        assert!(scope.ctes.is_empty());
+    }
+
+    #[test]
+    fn tables_from_where_subqueries_are_collected() {
+        let sql = "SELECT * FROM t WHERE id IN (SELECT id FROM t2) AND x > (SELECT avg(x) FROM t3)";
+        let parse = parser::parse(sql);
+        // The statement's own scope stops at subquery boundaries...
+        let scope = build_scope(&parse.tree, &parse.source);
+        assert_eq!(scope.table_refs.len(), 1);
+        // ...while the deep collection finds both subquery tables.
+        let tables: Vec<String> = collect_all_table_refs(&parse.tree, &parse.source)
+            .into_iter()
+            .map(|r| r.table)
+            .collect();
+        assert_eq!(tables, ["t", "t2", "t3"]);
+    }
+
+    #[test]
+    fn normalize_identifier_strips_quotes_and_keeps_case() {
+        let sql = "SELECT 1 FROM `MyTable`, MyTable, \"MyTable\", mytable, `a``b`, `c\\`d`";
+        let tokens = crate::lexer::tokenizer::tokenize(sql);
+        let keys: Vec<String> = tokens
+            .iter()
+            .filter(|t| matches!(t.kind, SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier))
+            .skip(2) // SELECT, FROM
+            .map(|t| normalize_identifier(t, sql))
+            .collect();
+        assert_eq!(keys, ["MyTable", "MyTable", "MyTable", "mytable", "a`b", "c`d"]);
+    }
+
+    #[test]
+    fn quoted_table_refs_are_normalized() {
+        let sql = "SELECT 1 FROM `db`.`MyTable` AS `T`";
+        let parse = parser::parse(sql);
+        let scope = build_scope(&parse.tree, &parse.source);
+        let tref = &scope.table_refs[0];
+        assert_eq!(tref.database.as_deref(), Some("db"));
+        assert_eq!(tref.table, "MyTable");
+        assert_eq!(tref.alias.as_deref(), Some("T"));
+    }
+
     #[test]
     fn table_alias_scope() {
         let sql = "SELECT t.a FROM my_table AS t";
@@ -309,6 +569,21 @@ mod tests {
         assert_eq!(scope.table_refs[0].table, "my_table");
     }
 
+    #[test]
+    fn subquery_and_table_function_aliases_scope() {
+        let sql = "SELECT * FROM t, (SELECT 1) AS x, numbers(10) n JOIN u ON x.a = n.number";
+        let parse = parser::parse(sql);
+        let scope = build_scope(&parse.tree, &parse.source);
+        let aliases: Vec<&str> = scope.table_aliases.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(aliases, ["x", "n"]);
+        let tables: Vec<(&str, Option<&str>)> = scope
+            .table_refs
+            .iter()
+            .map(|t| (t.table.as_str(), t.alias.as_deref()))
+            .collect();
+        assert_eq!(tables, [("t", None), ("u", None)]);
+    }
+
     #[test]
     fn table_ref_with_database() {
         let sql = "SELECT 1 FROM mydb.mytable";
@@ -328,4 +603,85 @@ mod tests {
         assert_eq!(scope.column_aliases.len(), 1);
         assert_eq!(scope.column_aliases[0].name, "total");
     }
+
+    #[test]
+    fn tuple_alias_list_scope() {
+        let sql = "SELECT f(x) AS (lo, `hi`) FROM t";
+        let parse = parser::parse(sql);
+        let scope = build_scope(&parse.tree, &parse.source);
+        let names: Vec<&str> = scope.column_aliases.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["lo", "hi"]);
+    }
+
+    #[test]
+    fn array_join_alias_scope() {
+        let sql = "SELECT el FROM t ARRAY JOIN arr AS el, [1, 2] AS n";
+        let parse = parser::parse(sql);
+        let scope = build_scope(&parse.tree, &parse.source);
+        let names: Vec<&str> = scope.column_aliases.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["el", "n"]);
+    }
+
+    #[test]
+    fn with_expression_alias_scope() {
+        let sql = "WITH 10 AS threshold, c AS (SELECT 1) SELECT a FROM t WHERE a > threshold";
+        let parse = parser::parse(sql);
+        let scope = build_scope(&parse.tree, &parse.source);
+        let aliases: Vec<&str> = scope.column_aliases.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(aliases, ["threshold"]);
+        assert_eq!(scope.ctes.len(), 1);
+    }
+
+    #[test]
+    fn mixed_scalar_and_subquery_with_items_scope() {
+        let sql = "WITH cte AS (SELECT 2 AS y), 1 AS x, (SELECT max(y) FROM cte) AS m \
+            SELECT x, m FROM cte";
+        let parse = parser::parse(sql);
+        assert!(parse.errors.is_empty());
+        let scope = build_scope(&parse.tree, &parse.source);
+        let ctes: Vec<&str> = scope.ctes.iter().map(|c| c.name.as_str()).collect();
+        let aliases: Vec<&str> = scope.column_aliases.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(ctes, ["cte"]);
+        assert_eq!(aliases, ["x", "m"]);
+    }
+
+    #[test]
+    fn with_constants_resolve() {
+        let sql = "WITH 10 AS threshold, c AS (SELECT 1), 'x' || 'y' AS tag \
+            SELECT a FROM t WHERE a > threshold";
+        let parse = parser::parse(sql);
+        let scope = build_scope(&parse.tree, &parse.source);
+        let constants: Vec<(&str, &str)> = scope
+            .constants
+            .iter()
+            .map(|c| {
+                let (start, end) = c.definition_range;
+                (c.name.as_str(), sql[start as usize..end as usize].trim())
+            })
+            .collect();
+        assert_eq!(constants, [("threshold", "10"), ("tag", "'x' || 'y'")]);
+        assert_eq!(
+            scope.resolve_constant("threshold").map(|c| c.range),
+            Some((11, 20))
+        );
+        assert!(scope.resolve_constant("c").is_none());
+        assert!(scope.resolve_constant("a").is_none());
+    }
+
+    #[test]
+    fn with_clause_applies_to_every_union_branch() {
+        let sql = "WITH c AS (SELECT 1 AS x), 2 AS k \
+            SELECT x FROM c UNION ALL SELECT x FROM c UNION ALL SELECT k FROM d";
+        let parse = parser::parse(sql);
+        let names = |offset: usize| {
+            let scope = build_scope_at(&parse.tree, &parse.source, offset as u32).unwrap();
+            let ctes: Vec<String> = scope.ctes.into_iter().map(|c| c.name).collect();
+            let constants: Vec<String> = scope.constants.into_iter().map(|c| c.name).collect();
+            (ctes, constants)
+        };
+        let expected = (vec!["c".to_string()], vec!["k".to_string()]);
+        assert_eq!(names(sql.find("FROM c").unwrap()), expected);
+        assert_eq!(names(sql.rfind("FROM c").unwrap()), expected);
+        assert_eq!(names(sql.find("FROM d").unwrap()), expected);
+    }
 }
@@ -0,0 +1,107 @@
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// A quick structural overview of one statement, for classifying queries
+/// without walking the tree yourself. The clause flags cover the whole
+/// statement, subqueries included.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct StatementSummary {
+    /// The statement node's kind, e.g. `SelectStatement`, `InsertStatement`,
+    /// or `UnionClause` for a set operation.
+    pub kind: SyntaxKind,
+    /// Byte range of the statement.
+    pub span: (u32, u32),
+    pub has_where: bool,
+    pub has_group_by: bool,
+    pub has_order_by: bool,
+    pub has_limit: bool,
+    pub has_join: bool,
+    pub has_subquery: bool,
+    /// Table names and table functions referenced, counting repeats.
+    pub table_count: usize,
+}
+
+/// One summary per top-level statement, in source order. Error nodes
+/// between statements are skipped.
+pub fn statement_summary(tree: &SyntaxTree) -> Vec<StatementSummary> {
+    tree.children
+        .iter()
+        .filter_map(SyntaxChild::as_tree)
+        .filter(|statement| statement.kind != SyntaxKind::Error)
+        .map(|statement| {
+            let mut summary = StatementSummary {
+                kind: statement.kind,
+                span: (statement.start, statement.end),
+                has_where: false,
+                has_group_by: false,
+                has_order_by: false,
+                has_limit: false,
+                has_join: false,
+                has_subquery: false,
+                table_count: 0,
+            };
+            visit(statement, &mut summary);
+            summary
+        })
+        .collect()
+}
+
+fn visit(tree: &SyntaxTree, summary: &mut StatementSummary) {
+    match tree.kind {
+        SyntaxKind::WhereClause => summary.has_where = true,
+        SyntaxKind::GroupByClause => summary.has_group_by = true,
+        SyntaxKind::OrderByClause => summary.has_order_by = true,
+        SyntaxKind::LimitClause | SyntaxKind::LimitByClause => summary.has_limit = true,
+        SyntaxKind::JoinClause => summary.has_join = true,
+        SyntaxKind::SubqueryExpression => summary.has_subquery = true,
+        SyntaxKind::TableIdentifier | SyntaxKind::TableFunction => summary.table_count += 1,
+        _ => {}
+    }
+    for subtree in tree.children.iter().filter_map(SyntaxChild::as_tree) {
+        visit(subtree, summary);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn summarizes_each_statement() {
+        let sql = "SELECT a, count() FROM t JOIN u ON t.id = u.id WHERE x GROUP BY a; \
+            INSERT INTO t VALUES (1); \
+            SELECT * FROM numbers(10) WHERE n IN (SELECT n FROM s) ORDER BY n LIMIT 5";
+        let result = parse(sql);
+        let summaries = statement_summary(&result.tree);
+        assert_eq!(summaries.len(), 3);
+
+        let first = &summaries[0];
+        assert_eq!(first.kind, SyntaxKind::SelectStatement);
+        assert!(first.has_where && first.has_group_by && first.has_join);
+        assert!(!first.has_order_by && !first.has_limit && !first.has_subquery);
+        assert_eq!(first.table_count, 2);
+
+        let insert = &summaries[1];
+        assert_eq!(insert.kind, SyntaxKind::InsertStatement);
+        assert!(!insert.has_where && !insert.has_join);
+        assert_eq!(insert.table_count, 1);
+
+        let last = &summaries[2];
+        assert!(last.has_where && last.has_order_by && last.has_limit && last.has_subquery);
+        assert!(!last.has_group_by && !last.has_join);
+        assert_eq!(last.table_count, 2);
+        let (start, end) = last.span;
+        assert!(sql[start as usize..end as usize].starts_with("SELECT * FROM numbers"));
+    }
+
+    #[test]
+    fn union_is_one_statement() {
+        let result = parse("SELECT 1 UNION ALL SELECT 2 FROM t");
+        let summaries = statement_summary(&result.tree);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].kind, SyntaxKind::UnionClause);
+        assert_eq!(summaries[0].table_count, 1);
+    }
+}
@@ -0,0 +1,105 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::analysis::literals::is_literal;
+use crate::analysis::scope::normalize_identifier;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// A hash of the query's structure, for grouping queries that differ only in
+/// formatting: whitespace, comments, keyword case and identifier quoting
+/// don't change it. Identifier case does, as ClickHouse names are
+/// case-sensitive.
+///
+/// With `ignore_literals`, every literal hashes as a placeholder of its kind,
+/// so `WHERE id = 1` and `WHERE id = 2` collide but `WHERE id = 'a'` doesn't.
+///
+/// The value is stable within one build; don't persist it across versions.
+pub fn structural_hash(tree: &SyntaxTree, source: &str, ignore_literals: bool) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_tree(tree, source, ignore_literals, &mut hasher);
+    hasher.finish()
+}
+
+fn hash_tree(tree: &SyntaxTree, source: &str, ignore_literals: bool, hasher: &mut DefaultHasher) {
+    (tree.kind as u16).hash(hasher);
+    if ignore_literals && is_literal(tree.kind) {
+        return;
+    }
+    let is_name_parent = matches!(
+        tree.kind,
+        SyntaxKind::ColumnReference
+            | SyntaxKind::Identifier
+            | SyntaxKind::QualifiedName
+            | SyntaxKind::TableIdentifier
+            | SyntaxKind::ColumnAlias
+            | SyntaxKind::TableAlias
+    );
+    for child in &tree.children {
+        match child {
+            SyntaxChild::Tree(subtree) => hash_tree(subtree, source, ignore_literals, hasher),
+            SyntaxChild::Token(token) => match token.kind {
+                SyntaxKind::Whitespace | SyntaxKind::Comment => {}
+                SyntaxKind::QuotedIdentifier => {
+                    (SyntaxKind::BareWord as u16).hash(hasher);
+                    normalize_identifier(token, source).hash(hasher);
+                }
+                SyntaxKind::BareWord => {
+                    (SyntaxKind::BareWord as u16).hash(hasher);
+                    let text = token.text(source);
+                    // `AS` is the one keyword that appears among alias names.
+                    if is_name_parent && !text.eq_ignore_ascii_case("AS") {
+                        text.hash(hasher);
+                    } else {
+                        text.to_ascii_uppercase().hash(hasher);
+                    }
+                }
+                kind => {
+                    (kind as u16).hash(hasher);
+                    token.text(source).hash(hasher);
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    fn hash(sql: &str, ignore_literals: bool) -> u64 {
+        let result = parse(sql);
+        structural_hash(&result.tree, &result.source, ignore_literals)
+    }
+
+    #[test]
+    fn formatting_does_not_change_the_hash() {
+        let a = hash("SELECT a, count() FROM t WHERE b = 1 GROUP BY a", false);
+        let b = hash(
+            "select a,count()\n  from `t` -- comment\n where b=1 /* x */ group by a",
+            false,
+        );
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn names_and_structure_change_the_hash() {
+        let base = hash("SELECT a FROM t WHERE b = 1", false);
+        assert_ne!(base, hash("SELECT A FROM t WHERE b = 1", false));
+        assert_ne!(base, hash("SELECT a FROM u WHERE b = 1", false));
+        assert_ne!(base, hash("SELECT a FROM t WHERE b = 2", false));
+        assert_ne!(base, hash("SELECT a FROM t WHERE b > 1", false));
+        assert_ne!(base, hash("SELECT a AS b FROM t WHERE b = 1", false));
+    }
+
+    #[test]
+    fn ignoring_literals() {
+        let base = hash("SELECT a FROM t WHERE b = 1 AND c = 'x'", true);
+        assert_eq!(base, hash("SELECT a FROM t WHERE b = 42 AND c = 'y'", true));
+        assert_ne!(base, hash("SELECT a FROM t WHERE b = 'z' AND c = 'y'", true));
+        assert_ne!(
+            hash("SELECT a FROM t WHERE b = 1", false),
+            hash("SELECT a FROM t WHERE b = 2", false)
+        );
+    }
+}
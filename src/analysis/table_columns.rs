@@ -0,0 +1,184 @@
+use crate::analysis::literals::string_value;
+use crate::analysis::scope::normalize_identifier;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// How a column's value is produced when it isn't written directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefaultKind {
+    /// `DEFAULT expr`: used when an INSERT omits the column.
+    Default,
+    /// `MATERIALIZED expr`: always computed, not in `SELECT *`.
+    Materialized,
+    /// `ALIAS expr`: computed on read, never stored.
+    Alias,
+    /// `EPHEMERAL [expr]`: accepted by INSERT but not stored.
+    Ephemeral,
+}
+
+/// A column declared in a CREATE TABLE.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSpec {
+    /// Column name, unquoted.
+    pub name: String,
+    /// The type as written, e.g. `Nullable(UInt8)`. `None` for columns
+    /// declared without one, such as `al ALIAS id + 1`.
+    pub type_text: Option<String>,
+    pub default_kind: Option<DefaultKind>,
+    /// Source text of the default, materialized or alias expression.
+    pub default_expr: Option<String>,
+    /// The `COMMENT 'text'` string, unquoted.
+    pub comment: Option<String>,
+}
+
+/// The columns of every CREATE TABLE in the tree, in declaration order.
+/// Indexes, projections and constraints in the column list are skipped.
+pub fn table_columns(tree: &SyntaxTree, source: &str) -> Vec<ColumnSpec> {
+    tree.find_all(SyntaxKind::TableDefinition)
+        .into_iter()
+        .filter_map(|table| {
+            table
+                .children
+                .iter()
+                .find_map(|child| child.get_tree_with_kind(SyntaxKind::ColumnDefinitionList))
+        })
+        .flat_map(|list| list.children.iter())
+        .filter_map(|child| child.get_tree_with_kind(SyntaxKind::ColumnDefinition))
+        .filter_map(|column| column_spec(column, source))
+        .collect()
+}
+
+/// The table-level `COMMENT 'text'` of every CREATE TABLE in the tree that
+/// has one, unquoted and in source order.
+pub fn table_comments(tree: &SyntaxTree, source: &str) -> Vec<String> {
+    tree.find_all(SyntaxKind::TableDefinition)
+        .into_iter()
+        .filter_map(|table| {
+            table
+                .children
+                .iter()
+                .find_map(|child| child.get_tree_with_kind(SyntaxKind::ColumnComment))
+        })
+        .filter_map(|comment| string_value(comment, source))
+        .collect()
+}
+
+fn column_spec(column: &SyntaxTree, source: &str) -> Option<ColumnSpec> {
+    let name = column.children.iter().find_map(|child| match child {
+        SyntaxChild::Token(token)
+            if matches!(token.kind, SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier) =>
+        {
+            Some(normalize_identifier(token, source))
+        }
+        _ => None,
+    })?;
+    let subtree = |kind| {
+        column
+            .children
+            .iter()
+            .find_map(|child| child.get_tree_with_kind(kind))
+    };
+    let type_text = subtree(SyntaxKind::DataType).map(|ty| ty.to_source(source).trim().to_string());
+
+    let (default_kind, default_expr) = match subtree(SyntaxKind::ColumnDefault) {
+        Some(default) => {
+            let keyword = default.children.iter().find_map(|child| match child {
+                SyntaxChild::Token(token) if token.kind == SyntaxKind::BareWord => {
+                    Some(token.text(source))
+                }
+                _ => None,
+            });
+            let kind = match keyword.map(str::to_ascii_uppercase).as_deref() {
+                Some("DEFAULT") => Some(DefaultKind::Default),
+                Some("MATERIALIZED") => Some(DefaultKind::Materialized),
+                Some("ALIAS") => Some(DefaultKind::Alias),
+                Some("EPHEMERAL") => Some(DefaultKind::Ephemeral),
+                _ => None,
+            };
+            let expr = default
+                .children
+                .iter()
+                .find_map(SyntaxChild::as_tree)
+                .map(|expr| expr.to_source(source).trim().to_string());
+            (kind, expr)
+        }
+        None => (None, None),
+    };
+    let comment = subtree(SyntaxKind::ColumnComment).and_then(|c| string_value(c, source));
+
+    Some(ColumnSpec {
+        name,
+        type_text,
+        default_kind,
+        default_expr,
+        comment,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn mixed_column_kinds() {
+        let sql = "CREATE TABLE t (\
+            id UInt64, \
+            `full name` String DEFAULT 'x', \
+            up String MATERIALIZED upper(`full name`), \
+            al ALIAS id + 1, \
+            e Nullable(UInt8) EPHEMERAL, \
+            c UInt8 CODEC(ZSTD) COMMENT 'c', \
+            INDEX idx id TYPE minmax GRANULARITY 1\
+        ) ENGINE = MergeTree ORDER BY id";
+        let result = parse(sql);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let spec = |name: &str, ty: Option<&str>, kind, expr: Option<&str>| ColumnSpec {
+            name: name.to_string(),
+            type_text: ty.map(str::to_string),
+            default_kind: kind,
+            default_expr: expr.map(str::to_string),
+            comment: None,
+        };
+        assert_eq!(
+            table_columns(&result.tree, sql),
+            [
+                spec("id", Some("UInt64"), None, None),
+                spec("full name", Some("String"), Some(DefaultKind::Default), Some("'x'")),
+                spec(
+                    "up",
+                    Some("String"),
+                    Some(DefaultKind::Materialized),
+                    Some("upper(`full name`)"),
+                ),
+                spec("al", None, Some(DefaultKind::Alias), Some("id + 1")),
+                spec("e", Some("Nullable(UInt8)"), Some(DefaultKind::Ephemeral), None),
+                ColumnSpec {
+                    comment: Some("c".to_string()),
+                    ..spec("c", Some("UInt8"), None, None)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn column_and_table_comments() {
+        let sql = "CREATE TABLE t (id UInt64 COMMENT 'row id', name String) \
+            ENGINE = MergeTree ORDER BY id COMMENT 'it''s the users table'";
+        let result = parse(sql);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let comments: Vec<Option<String>> = table_columns(&result.tree, sql)
+            .into_iter()
+            .map(|column| column.comment)
+            .collect();
+        assert_eq!(comments, [Some("row id".to_string()), None]);
+        assert_eq!(table_comments(&result.tree, sql), ["it's the users table"]);
+    }
+
+    #[test]
+    fn table_without_comment() {
+        let sql = "CREATE TABLE t (id UInt64 COMMENT 'row id') ENGINE = Memory";
+        let result = parse(sql);
+        assert!(table_comments(&result.tree, sql).is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::lexer::tokenizer::tokenize_with_whitespace;
+use crate::parser::syntax_kind::SyntaxKind;
+
+/// A token of the raw token stream together with where it starts, for
+/// editors that address text by line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PositionedToken {
+    pub kind: SyntaxKind,
+    pub start: usize,
+    pub end: usize,
+    /// 1-based line of `start`.
+    pub line: usize,
+    /// 1-based column of `start`, in UTF-16 code units as LSP and JavaScript
+    /// editors count them.
+    pub column: usize,
+}
+
+/// Every token of `sql`, whitespace and comments included, in source order.
+/// Lexer errors, including an over-long query, come back as error-kind
+/// tokens rather than failing.
+pub fn tokens_with_positions(sql: &str) -> Vec<PositionedToken> {
+    let (mut line, mut column, mut offset) = (1, 1, 0);
+    tokenize_with_whitespace(sql)
+        .into_iter()
+        .filter(|token| token.kind != SyntaxKind::EndOfStream)
+        .map(|token| {
+            let start = token.start as usize;
+            for ch in sql[offset..start].chars() {
+                if ch == '\n' {
+                    line += 1;
+                    column = 1;
+                } else {
+                    column += ch.len_utf16();
+                }
+            }
+            offset = start;
+            PositionedToken {
+                kind: token.kind,
+                start,
+                end: token.end as usize,
+                line,
+                column,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_and_columns() {
+        let tokens = tokens_with_positions("SELECT a,\n  bé FROM t");
+        let positions: Vec<_> = tokens
+            .iter()
+            .map(|t| (t.kind, t.line, t.column))
+            .collect();
+        assert_eq!(
+            positions,
+            [
+                (SyntaxKind::BareWord, 1, 1),
+                (SyntaxKind::Whitespace, 1, 7),
+                (SyntaxKind::BareWord, 1, 8),
+                (SyntaxKind::Comma, 1, 9),
+                (SyntaxKind::Whitespace, 1, 10),
+                (SyntaxKind::BareWord, 2, 3),
+                (SyntaxKind::Whitespace, 2, 5),
+                (SyntaxKind::BareWord, 2, 6),
+                (SyntaxKind::Whitespace, 2, 10),
+                (SyntaxKind::BareWord, 2, 11),
+            ]
+        );
+        assert_eq!((tokens[5].start, tokens[5].end), (12, 15));
+    }
+
+    #[test]
+    fn columns_count_utf16_code_units() {
+        // The emoji is one char but two UTF-16 code units.
+        let tokens = tokens_with_positions("SELECT '😀' AS x");
+        let columns: Vec<_> = tokens.iter().map(|t| t.column).collect();
+        assert_eq!(columns, [1, 7, 8, 12, 13, 15, 16]);
+    }
+
+    #[test]
+    fn oversized_input_is_one_error_token() {
+        let sql = "SELECT 1 ".repeat(200_000);
+        let tokens = tokens_with_positions(&sql);
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].kind, SyntaxKind::ErrorMaxQuerySizeExceeded);
+        assert_eq!((tokens[0].line, tokens[0].column), (1, 1));
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(tokens_with_positions("").is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+//! One-call summary of a query for embedders such as the web playground:
+//! parse errors, warnings, and the tables, dictionaries and columns it
+//! mentions.
+
+use crate::analysis::dictionaries::dictionary_names;
+use crate::analysis::lints::{run_configured_lints, LintConfig};
+use crate::analysis::sample::{samples, Sample};
+use crate::analysis::scope::{collect_all_table_refs, normalize_identifier};
+use crate::diagnostics::{enrich_diagnostics, Diagnostic, Severity};
+use crate::parser::parse;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Validation {
+    /// True if the query has no errors. Warnings don't affect this.
+    pub ok: bool,
+    pub errors: Vec<Diagnostic>,
+    /// Warnings and hints.
+    pub warnings: Vec<Diagnostic>,
+    /// Referenced tables as `db.table` or `table`, deduplicated, in source
+    /// order. Includes tables inside subqueries.
+    pub tables: Vec<String>,
+    /// Dictionaries read with `dictGet` family calls, deduplicated, in source
+    /// order.
+    pub dictionaries: Vec<String>,
+    /// Referenced columns as written (`a`, `t.a`), unquoted, deduplicated, in
+    /// source order.
+    pub columns: Vec<String>,
+    /// Each `SAMPLE` clause, classified as a ratio, fraction or row count.
+    pub samples: Vec<Sample>,
+}
+
+pub fn validate(sql: &str) -> Validation {
+    validate_with_min_severity(sql, Severity::Hint)
+}
+
+/// Like `validate`, but drops diagnostics less severe than `min_severity`:
+/// `Severity::Error` returns no warnings or hints.
+pub fn validate_with_min_severity(sql: &str, min_severity: Severity) -> Validation {
+    validate_with_lints(sql, min_severity, &LintConfig::default())
+}
+
+/// Like `validate_with_min_severity`, but also runs the lints enabled in
+/// `lints`. A lint whose severity is overridden to `Error` makes the query
+/// not `ok`.
+pub fn validate_with_lints(sql: &str, min_severity: Severity, lints: &LintConfig) -> Validation {
+    let result = parse(sql);
+    let mut diagnostics = enrich_diagnostics(&result, sql);
+    diagnostics.extend(run_configured_lints(&result.tree, sql, lints));
+    let (errors, warnings): (Vec<_>, Vec<_>) = diagnostics
+        .into_iter()
+        .filter(|d| d.severity >= min_severity)
+        .partition(|d| d.severity == Severity::Error);
+
+    let mut tables = Vec::new();
+    for table_ref in collect_all_table_refs(&result.tree, sql) {
+        let name = match table_ref.database {
+            Some(db) => format!("{db}.{}", table_ref.table),
+            None => table_ref.table,
+        };
+        push_unique(&mut tables, name);
+    }
+
+    let mut columns = Vec::new();
+    for column in result.tree.find_all(SyntaxKind::ColumnReference) {
+        if let Some(name) = column_path(column, sql) {
+            push_unique(&mut columns, name);
+        }
+    }
+
+    Validation {
+        ok: errors.is_empty(),
+        errors,
+        warnings,
+        tables,
+        dictionaries: dictionary_names(&result.tree, sql),
+        columns,
+        samples: samples(&result.tree, sql),
+    }
+}
+
+pub(crate) fn push_unique(list: &mut Vec<String>, item: String) {
+    if !list.contains(&item) {
+        list.push(item);
+    }
+}
+
+/// `t.a` for a column reference, with each part unquoted. `None` for any
+/// other node.
+pub(crate) fn column_path(column: &SyntaxTree, source: &str) -> Option<String> {
+    if column.kind != SyntaxKind::ColumnReference {
+        return None;
+    }
+    let parts: Vec<String> = column
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_token)
+        .filter(|token| matches!(token.kind, SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier))
+        .map(|token| normalize_identifier(token, source))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::sample::SampleSize;
+
+    #[test]
+    fn valid_query() {
+        let v = validate("SELECT a, `t`.b, a + 1 AS c FROM db.t WHERE id IN (SELECT id FROM u)");
+        assert!(v.ok);
+        assert!(v.errors.is_empty());
+        assert_eq!(v.tables, ["db.t", "u"]);
+        assert_eq!(v.columns, ["a", "t.b", "id"]);
+        assert!(v.dictionaries.is_empty());
+        assert!(v.samples.is_empty());
+    }
+
+    #[test]
+    fn dictionaries_are_collected() {
+        let v = validate("SELECT dictGet('my_dict', 'attr', id) FROM t");
+        assert_eq!(v.tables, ["t"]);
+        assert_eq!(v.dictionaries, ["my_dict"]);
+    }
+
+    #[test]
+    fn min_severity_drops_less_severe_diagnostics() {
+        let sql = "SELECT a AS from FROM t WHERE";
+        let all = validate_with_min_severity(sql, Severity::Hint);
+        assert_eq!(all.warnings.len(), 1);
+
+        let errors_only = validate_with_min_severity(sql, Severity::Error);
+        assert!(errors_only.warnings.is_empty());
+        assert_eq!(errors_only.errors, all.errors);
+        assert!(!errors_only.ok);
+    }
+
+    #[test]
+    fn configured_lints_are_reported() {
+        let sql = "SELECT a FROM t LIMIT 1";
+        assert!(validate(sql).warnings.is_empty());
+
+        let mut lints = LintConfig::default();
+        lints.enable("limit-without-order-by");
+        let v = validate_with_lints(sql, Severity::Hint, &lints);
+        assert!(v.ok);
+        assert_eq!(v.warnings[0].code, Some("limit-without-order-by"));
+
+        lints.set_severity("limit-without-order-by", Severity::Error);
+        let v = validate_with_lints(sql, Severity::Hint, &lints);
+        assert!(!v.ok);
+        assert_eq!(v.errors[0].code, Some("limit-without-order-by"));
+    }
+
+    #[test]
+    fn severity_order_and_names() {
+        assert!(Severity::Error > Severity::Warning && Severity::Warning > Severity::Hint);
+        assert_eq!(Severity::from_name("WARNING"), Some(Severity::Warning));
+        assert_eq!(Severity::from_name("info"), None);
+    }
+
+    #[test]
+    fn sample_clause() {
+        let v = validate("SELECT a FROM t SAMPLE 0");
+        assert!(!v.ok);
+        assert_eq!(v.errors[0].code, Some("invalid-sample"));
+        assert_eq!(v.samples.len(), 1);
+        assert_eq!(v.samples[0].size, Some(SampleSize::Ratio(0.0)));
+    }
+
+    #[test]
+    fn invalid_query() {
+        let v = validate("SELECT a AS from FROM t WHERE");
+        assert!(!v.ok);
+        assert!(!v.errors.is_empty());
+        assert!(v.errors.iter().all(|d| d.severity == Severity::Error));
+        assert_eq!(v.warnings.len(), 1);
+        assert_eq!(v.warnings[0].code, Some("reserved-keyword-alias"));
+        assert_eq!(v.tables, ["t"]);
+    }
+}
@@ -0,0 +1,28 @@
+use super::types::{Diagnostic, Severity};
+use crate::analysis::sample::samples;
+use crate::parser::syntax_tree::SyntaxTree;
+
+/// Flag `SAMPLE` sizes that select nothing or can't be a share of the data:
+/// `SAMPLE 0`, `SAMPLE 1.5`, `SAMPLE 3/2`, `SAMPLE 1/0`.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    for sample in samples(tree, source) {
+        let Some(size) = sample.size else {
+            continue;
+        };
+        if size.is_valid() {
+            continue;
+        }
+        let (start, end) = (sample.size_span.0 as usize, sample.size_span.1 as usize);
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "Invalid SAMPLE size `{}`: expected a ratio greater than 0 and at most 1, or a row count",
+                &source[start..end]
+            ),
+            range: (start, end),
+            severity: Severity::Error,
+            code: Some("invalid-sample"),
+            suggestion: None,
+            related: Vec::new(),
+        });
+    }
+}
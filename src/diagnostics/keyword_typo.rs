@@ -0,0 +1,160 @@
+use super::types::{Diagnostic, Severity, Suggestion};
+use crate::lexer::token::Token;
+use crate::parser::keyword::Keyword;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Keywords worth suggesting when an unexpected word is close to one:
+/// statement starters and clause keywords, where a typo derails the whole
+/// statement.
+const TYPO_CANDIDATES: &[Keyword] = &[
+    Keyword::Select,
+    Keyword::Insert,
+    Keyword::Create,
+    Keyword::Alter,
+    Keyword::Drop,
+    Keyword::Delete,
+    Keyword::Update,
+    Keyword::Truncate,
+    Keyword::Optimize,
+    Keyword::Describe,
+    Keyword::Explain,
+    Keyword::Show,
+    Keyword::System,
+    Keyword::From,
+    Keyword::Where,
+    Keyword::Prewhere,
+    Keyword::Group,
+    Keyword::Order,
+    Keyword::Having,
+    Keyword::Qualify,
+    Keyword::Limit,
+    Keyword::Offset,
+    Keyword::Window,
+    Keyword::Settings,
+    Keyword::Format,
+    Keyword::Union,
+    Keyword::Except,
+    Keyword::Intersect,
+    Keyword::Join,
+    Keyword::Values,
+    Keyword::Table,
+    Keyword::Distinct,
+];
+
+/// Attach a "did you mean" suggestion to syntax errors that sit on a word
+/// one or two edits away from a keyword, e.g. `SELET 1`.
+///
+/// For `expected KEYWORD` errors only the expected keyword is considered;
+/// otherwise the word is compared against [`TYPO_CANDIDATES`].
+pub fn enrich(diagnostics: &mut [Diagnostic], tree: &SyntaxTree, source: &str) {
+    for diag in diagnostics.iter_mut() {
+        if diag.severity != Severity::Error || diag.suggestion.is_some() {
+            continue;
+        }
+        let Some(token) = bare_word_at(tree, diag.range.0) else {
+            continue;
+        };
+        let word = token.text(source);
+
+        let keyword = match diag.message.strip_prefix("expected ") {
+            Some(expected) if is_keyword_text(expected) => {
+                Some(expected).filter(|kw| (1..=max_edits(word)).contains(&edit_distance(word, kw)))
+            }
+            Some(_) => None,
+            None => closest_candidate(word),
+        };
+
+        if let Some(keyword) = keyword {
+            diag.suggestion = Some(Suggestion {
+                message: format!("Did you mean `{keyword}`?"),
+                replacement: Some(keyword.to_string()),
+            });
+        }
+    }
+}
+
+/// The `BareWord` token starting at `offset`, if any.
+fn bare_word_at(tree: &SyntaxTree, offset: usize) -> Option<&Token> {
+    if (tree.start as usize) > offset || (tree.end as usize) <= offset {
+        return None;
+    }
+    tree.children.iter().find_map(|child| match child {
+        SyntaxChild::Token(token)
+            if token.kind == SyntaxKind::BareWord && token.start as usize == offset =>
+        {
+            Some(token)
+        }
+        SyntaxChild::Tree(subtree) => bare_word_at(subtree, offset),
+        SyntaxChild::Token(_) => None,
+    })
+}
+
+/// `expected BY` names a keyword; `expected Comma` names a token kind.
+fn is_keyword_text(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|b| b.is_ascii_uppercase() || b == b'_')
+}
+
+/// Short words get one edit, longer ones two.
+fn max_edits(word: &str) -> usize {
+    if word.len() <= 4 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Words under three letters are never matched against the whole list, so a
+/// stray `a` or `t` doesn't turn into an `AS`-like suggestion.
+fn closest_candidate(word: &str) -> Option<&'static str> {
+    if word.len() < 3 {
+        return None;
+    }
+    let max = max_edits(word);
+    TYPO_CANDIDATES
+        .iter()
+        .map(|kw| kw.as_str())
+        .filter(|kw| !kw.eq_ignore_ascii_case(word))
+        .map(|kw| (edit_distance(word, kw), kw))
+        .filter(|(distance, _)| *distance <= max)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, kw)| kw)
+}
+
+/// Case-insensitive Levenshtein distance over ASCII bytes.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(!ca.eq_ignore_ascii_case(cb));
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distances() {
+        assert_eq!(edit_distance("SELET", "SELECT"), 1);
+        assert_eq!(edit_distance("selcet", "SELECT"), 2);
+        assert_eq!(edit_distance("FROM", "from"), 0);
+        assert_eq!(edit_distance("", "BY"), 2);
+    }
+
+    #[test]
+    fn short_words_need_a_close_match() {
+        assert_eq!(closest_candidate("SELET"), Some("SELECT"));
+        assert_eq!(closest_candidate("WHER"), Some("WHERE"));
+        assert_eq!(closest_candidate("t"), None);
+        assert_eq!(closest_candidate("SHWO"), None);
+    }
+}
@@ -0,0 +1,70 @@
+use super::types::{Diagnostic, Severity, Suggestion};
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Warn on `SELECT a\n    b`: an alias without `AS` on the line after its
+/// expression is more often a forgotten comma than an alias. The query still
+/// parses `b` as an alias, since that is what ClickHouse does.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    for clause in tree.find_all(SyntaxKind::SelectClause) {
+        for list in clause
+            .children
+            .iter()
+            .filter_map(|c| c.get_tree_with_kind(SyntaxKind::ColumnList))
+        {
+            check_list(diagnostics, list, source);
+        }
+    }
+}
+
+fn check_list(diagnostics: &mut Vec<Diagnostic>, list: &SyntaxTree, source: &str) {
+    let items: Vec<&SyntaxTree> = list
+        .children
+        .iter()
+        .filter_map(SyntaxChild::as_tree)
+        .collect();
+    for pair in items.windows(2) {
+        let (expr, alias) = (pair[0], pair[1]);
+        if alias.kind != SyntaxKind::ColumnAlias {
+            continue;
+        }
+        let tokens = alias.tokens_in_order();
+        let mut words = tokens.iter().filter(|t| !is_trivia(t.kind));
+        // Only the bare form: `AS` makes the alias explicit.
+        let (Some(name), None) = (words.next(), words.next()) else {
+            continue;
+        };
+        let Some(expr_end) = expr
+            .tokens_in_order()
+            .into_iter()
+            .rev()
+            .find(|t| !is_trivia(t.kind))
+            .map(|t| t.end)
+        else {
+            continue;
+        };
+        if !source[expr_end as usize..name.start as usize].contains('\n') {
+            continue;
+        }
+        let text = name.text(source);
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "`{}` on a new line is parsed as an alias; missing comma?",
+                text
+            ),
+            range: (name.start as usize, name.end as usize),
+            severity: Severity::Warning,
+            code: Some("missing-comma"),
+            suggestion: Some(Suggestion {
+                message: "Add a comma if this is a separate column, or AS if it is an alias"
+                    .to_string(),
+                replacement: None,
+            }),
+            related: Vec::new(),
+        });
+    }
+}
+
+fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(kind, SyntaxKind::Whitespace | SyntaxKind::Comment)
+}
@@ -1,12 +1,18 @@
 mod types;
 mod bracket_matching;
 mod context;
+mod invalid_sample;
+mod keyword_typo;
+mod missing_comma;
+mod negative_limit;
+mod reserved_alias;
+mod with_ties_without_order_by;
 
 pub use types::{Diagnostic, Severity, Suggestion, RelatedSpan};
 
 use crate::parser::diagnostic::Parse;
 
-pub fn enrich_diagnostics(parse: &Parse, _source: &str) -> Vec<Diagnostic> {
+pub fn enrich_diagnostics(parse: &Parse, source: &str) -> Vec<Diagnostic> {
     // Convert raw SyntaxErrors to base Diagnostics
     let mut diagnostics: Vec<Diagnostic> = parse.errors.iter().map(|e| {
         Diagnostic {
@@ -22,6 +28,16 @@ pub fn enrich_diagnostics(parse: &Parse, _source: &str) -> Vec<Diagnostic> {
     // Run enrichment passes
     bracket_matching::enrich(&mut diagnostics, &parse.tree);
     context::enrich(&mut diagnostics, &parse.tree);
+    keyword_typo::enrich(&mut diagnostics, &parse.tree, source);
+
+    // Warnings on syntactically valid but risky constructs
+    reserved_alias::check(&mut diagnostics, &parse.tree, source);
+    missing_comma::check(&mut diagnostics, &parse.tree, source);
+
+    // Valid syntax that ClickHouse rejects
+    negative_limit::check(&mut diagnostics, &parse.tree, source);
+    invalid_sample::check(&mut diagnostics, &parse.tree, source);
+    with_ties_without_order_by::check(&mut diagnostics, &parse.tree, source);
 
     diagnostics
 }
@@ -59,6 +75,96 @@ mod tests {
         check_diagnostics("SELECT 1 FROM t", expect![[""]]);
     }
 
+    #[test]
+    fn reserved_keyword_alias_warns() {
+        check_diagnostics("SELECT x AS select", expect![[r#"
+            12..18: [warning] Reserved keyword `select` used as an unquoted alias (suggestion: Quote the alias)
+        "#]]);
+        check_diagnostics("SELECT x AS from, y AS limit FROM t", expect![[r#"
+            12..16: [warning] Reserved keyword `from` used as an unquoted alias (suggestion: Quote the alias)
+            23..28: [warning] Reserved keyword `limit` used as an unquoted alias (suggestion: Quote the alias)
+        "#]]);
+        check_diagnostics("SELECT x AS \"select\", y AS `from`", expect![[""]]);
+        check_diagnostics("SELECT x AS selected, y ORDER_ FROM t", expect![[""]]);
+        // The same reserved words as for bare column names.
+        check_diagnostics("SELECT x AS case, y AS offset FROM t", expect![[r#"
+            12..16: [warning] Reserved keyword `case` used as an unquoted alias (suggestion: Quote the alias)
+        "#]]);
+    }
+
+    #[test]
+    fn alias_on_next_line_suggests_missing_comma() {
+        check_diagnostics("SELECT a\n    b FROM t", expect![[r#"
+            13..14: [warning] `b` on a new line is parsed as an alias; missing comma? (suggestion: Add a comma if this is a separate column, or AS if it is an alias)
+        "#]]);
+        check_diagnostics("SELECT a b, c\n  AS d, e\n  -- note\n  f FROM t", expect![[r#"
+            36..37: [warning] `f` on a new line is parsed as an alias; missing comma? (suggestion: Add a comma if this is a separate column, or AS if it is an alias)
+        "#]]);
+    }
+
+    #[test]
+    fn negative_limit_is_an_error() {
+        check_diagnostics("SELECT a FROM t LIMIT -1", expect![[r#"
+            22..24: [error] LIMIT and OFFSET must not be negative, found `-1`
+        "#]]);
+        check_diagnostics("SELECT a FROM t LIMIT 10 OFFSET - 5", expect![[r#"
+            32..35: [error] LIMIT and OFFSET must not be negative, found `- 5`
+        "#]]);
+        check_diagnostics("SELECT a FROM t LIMIT -1 BY a LIMIT 1, -2", expect![[r#"
+            22..24: [error] LIMIT and OFFSET must not be negative, found `-1`
+            39..41: [error] LIMIT and OFFSET must not be negative, found `-2`
+        "#]]);
+    }
+
+    #[test]
+    fn invalid_sample_size_is_an_error() {
+        check_diagnostics("SELECT a FROM t SAMPLE 0", expect![[r#"
+            23..24: [error] Invalid SAMPLE size `0`: expected a ratio greater than 0 and at most 1, or a row count
+        "#]]);
+        check_diagnostics("SELECT a FROM t SAMPLE 3/2 OFFSET 0.5", expect![[r#"
+            23..26: [error] Invalid SAMPLE size `3/2`: expected a ratio greater than 0 and at most 1, or a row count
+        "#]]);
+        check_diagnostics("SELECT a FROM t SAMPLE 0.1", expect![[""]]);
+        check_diagnostics("SELECT a FROM t SAMPLE 1/10", expect![[""]]);
+        check_diagnostics("SELECT a FROM t SAMPLE 10000", expect![[""]]);
+    }
+
+    #[test]
+    fn with_ties_without_order_by_is_an_error() {
+        check_diagnostics("SELECT a FROM t LIMIT 10 WITH TIES", expect![[r#"
+            25..34: [error] WITH TIES requires an ORDER BY clause (suggestion: Add an ORDER BY clause or remove WITH TIES)
+        "#]]);
+        check_diagnostics("SELECT a FROM t LIMIT 1 BY a LIMIT 3 WITH TIES", expect![[r#"
+            37..46: [error] WITH TIES requires an ORDER BY clause (suggestion: Add an ORDER BY clause or remove WITH TIES)
+        "#]]);
+        check_diagnostics("SELECT a FROM t ORDER BY a LIMIT 10 WITH TIES", expect![[""]]);
+        check_diagnostics("SELECT a FROM t ORDER BY a LIMIT 5, 10 WITH TIES", expect![[""]]);
+    }
+
+    #[test]
+    fn non_negative_limit_is_fine() {
+        check_diagnostics("SELECT a FROM t LIMIT 10", expect![[""]]);
+        check_diagnostics("SELECT a FROM t LIMIT 10 OFFSET 5", expect![[""]]);
+        // Only literal constants are checked.
+        check_diagnostics("SELECT a FROM t LIMIT 1 - 2", expect![[""]]);
+        check_diagnostics("SELECT a FROM t LIMIT -n", expect![[""]]);
+    }
+
+    #[test]
+    fn misspelled_keyword_suggests_keyword() {
+        check_diagnostics("SELET 1", expect![[r#"
+            0..7: [error] Unknown statement (suggestion: Did you mean `SELECT`?)
+        "#]]);
+        check_diagnostics("SELECT a FROM t GROUP BT a", expect![[r#"
+            22..24: [error] expected BY (suggestion: Did you mean `BY`?)
+            25..26: [error] expected Comma
+        "#]]);
+        check_diagnostics("SELECT a FROM t GROUP xyz a", expect![[r#"
+            22..25: [error] expected BY
+            26..27: [error] expected Comma
+        "#]]);
+    }
+
     #[test]
     fn bracket_matching_related_span() {
         let result = parse("SELECT (1 + 2");
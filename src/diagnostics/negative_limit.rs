@@ -0,0 +1,62 @@
+use super::types::{Diagnostic, Severity};
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Flag LIMIT and OFFSET values written as negative number literals, which
+/// ClickHouse rejects. Only literal constants are checked: `LIMIT -1` is
+/// flagged, `LIMIT 1 - 2` is not.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    let mut clauses = tree.find_all(SyntaxKind::LimitClause);
+    clauses.extend(tree.find_all(SyntaxKind::LimitByClause));
+    clauses.sort_by_key(|clause| clause.start);
+    for clause in clauses {
+        // In `LIMIT n BY ...` only the count precedes `BY`.
+        let values = clause.children.iter().take_while(|child| {
+            !matches!(child, SyntaxChild::Token(token)
+                if token.text(source).eq_ignore_ascii_case("BY"))
+        });
+        check_values(diagnostics, values, source);
+    }
+}
+
+fn check_values<'a>(
+    diagnostics: &mut Vec<Diagnostic>,
+    children: impl Iterator<Item = &'a SyntaxChild>,
+    source: &str,
+) {
+    for value in children.filter_map(SyntaxChild::as_tree) {
+        let Some((start, end)) = negative_literal(value) else {
+            continue;
+        };
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "LIMIT and OFFSET must not be negative, found `{}`",
+                &source[start..end]
+            ),
+            range: (start, end),
+            severity: Severity::Error,
+            code: Some("negative-limit"),
+            suggestion: None,
+            related: Vec::new(),
+        });
+    }
+}
+
+/// The span of `-<number>` if the expression is a minus applied directly
+/// to a number literal.
+fn negative_literal(expr: &SyntaxTree) -> Option<(usize, usize)> {
+    if expr.kind != SyntaxKind::UnaryExpression {
+        return None;
+    }
+    let mut children = expr.children.iter().filter(|child| {
+        !matches!(child, SyntaxChild::Token(token)
+            if matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Comment))
+    });
+    let minus = children.next()?.get_token_with_kind(SyntaxKind::Minus)?;
+    let number = children.next()?.get_tree_with_kind(SyntaxKind::NumberLiteral)?;
+    let digits = number.children.first()?.as_token()?;
+    if children.next().is_some() {
+        return None;
+    }
+    Some((minus.start as usize, digits.end as usize))
+}
@@ -0,0 +1,43 @@
+use super::types::{Diagnostic, Severity, Suggestion};
+use crate::parser::keyword::Keyword;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Warn on column aliases that are unquoted reserved keywords.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    for alias in tree.find_all(SyntaxKind::ColumnAlias) {
+        // The alias name follows an optional `AS`. A lone `AS` means the
+        // name is missing, which the parser already reports.
+        let words: Vec<_> = alias
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                SyntaxChild::Token(token) if token.kind == SyntaxKind::BareWord => Some(token),
+                _ => None,
+            })
+            .collect();
+        let name = match words.as_slice() {
+            [only] if !only.text(source).eq_ignore_ascii_case("AS") => *only,
+            [_, name] => *name,
+            _ => continue,
+        };
+        // `SELECT x AS select` parses, but the alias is easy to misread and
+        // breaks as soon as the query is edited.
+        let text = name.text(source);
+        if !Keyword::from_text(text).is_some_and(Keyword::is_reserved) {
+            continue;
+        }
+
+        diagnostics.push(Diagnostic {
+            message: format!("Reserved keyword `{}` used as an unquoted alias", text),
+            range: (name.start as usize, name.end as usize),
+            severity: Severity::Warning,
+            code: Some("reserved-keyword-alias"),
+            suggestion: Some(Suggestion {
+                message: "Quote the alias".to_string(),
+                replacement: Some(format!("`{}`", text)),
+            }),
+            related: Vec::new(),
+        });
+    }
+}
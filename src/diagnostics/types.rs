@@ -1,9 +1,20 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Ordered from least to most severe, so `severity >= Severity::Warning`
+/// keeps warnings and errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Severity {
-    Error,
-    Warning,
     Hint,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Parse `error`, `warning` or `hint`, ignoring case.
+    pub fn from_name(name: &str) -> Option<Severity> {
+        [Severity::Error, Severity::Warning, Severity::Hint]
+            .into_iter()
+            .find(|severity| format!("{severity:?}").eq_ignore_ascii_case(name))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,3 +41,71 @@ pub struct Diagnostic {
     pub suggestion: Option<Suggestion>,
     pub related: Vec<RelatedSpan>,
 }
+
+#[cfg(feature = "lsp")]
+impl Diagnostic {
+    /// Convert to an LSP diagnostic for the document at `uri`, mapping byte
+    /// ranges (including related spans) to line/UTF-16 positions.
+    pub fn to_lsp(
+        &self,
+        line_index: &crate::lsp::line_index::LineIndex,
+        uri: &tower_lsp::lsp_types::Url,
+    ) -> tower_lsp::lsp_types::Diagnostic {
+        use tower_lsp::lsp_types::{
+            DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString,
+        };
+
+        let severity = match self.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+            Severity::Hint => DiagnosticSeverity::HINT,
+        };
+        let related_information = (!self.related.is_empty()).then(|| {
+            self.related
+                .iter()
+                .map(|r| DiagnosticRelatedInformation {
+                    location: Location {
+                        uri: uri.clone(),
+                        range: line_index.range(r.range.0 as u32, r.range.1 as u32),
+                    },
+                    message: r.message.clone(),
+                })
+                .collect()
+        });
+        tower_lsp::lsp_types::Diagnostic {
+            range: line_index.range(self.range.0 as u32, self.range.1 as u32),
+            severity: Some(severity),
+            code: self.code.map(|c| NumberOrString::String(c.to_owned())),
+            source: Some("clickhouse-analyzer".to_owned()),
+            message: self.message.clone(),
+            related_information,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(all(test, feature = "lsp"))]
+mod tests {
+    use crate::diagnostics::enrich_diagnostics;
+    use crate::lsp::line_index::LineIndex;
+    use crate::parser::parse;
+    use tower_lsp::lsp_types::{DiagnosticSeverity, NumberOrString, Position, Range, Url};
+
+    #[test]
+    fn converts_parser_diagnostic_to_lsp() {
+        let sql = "SELECT 1;\nSELECT a FROM t LIMIT -1";
+        let diagnostics = enrich_diagnostics(&parse(sql), sql);
+        let uri = Url::parse("file:///query.sql").unwrap();
+        let lsp = diagnostics[0].to_lsp(&LineIndex::new(sql), &uri);
+        assert_eq!(
+            lsp.range,
+            Range {
+                start: Position { line: 1, character: 22 },
+                end: Position { line: 1, character: 24 },
+            }
+        );
+        assert_eq!(lsp.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(lsp.code, Some(NumberOrString::String("negative-limit".to_owned())));
+        assert_eq!(lsp.message, diagnostics[0].message);
+    }
+}
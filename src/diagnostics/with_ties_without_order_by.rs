@@ -0,0 +1,40 @@
+use super::types::{Diagnostic, Severity, Suggestion};
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Flag `LIMIT n WITH TIES` in a SELECT without ORDER BY. Ties are defined by
+/// the sort key, so ClickHouse rejects the modifier when there is none.
+pub fn check(diagnostics: &mut Vec<Diagnostic>, tree: &SyntaxTree, source: &str) {
+    for select in tree.find_all(SyntaxKind::SelectStatement) {
+        let clauses = || select.children.iter().filter_map(SyntaxChild::as_tree);
+        if clauses().any(|clause| clause.kind == SyntaxKind::OrderByClause) {
+            continue;
+        }
+        for limit in clauses().filter(|clause| clause.kind == SyntaxKind::LimitClause) {
+            let Some((start, end)) = with_ties_span(limit, source) else {
+                continue;
+            };
+            diagnostics.push(Diagnostic {
+                message: "WITH TIES requires an ORDER BY clause".to_string(),
+                range: (start, end),
+                severity: Severity::Error,
+                code: Some("with-ties-without-order-by"),
+                suggestion: Some(Suggestion {
+                    message: "Add an ORDER BY clause or remove WITH TIES".to_string(),
+                    replacement: None,
+                }),
+                related: Vec::new(),
+            });
+        }
+    }
+}
+
+/// The span from `WITH` to `TIES` if the LIMIT clause carries the modifier.
+fn with_ties_span(limit: &SyntaxTree, source: &str) -> Option<(usize, usize)> {
+    let mut words = limit.children.iter().filter_map(SyntaxChild::as_token).filter(|token| {
+        !matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Comment)
+    });
+    let with = words.find(|token| token.text(source).eq_ignore_ascii_case("WITH"))?;
+    let ties = words.next().filter(|token| token.text(source).eq_ignore_ascii_case("TIES"))?;
+    Some((with.start as usize, ties.end as usize))
+}
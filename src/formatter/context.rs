@@ -1,4 +1,5 @@
 use super::FormatConfig;
+use crate::parser::syntax_kind::SyntaxKind;
 
 pub struct FormatterContext<'a> {
     config: &'a FormatConfig,
@@ -12,6 +13,9 @@ pub struct FormatterContext<'a> {
     pending_newline: bool,
     /// Set when skipped whitespace contained a blank line (2+ newlines).
     pending_blank_line: bool,
+    /// Kind of the node being formatted, so tokens can tell names from
+    /// keywords.
+    node_kind: SyntaxKind,
 }
 
 impl<'a> FormatterContext<'a> {
@@ -25,9 +29,24 @@ impl<'a> FormatterContext<'a> {
             needs_space: false,
             pending_newline: false,
             pending_blank_line: false,
+            node_kind: SyntaxKind::File,
         }
     }
 
+    /// Start formatting a node of `kind` and return the previous kind, to
+    /// hand back to `leave_node` when done.
+    pub fn enter_node(&mut self, kind: SyntaxKind) -> SyntaxKind {
+        std::mem::replace(&mut self.node_kind, kind)
+    }
+
+    pub fn leave_node(&mut self, parent: SyntaxKind) {
+        self.node_kind = parent;
+    }
+
+    pub fn node_kind(&self) -> SyntaxKind {
+        self.node_kind
+    }
+
     pub fn write_newline(&mut self) {
         self.buf.push('\n');
         self.at_line_start = true;
@@ -113,6 +132,7 @@ impl<'a> FormatterContext<'a> {
             needs_space: false,
             pending_newline: false,
             pending_blank_line: false,
+            node_kind: self.node_kind,
         }
     }
 
@@ -1,5 +1,6 @@
 use super::context::FormatterContext;
 use crate::lexer::token::Token;
+use crate::parser::keyword::Keyword;
 use crate::parser::syntax_kind::SyntaxKind;
 use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
 
@@ -7,50 +8,41 @@ use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
 // Keyword detection
 // ---------------------------------------------------------------------------
 
-/// All keyword uppercase forms for matching BareWord tokens.
-const KEYWORDS: &[&str] = &[
-    "SELECT", "FROM", "WHERE", "ORDER", "BY", "GROUP", "HAVING", "LIMIT",
-    "OFFSET", "WITH", "AS", "ON", "USING", "BETWEEN", "IN", "LIKE", "ILIKE",
-    "IS", "NOT", "CASE", "WHEN", "THEN", "ELSE", "END", "CAST", "DISTINCT",
-    "ALL", "EXISTS", "AND", "OR", "JOIN", "INNER", "LEFT", "RIGHT", "FULL",
-    "OUTER", "CROSS", "GLOBAL", "ANY", "SEMI", "ANTI", "ASOF", "NATURAL",
-    "ARRAY", "FINAL", "ASC", "DESC", "NULLS", "FIRST", "LAST", "TOTALS",
-    "ROLLUP", "CUBE", "UNION", "EXCEPT", "INTERSECT", "INSERT", "INTO",
-    "VALUES", "DELETE", "UPDATE", "SET", "CREATE", "ALTER", "DROP", "DETACH",
-    "ATTACH", "RENAME", "TRUNCATE", "SHOW", "USE", "OPTIMIZE", "SYSTEM",
-    "EXCHANGE", "UNDROP", "TABLE", "VIEW", "DATABASE", "DICTIONARY",
-    "FUNCTION", "MATERIALIZED", "TEMPORARY", "IF", "REPLACE", "LIVE",
-    "DEFAULT", "CODEC", "TTL", "COMMENT", "PRIMARY", "KEY", "ALIAS",
-    "EPHEMERAL", "PREWHERE", "SETTINGS", "FORMAT", "SAMPLE", "NULL", "TRUE",
-    "FALSE", "INTERVAL", "ENGINE", "PARTITION", "CLUSTER", "TO", "POPULATE",
-    "EMPTY", "PERMANENTLY", "AFTER", "COLUMN", "INDEX", "PROJECTION",
-    "CONSTRAINT", "ADD", "MODIFY", "CLEAR", "MOVE", "GRANULARITY", "TYPE",
-    "DEDUPLICATE", "EXPLAIN", "DESCRIBE", "AST", "PLAN", "PIPELINE",
-    "ESTIMATE", "TABLES", "DATABASES", "COLUMNS", "DICTIONARIES",
-    "FUNCTIONS", "PROCESSLIST", "PRIVILEGES", "GRANTS", "RELOAD", "FLUSH",
-    "STOP", "START", "MERGES", "REPLICA", "REPLICAS", "DISTRIBUTED",
-    "SENDING", "FETCHES", "MOVES", "LOGS", "CACHE", "DNS", "MARK",
-    "UNCOMPRESSED", "COMPILED", "MODELS", "DISKS", "GRANT", "REVOKE",
-    "USER", "ROLE", "QUOTA", "POLICY", "PROFILE", "ROW", "NONE", "KILL",
-    "QUERY", "MUTATION", "SYNC", "ASYNC", "TEST", "CHECK", "BEGIN", "COMMIT",
-    "ROLLBACK", "TRANSACTION", "BACKUP", "RESTORE", "LOCAL", "FREEZE",
-    "UNFREEZE", "FETCH", "APPLY", "DELETED", "SOURCE", "LAYOUT", "LIFETIME",
-    "RANGE", "HASHED", "FLAT", "COMPLEX", "DIRECT", "INJECTIVE",
-    "HIERARCHICAL", "WINDOW", "OVER", "ROWS", "GROUPS", "UNBOUNDED",
-    "PRECEDING", "FOLLOWING", "CURRENT", "DIV", "MOD",
-    "SYNTAX", "TREE", "OVERRIDE", "ENGINES", "FOR", "PART",
-    "MATERIALIZE", "SETTING", "RESET",
-    "FILL", "STEP", "INTERPOLATE", "OPTION",
-    "IDENTIFIED", "HOST", "KEYED",
+/// Keywords that are more often function or column names (`max(x)`, `t.id`,
+/// `isNull(x)`), so the formatter leaves them as written.
+const NAME_LIKE_KEYWORDS: &[Keyword] = &[
+    Keyword::Id,
+    Keyword::Max,
+    Keyword::Min,
+    Keyword::Level,
+    Keyword::Config,
+    Keyword::Filter,
+    Keyword::Grouping,
+    Keyword::Isnull,
+    Keyword::Ignore,
 ];
 
-fn is_keyword(text: &str) -> bool {
-    let upper = text.to_uppercase();
-    KEYWORDS.contains(&upper.as_str())
+pub(super) fn is_keyword(text: &str) -> bool {
+    Keyword::from_text(text).is_some_and(|keyword| !NAME_LIKE_KEYWORDS.contains(&keyword))
+}
+
+/// True if `text`, a token directly under a `parent` node, is a column,
+/// table or alias name rather than a keyword, e.g. `skip` in `SELECT skip
+/// FROM t`. Identifiers are case-sensitive, so names are written as they are.
+/// Function names (`Identifier`) are not included: `COLUMNS(...)` and `IF(...)`
+/// are still uppercased.
+pub(super) fn is_name_token(parent: SyntaxKind, text: &str) -> bool {
+    match parent {
+        SyntaxKind::ColumnReference
+        | SyntaxKind::QualifiedName
+        | SyntaxKind::TableIdentifier => true,
+        SyntaxKind::ColumnAlias | SyntaxKind::TableAlias => !text.eq_ignore_ascii_case("AS"),
+        _ => false,
+    }
 }
 
 /// Keywords that should NOT be uppercased (they act as literal values).
-fn is_value_keyword(text: &str) -> bool {
+pub(super) fn is_value_keyword(text: &str) -> bool {
     let upper = text.to_uppercase();
     matches!(upper.as_str(), "TRUE" | "FALSE" | "NULL")
 }
@@ -98,6 +90,12 @@ fn has_error_child(tree: &SyntaxTree) -> bool {
 }
 
 pub fn format_node(tree: &SyntaxTree, ctx: &mut FormatterContext) {
+    let parent = ctx.enter_node(tree.kind);
+    dispatch(tree, ctx);
+    ctx.leave_node(parent);
+}
+
+fn dispatch(tree: &SyntaxTree, ctx: &mut FormatterContext) {
     // If a non-root node contains Error children, emit it verbatim.
     // Reformatting error-recovery structures changes token boundaries on
     // re-parse, breaking idempotency.
@@ -128,7 +126,7 @@ pub fn format_node(tree: &SyntaxTree, ctx: &mut FormatterContext) {
         SyntaxKind::ArrayJoinClause => format_simple_clause(tree, ctx),
         SyntaxKind::WindowClause => format_simple_clause(tree, ctx),
         SyntaxKind::WindowDefinition => format_inline(tree, ctx),
-        SyntaxKind::WindowFrame => format_inline(tree, ctx),
+        SyntaxKind::WindowFrame | SyntaxKind::WindowFrameBound => format_inline(tree, ctx),
         SyntaxKind::WindowSpec => format_inline(tree, ctx),
         SyntaxKind::WindowExpression => format_inline(tree, ctx),
         SyntaxKind::SampleClause => format_inline(tree, ctx),
@@ -160,16 +158,19 @@ pub fn format_node(tree: &SyntaxTree, ctx: &mut FormatterContext) {
         SyntaxKind::LikeExpression => format_inline(tree, ctx),
         SyntaxKind::IntervalExpression => format_inline(tree, ctx),
         SyntaxKind::LambdaExpression => format_inline(tree, ctx),
+        SyntaxKind::TernaryExpression => format_inline(tree, ctx),
         SyntaxKind::TupleExpression => format_paren_list(tree, ctx),
         SyntaxKind::ArrayExpression => format_bracket_list(tree, ctx),
         SyntaxKind::ArrayAccessExpression => format_inline_no_spaces(tree, ctx),
         SyntaxKind::MapExpression => format_brace_list(tree, ctx),
         SyntaxKind::QueryParameterExpression => format_inline_no_spaces(tree, ctx),
+        SyntaxKind::PositionalParameter => format_inline_no_spaces(tree, ctx),
         SyntaxKind::TableIdentifier => format_inline(tree, ctx),
         SyntaxKind::TableExpression => format_inline(tree, ctx),
         SyntaxKind::TableFunction => format_function_call(tree, ctx),
         SyntaxKind::QualifiedName => format_inline_no_spaces(tree, ctx),
         SyntaxKind::ColumnReference => format_inline(tree, ctx),
+        SyntaxKind::ColumnTransformer => format_column_transformer(tree, ctx),
         SyntaxKind::DataType => format_data_type(tree, ctx),
         SyntaxKind::DataTypeParameters => format_data_type(tree, ctx),
         SyntaxKind::UsingList => format_inline(tree, ctx),
@@ -181,6 +182,7 @@ pub fn format_node(tree: &SyntaxTree, ctx: &mut FormatterContext) {
         SyntaxKind::ValueRow => format_paren_list(tree, ctx),
         SyntaxKind::InsertFormatClause => format_simple_clause(tree, ctx),
         SyntaxKind::FormatClause => format_simple_clause(tree, ctx),
+        SyntaxKind::IntoOutfileClause => format_simple_clause(tree, ctx),
 
         // CREATE / DDL
         SyntaxKind::CreateStatement => format_create_statement(tree, ctx),
@@ -859,9 +861,14 @@ fn format_unary_expression(tree: &SyntaxTree, ctx: &mut FormatterContext) {
                 ctx.write_keyword(t.text(ctx.source));
                 ctx.write_space();
             }
-            SyntaxChild::Token(t) if t.kind == SyntaxKind::Minus => {
-                // Unary minus: no space between - and operand
-                ctx.write_token("-");
+            SyntaxChild::Token(t) if matches!(t.kind, SyntaxKind::Minus | SyntaxKind::Plus) => {
+                // Unary sign: no space before the operand, except in `- -x`,
+                // where `--` would start a comment.
+                ctx.write_token(t.text(ctx.source));
+                let operand = tree.children.iter().find_map(SyntaxChild::as_tree);
+                if operand.is_some_and(|operand| starts_with_minus(operand, ctx.source)) {
+                    ctx.write_space();
+                }
             }
             SyntaxChild::Token(t) => emit_token(t, ctx),
             SyntaxChild::Tree(subtree) => format_node(subtree, ctx),
@@ -869,7 +876,13 @@ fn format_unary_expression(tree: &SyntaxTree, ctx: &mut FormatterContext) {
     }
 }
 
+fn starts_with_minus(tree: &SyntaxTree, source: &str) -> bool {
+    tree.significant_span()
+        .is_some_and(|(start, _)| source[start as usize..].starts_with('-'))
+}
+
 fn format_function_call(tree: &SyntaxTree, ctx: &mut FormatterContext) {
+    let mut in_args = false;
     for child in &tree.children {
         match child {
             SyntaxChild::Token(t) if t.kind == SyntaxKind::Whitespace => {
@@ -882,12 +895,27 @@ fn format_function_call(tree: &SyntaxTree, ctx: &mut FormatterContext) {
                 if t.kind == SyntaxKind::OpeningRoundBracket =>
             {
                 ctx.write_token("(");
+                in_args = true;
             }
             SyntaxChild::Token(t)
                 if t.kind == SyntaxKind::ClosingRoundBracket =>
             {
                 ctx.write_token(")");
             }
+            SyntaxChild::Token(t) if in_args && t.kind == SyntaxKind::Comma => {
+                // Table function arguments are direct children, not an ExpressionList
+                ctx.write_token(",");
+                ctx.write_space();
+            }
+            SyntaxChild::Token(t)
+                if in_args && t.kind == SyntaxKind::BareWord
+                    && t.text(ctx.source).eq_ignore_ascii_case("ELSE") =>
+            {
+                // viewIfPermitted(SELECT ... ELSE null(...))
+                ctx.write_space();
+                ctx.write_keyword(t.text(ctx.source));
+                ctx.write_space();
+            }
             SyntaxChild::Token(t) if t.kind == SyntaxKind::BareWord => {
                 // Function name -- don't uppercase, it's an identifier
                 ctx.write_token(t.text(ctx.source));
@@ -1011,14 +1039,16 @@ fn format_subquery(tree: &SyntaxTree, ctx: &mut FormatterContext) {
 // ---------------------------------------------------------------------------
 
 fn format_paren_list(tree: &SyntaxTree, ctx: &mut FormatterContext) {
-    for child in &tree.children {
+    for (i, child) in tree.children.iter().enumerate() {
         match child {
             SyntaxChild::Token(t) if t.kind == SyntaxKind::Whitespace => {
                 ctx.note_skipped_whitespace(t.text(ctx.source));
             }
             SyntaxChild::Token(t) if t.kind == SyntaxKind::Comma => {
                 ctx.write_token(",");
-                ctx.write_space();
+                if !is_trailing_comma(tree, i, SyntaxKind::ClosingRoundBracket) {
+                    ctx.write_space();
+                }
             }
             SyntaxChild::Token(t)
                 if t.kind == SyntaxKind::OpeningRoundBracket =>
@@ -1037,14 +1067,16 @@ fn format_paren_list(tree: &SyntaxTree, ctx: &mut FormatterContext) {
 }
 
 fn format_bracket_list(tree: &SyntaxTree, ctx: &mut FormatterContext) {
-    for child in &tree.children {
+    for (i, child) in tree.children.iter().enumerate() {
         match child {
             SyntaxChild::Token(t) if t.kind == SyntaxKind::Whitespace => {
                 ctx.note_skipped_whitespace(t.text(ctx.source));
             }
             SyntaxChild::Token(t) if t.kind == SyntaxKind::Comma => {
                 ctx.write_token(",");
-                ctx.write_space();
+                if !is_trailing_comma(tree, i, SyntaxKind::ClosingSquareBracket) {
+                    ctx.write_space();
+                }
             }
             SyntaxChild::Token(t)
                 if t.kind == SyntaxKind::OpeningSquareBracket =>
@@ -1062,6 +1094,15 @@ fn format_bracket_list(tree: &SyntaxTree, ctx: &mut FormatterContext) {
     }
 }
 
+/// True if the comma at `index` is followed only by whitespace and `close`, as
+/// in `(1,)` or `[1, 2,]`.
+fn is_trailing_comma(tree: &SyntaxTree, index: usize, close: SyntaxKind) -> bool {
+    tree.children[index + 1..]
+        .iter()
+        .find(|child| !matches!(child, SyntaxChild::Token(t) if t.kind == SyntaxKind::Whitespace))
+        .is_some_and(|child| matches!(child, SyntaxChild::Token(t) if t.kind == close))
+}
+
 fn format_brace_list(tree: &SyntaxTree, ctx: &mut FormatterContext) {
     for child in &tree.children {
         match child {
@@ -1274,6 +1315,37 @@ fn format_error_verbatim(tree: &SyntaxTree, ctx: &mut FormatterContext) {
     }
 }
 
+/// `* EXCEPT(id)`, `t.* APPLY toString`. The space before the transformer
+/// keyword can sit in the operand's trailing trivia, so write it explicitly;
+/// a parenthesized argument list keeps the source spacing.
+fn format_column_transformer(tree: &SyntaxTree, ctx: &mut FormatterContext) {
+    for child in &tree.children {
+        match child {
+            SyntaxChild::Token(t) if t.kind == SyntaxKind::Whitespace => {
+                ctx.note_skipped_whitespace(t.text(ctx.source));
+                ctx.write_space();
+            }
+            SyntaxChild::Token(t) if t.kind == SyntaxKind::Comment => {
+                emit_comment(t, ctx);
+            }
+            SyntaxChild::Token(t) => {
+                ctx.write_space();
+                emit_token(t, ctx);
+            }
+            SyntaxChild::Tree(subtree) => {
+                let parenthesized = matches!(
+                    subtree.children.first(),
+                    Some(SyntaxChild::Token(t)) if t.kind == SyntaxKind::OpeningRoundBracket
+                );
+                if subtree.kind == SyntaxKind::ExpressionList && !parenthesized {
+                    ctx.write_space();
+                }
+                format_node(subtree, ctx);
+            }
+        }
+    }
+}
+
 fn format_passthrough(tree: &SyntaxTree, ctx: &mut FormatterContext) {
     for child in &tree.children {
         match child {
@@ -1315,10 +1387,15 @@ fn emit_token(token: &Token, ctx: &mut FormatterContext) {
     if token.kind == SyntaxKind::Whitespace {
         return;
     }
-    if token.kind == SyntaxKind::BareWord && is_keyword(token.text(ctx.source)) && !is_value_keyword(token.text(ctx.source)) {
-        ctx.write_keyword(token.text(ctx.source));
+    let text = token.text(ctx.source);
+    if token.kind == SyntaxKind::BareWord
+        && !is_name_token(ctx.node_kind(), text)
+        && is_keyword(text)
+        && !is_value_keyword(text)
+    {
+        ctx.write_keyword(text);
     } else {
-        ctx.write_token(token.text(ctx.source));
+        ctx.write_token(text);
     }
 }
 
@@ -1338,6 +1415,7 @@ fn is_operator(token: &Token) -> bool {
             | SyntaxKind::GreaterOrEquals
             | SyntaxKind::Spaceship
             | SyntaxKind::Concatenation
+            | SyntaxKind::JsonExtractArrow
     )
 }
 
@@ -1550,11 +1628,10 @@ fn format_column_def_list(tree: &SyntaxTree, ctx: &mut FormatterContext) {
         let content = if let Some(ref c) = comment {
             // Strip the comment from the formatted content
             let trimmed = formatted.trim_end();
-            let content = trimmed.strip_suffix(c.as_str())
+            trimmed.strip_suffix(c.as_str())
                 .unwrap_or(trimmed)
                 .trim_end()
-                .to_string();
-            content
+                .to_string()
         } else {
             formatted.trim_end().to_string()
         };
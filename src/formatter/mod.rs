@@ -1,7 +1,8 @@
 mod context;
 mod format_node;
 
-use crate::parser::syntax_tree::SyntaxTree;
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
 use context::FormatterContext;
 
 pub struct FormatConfig {
@@ -23,3 +24,41 @@ pub fn format(tree: &SyntaxTree, config: &FormatConfig, source: &str) -> String
     format_node::format_node(tree, &mut ctx);
     ctx.finish()
 }
+
+/// A minimal-diff alternative to [`format`]: only keyword case changes (per
+/// `config.uppercase_keywords`), and every other byte, including whitespace,
+/// comments and line breaks, is kept as written. Names that happen to be
+/// keywords (`SELECT date FROM t`) and nodes that failed to parse are left
+/// alone. `indent_width` is unused.
+pub fn format_minimal(tree: &SyntaxTree, config: &FormatConfig, source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    write_minimal(tree, config, source, &mut out);
+    out
+}
+
+fn write_minimal(tree: &SyntaxTree, config: &FormatConfig, source: &str, out: &mut String) {
+    for child in &tree.children {
+        match child {
+            SyntaxChild::Tree(subtree) if subtree.kind == SyntaxKind::Error => {
+                out.push_str(&subtree.to_source(source));
+            }
+            SyntaxChild::Tree(subtree) => write_minimal(subtree, config, source, out),
+            SyntaxChild::Token(token) => {
+                let text = token.text(source);
+                if token.kind == SyntaxKind::BareWord
+                    && !format_node::is_name_token(tree.kind, text)
+                    && format_node::is_keyword(text)
+                    && !format_node::is_value_keyword(text)
+                {
+                    if config.uppercase_keywords {
+                        out.push_str(&text.to_uppercase());
+                    } else {
+                        out.push_str(&text.to_lowercase());
+                    }
+                } else {
+                    out.push_str(text);
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,9 @@
+use crate::parser::keyword::Keyword;
 use crate::parser::syntax_kind::SyntaxKind;
 
 /// Structure representing a token in the SQL
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Token {
     pub kind: SyntaxKind,
     pub start: u32, // Start byte offset in the source
@@ -18,4 +19,15 @@ impl Token {
     pub fn text<'a>(&self, source: &'a str) -> &'a str {
         &source[self.start as usize..self.end as usize]
     }
+
+    /// True if this token has the given kind and text, ignoring its position.
+    pub fn same_as(&self, source: &str, kind: SyntaxKind, text: &str) -> bool {
+        self.kind == kind && self.text(source) == text
+    }
+
+    /// True if this is a bareword spelling a [`Keyword`], in any case. Whether
+    /// it is used as one depends on where it stands: `key` also names columns.
+    pub fn is_keyword(&self, source: &str) -> bool {
+        self.kind == SyntaxKind::BareWord && Keyword::from_text(self.text(source)).is_some()
+    }
 }
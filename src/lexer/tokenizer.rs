@@ -1,8 +1,69 @@
+use std::ops::ControlFlow;
+
 use crate::lexer::token::Token;
+use crate::parser::keyword::Keyword;
 use crate::parser::syntax_kind::SyntaxKind;
 
-/// Maximum query size (can be configured)
-const MAX_QUERY_SIZE: usize = 1_000_000; // 1MB
+/// Default limit on the input length in bytes, as in ClickHouse's
+/// `max_query_size` setting. Longer input tokenizes to a single
+/// `ErrorMaxQuerySizeExceeded` token; see [`Tokenizer::set_max_query_size`].
+pub const DEFAULT_MAX_QUERY_SIZE: usize = 1_000_000; // 1MB
+
+/// Keywords that are always followed by an expression, so a `.` right after
+/// them starts a number (`SELECT .5`) rather than a qualified name.
+const EXPRESSION_KEYWORDS: &[Keyword] = &[
+    Keyword::Select, Keyword::Where, Keyword::Prewhere, Keyword::Having, Keyword::Qualify,
+    Keyword::And, Keyword::Or, Keyword::Not, Keyword::Case, Keyword::When, Keyword::Then,
+    Keyword::Else, Keyword::In, Keyword::Between, Keyword::Like, Keyword::Ilike, Keyword::Limit,
+    Keyword::Offset, Keyword::By, Keyword::Distinct, Keyword::Interval, Keyword::Values,
+];
+
+/// Fixed-spelling tokens, longest first so that a prefix (`<`) never wins
+/// over a longer operator (`<=>`). `--` and `/*` start comments and are
+/// handled before this table is consulted.
+const OPERATORS: &[(&str, SyntaxKind)] = &[
+    // Three characters
+    ("->>", SyntaxKind::JsonExtractArrow),
+    ("<=>", SyntaxKind::Spaceship),
+    // Two characters
+    ("->", SyntaxKind::Arrow),
+    ("::", SyntaxKind::DoubleColon),
+    ("==", SyntaxKind::Equals), // `==` is treated as `=` in ClickHouse
+    ("!=", SyntaxKind::NotEquals),
+    ("<>", SyntaxKind::NotEquals),
+    ("<=", SyntaxKind::LessOrEquals),
+    (">=", SyntaxKind::GreaterOrEquals),
+    ("||", SyntaxKind::Concatenation),
+    ("@@", SyntaxKind::DoubleAt),
+    // Vertical output delimiter - ClickHouse specific
+    ("\\G", SyntaxKind::VerticalDelimiter),
+    ("\\g", SyntaxKind::VerticalDelimiter),
+    // One character
+    ("(", SyntaxKind::OpeningRoundBracket),
+    (")", SyntaxKind::ClosingRoundBracket),
+    ("[", SyntaxKind::OpeningSquareBracket),
+    ("]", SyntaxKind::ClosingSquareBracket),
+    ("{", SyntaxKind::OpeningCurlyBrace),
+    ("}", SyntaxKind::ClosingCurlyBrace),
+    (",", SyntaxKind::Comma),
+    (";", SyntaxKind::Semicolon),
+    ("*", SyntaxKind::Star),
+    ("$", SyntaxKind::DollarSign),
+    ("+", SyntaxKind::Plus),
+    ("-", SyntaxKind::Minus),
+    ("/", SyntaxKind::Slash),
+    ("%", SyntaxKind::Percent),
+    ("?", SyntaxKind::QuestionMark),
+    (":", SyntaxKind::Colon),
+    ("^", SyntaxKind::Caret),
+    // No `=>`: like ClickHouse, it lexes as `=` followed by `>`.
+    ("=", SyntaxKind::Equals),
+    ("!", SyntaxKind::ErrorSingleExclamationMark),
+    ("<", SyntaxKind::Less),
+    (">", SyntaxKind::Greater),
+    ("|", SyntaxKind::ErrorSinglePipeMark),
+    ("@", SyntaxKind::At),
+];
 
 /// Tokenizer for ClickHouse SQL
 pub struct Tokenizer<'a> {
@@ -11,41 +72,67 @@ pub struct Tokenizer<'a> {
     position: usize,
     start: usize,
     include_whitespace: bool,
+    max_query_size: Option<usize>,
+    /// Last non-trivia token, used to tell `.5` (a number) from `t.5`
+    /// (tuple element access).
+    prev_significant: Option<Token>,
 }
 
 impl<'a> Tokenizer<'a> {
-    /// Create a new tokenizer for the given input
+    /// Create a new tokenizer for the given input, limited to
+    /// [`DEFAULT_MAX_QUERY_SIZE`] bytes.
     pub fn new(input: &'a str) -> Self {
-        // Check query size limit
-        if input.len() > MAX_QUERY_SIZE {
-            // We still create the tokenizer but will return an error token
-            // when tokenizing starts
-        }
-
         Self {
             input,
             chars: input.chars(),
             position: 0,
             start: 0,
             include_whitespace: true, // Default to including whitespace
+            max_query_size: Some(DEFAULT_MAX_QUERY_SIZE),
+            prev_significant: None,
         }
     }
 
+    /// Create a new tokenizer with the given input size limit in bytes, or
+    /// none at all. Input over the limit tokenizes to a single
+    /// `ErrorMaxQuerySizeExceeded` token.
+    pub fn with_max_query_size(input: &'a str, limit: Option<usize>) -> Self {
+        let mut tokenizer = Self::new(input);
+        tokenizer.set_max_query_size(limit);
+        tokenizer
+    }
+
     /// Set whether to include whitespace tokens in the output
     pub fn set_include_whitespace(&mut self, include: bool) -> &mut Self {
         self.include_whitespace = include;
         self
     }
 
+    /// Change the input size limit; `None` disables it.
+    pub fn set_max_query_size(&mut self, limit: Option<usize>) -> &mut Self {
+        self.max_query_size = limit;
+        self
+    }
+
     /// Tokenize the entire input
     pub fn tokenize(&mut self) -> Vec<Token> {
         let mut tokens = Vec::new();
+        self.tokenize_each(|token| {
+            tokens.push(token.clone());
+            ControlFlow::Continue(())
+        });
+        tokens
+    }
 
+    /// Tokenize the input, handing each token to `f` instead of collecting
+    /// them. Returning `ControlFlow::Break` stops tokenizing.
+    pub fn tokenize_each(&mut self, mut f: impl FnMut(&Token) -> ControlFlow<()>) {
         // Check for max query size
-        if self.input.len() > MAX_QUERY_SIZE {
-            tokens.push(self.error_token(SyntaxKind::ErrorMaxQuerySizeExceeded));
-            tokens.push(self.eof_token());
-            return tokens;
+        if self.max_query_size.is_some_and(|limit| self.input.len() > limit) {
+            if f(&self.error_token(SyntaxKind::ErrorMaxQuerySizeExceeded)).is_continue() {
+                let _ = f(&self.eof_token());
+            }
+            return;
         }
 
         loop {
@@ -62,14 +149,22 @@ impl<'a> Tokenizer<'a> {
                 break;
             }
 
-            tokens.push(token.clone());
+            if f(&token).is_break() {
+                break;
+            }
         }
-
-        tokens
     }
 
     /// Get the next token
     pub fn next_token(&mut self) -> Token {
+        let token = self.scan_token();
+        if !matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Comment) {
+            self.prev_significant = Some(token.clone());
+        }
+        token
+    }
+
+    fn scan_token(&mut self) -> Token {
         self.start = self.position;
 
         // Check for end of input
@@ -118,115 +213,70 @@ impl<'a> Tokenizer<'a> {
                 SyntaxKind::ErrorBackQuoteIsNotClosed,
             ),
 
-            // Brackets
-            '(' => self.create_token(SyntaxKind::OpeningRoundBracket),
-            ')' => self.create_token(SyntaxKind::ClosingRoundBracket),
-            '[' => self.create_token(SyntaxKind::OpeningSquareBracket),
-            ']' => self.create_token(SyntaxKind::ClosingSquareBracket),
-            '{' => self.create_token(SyntaxKind::OpeningCurlyBrace),
-            '}' => self.create_token(SyntaxKind::ClosingCurlyBrace),
-
-            // Punctuation
-            ',' => self.create_token(SyntaxKind::Comma),
-            ';' => self.create_token(SyntaxKind::Semicolon),
-            '.' => self.create_token(SyntaxKind::Dot),
-
-            // Operators and symbols
-            '*' => self.create_token(SyntaxKind::Star),
-            '$' => self.create_token(SyntaxKind::DollarSign),
-            '+' => self.create_token(SyntaxKind::Plus),
-            '-' => {
-                if self.match_char('>') {
-                    self.create_token(SyntaxKind::Arrow)
-                } else {
-                    self.create_token(SyntaxKind::Minus)
-                }
-            }
-            '/' => self.create_token(SyntaxKind::Slash),
-            '%' => self.create_token(SyntaxKind::Percent),
-            '?' => self.create_token(SyntaxKind::QuestionMark),
-            ':' => {
-                if self.match_char(':') {
-                    self.create_token(SyntaxKind::DoubleColon)
-                } else {
-                    self.create_token(SyntaxKind::Colon)
-                }
-            }
-            '^' => self.create_token(SyntaxKind::Caret),
-            '=' => {
-                if self.match_char('>') {
-                    if self.match_char('<') {
-                        self.create_token(SyntaxKind::Spaceship)
-                    } else {
-                        // Invalid, but treat as equals for now
-                        self.create_token(SyntaxKind::Equals)
-                    }
-                } else if self.match_char('=') {
-                    // `==` is treated as `=` in ClickHouse
-                    self.create_token(SyntaxKind::Equals)
-                } else {
-                    self.create_token(SyntaxKind::Equals)
-                }
-            }
-            '!' => {
-                if self.match_char('=') {
-                    self.create_token(SyntaxKind::NotEquals)
-                } else {
-                    self.create_token(SyntaxKind::ErrorSingleExclamationMark)
-                }
-            }
-            '<' => {
-                if self.match_char('=') {
-                    if self.match_char('>') {
-                        self.create_token(SyntaxKind::Spaceship)
-                    } else {
-                        self.create_token(SyntaxKind::LessOrEquals)
-                    }
-                } else if self.match_char('>') {
-                    self.create_token(SyntaxKind::NotEquals)
-                } else {
-                    self.create_token(SyntaxKind::Less)
-                }
-            }
-            '>' => {
-                if self.match_char('=') {
-                    self.create_token(SyntaxKind::GreaterOrEquals)
-                } else {
-                    self.create_token(SyntaxKind::Greater)
-                }
-            }
-            '|' => {
-                if self.match_char('|') {
-                    self.create_token(SyntaxKind::Concatenation)
+            // `.5` is a number, `t.a` member access
+            '.' => {
+                if self.current_char_is_digit() && !self.prev_allows_member_access() {
+                    self.read_leading_dot_number()
                 } else {
-                    self.create_token(SyntaxKind::ErrorSinglePipeMark)
-                }
-            }
-            '@' => {
-                if self.match_char('@') {
-                    self.create_token(SyntaxKind::DoubleAt)
-                } else {
-                    self.create_token(SyntaxKind::At)
+                    self.create_token(SyntaxKind::Dot)
                 }
             }
 
             // Identifiers and keywords
             'a'..='z' | 'A'..='Z' | '_' => self.read_bare_word(),
 
-            // Catch vertical delimiter - ClickHouse specific
-            '\\' => {
-                if self.match_char('G') || self.match_char('g') {
-                    self.create_token(SyntaxKind::VerticalDelimiter)
-                } else {
-                    self.create_token(SyntaxKind::ErrorToken)
-                }
-            }
+            // Here-doc strings: $$...$$ or $tag$...$tag$
+            '$' => self.read_heredoc(),
 
-            // Anything else is an error
-            _ => self.create_token(SyntaxKind::ErrorToken),
+            // Brackets, punctuation and operators
+            _ => self.read_operator(),
         }
     }
 
+    /// Read a here-doc string starting at `self.start`. The body between the
+    /// `$$` or `$tag$` delimiters is taken verbatim, quotes and all. A tag
+    /// starts with a letter or `_`, like an identifier. A `$` that doesn't
+    /// open a delimiter is an operator.
+    fn read_heredoc(&mut self) -> Token {
+        let rest = &self.input[self.start..];
+        if rest[1..].starts_with(|c: char| c.is_ascii_digit()) {
+            return self.read_operator();
+        }
+        let tag_len = rest[1..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len() - 1);
+        if !rest[1 + tag_len..].starts_with('$') {
+            return self.read_operator();
+        }
+        let delimiter = &rest[..tag_len + 2];
+        let (end, kind) = match rest[delimiter.len()..].find(delimiter) {
+            Some(body_len) => (
+                self.start + 2 * delimiter.len() + body_len,
+                SyntaxKind::HereDoc,
+            ),
+            None => (self.input.len(), SyntaxKind::ErrorHereDocIsNotClosed),
+        };
+        while self.position < end {
+            self.advance();
+        }
+        self.create_token(kind)
+    }
+
+    /// Read a bracket, punctuation mark or operator starting at `self.start`,
+    /// taking the longest match in [`OPERATORS`]. The first character has
+    /// already been consumed.
+    fn read_operator(&mut self) -> Token {
+        let rest = &self.input[self.start..];
+        let Some(&(text, kind)) = OPERATORS.iter().find(|(text, _)| rest.starts_with(text)) else {
+            return self.create_token(SyntaxKind::ErrorToken);
+        };
+        // Operators are ASCII, so one char per byte.
+        for _ in 1..text.len() {
+            self.advance();
+        }
+        self.create_token(kind)
+    }
+
     /// Read whitespace characters
     fn read_whitespace(&mut self) -> Token {
         while let Some(c) = self.peek() {
@@ -274,75 +324,55 @@ impl<'a> Tokenizer<'a> {
 
     /// Read a number (integer, float, hex, etc.)
     fn read_number(&mut self) -> Token {
-        // Check if previous token was a dot - for chained tuple access operators (x.1.1)
-        let prev_was_dot =
-            self.start > 0 && self.input.as_bytes()[self.start - 1] == b'.';
+        // A number right after a member-access dot is a tuple index: read
+        // digits only, so in `x.1.2` the second `.` is another access rather
+        // than a decimal point. Going by the previous token instead of the
+        // preceding byte also covers `x. 1` and needs no offset arithmetic.
+        let prev_was_dot = self
+            .prev_significant
+            .as_ref()
+            .is_some_and(|prev| prev.kind == SyntaxKind::Dot);
 
         if prev_was_dot {
             // Simple integer parsing for tuple access
             self.read_digits();
         } else {
-            // Check for hex/binary prefix
-            let mut hex = false;
-
+            // A `0x`, `0b` or `0o` prefix only counts when a digit of that
+            // base follows; otherwise `0` is a number of its own.
+            let mut radix = 10;
             if self.position - self.start == 1 && &self.input[self.start..self.position] == "0" {
-                if let Some(next) = self.peek() {
-                    match next {
-                        'x' | 'X' => {
-                            if let Some(next_next) = self.peek_next() {
-                                if next_next.is_ascii_hexdigit() {
-                                    self.advance(); // Consume 'x' or 'X'
-                                    hex = true;
-                                }
-                            }
-                        }
-                        'b' | 'B' => {
-                            if let Some(next_next) = self.peek_next() {
-                                if next_next == '0' || next_next == '1' {
-                                    self.advance(); // Consume 'b' or 'B'
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
-
-            // Read the main part of the number
-            if hex {
-                self.read_hex_digits();
-            } else {
-                self.read_digits();
-            }
-
-            // Decimal point
-            if self.peek_is('.') {
-                self.advance(); // Consume the decimal point
-
-                if hex {
-                    self.read_hex_digits();
-                } else {
-                    self.read_digits();
+                radix = match (self.peek(), self.peek_next()) {
+                    (Some('x' | 'X'), Some(d)) if d.is_ascii_hexdigit() => 16,
+                    (Some('b' | 'B'), Some(d)) if d.is_digit(2) => 2,
+                    (Some('o' | 'O'), Some(d)) if d.is_digit(8) => 8,
+                    _ => 10,
+                };
+                if radix != 10 {
+                    self.advance(); // Consume the prefix letter
                 }
             }
 
-            // Exponentiation
-            if let Some(c) = self.peek() {
-                // Hex numbers use 'p'/'P', decimal numbers use 'e'/'E'
-                if (hex && (c == 'p' || c == 'P')) || (!hex && (c == 'e' || c == 'E')) {
-                    self.advance(); // Consume e/E/p/P
+            self.read_radix_digits(radix);
 
-                    // Optional sign
-                    if self.peek_is('+') || self.peek_is('-') {
+            if radix == 2 || radix == 8 {
+                // `0b12` or `0o78`: a decimal digit out of range for the base.
+                if self.current_char_is_digit() {
+                    while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
                         self.advance();
                     }
+                    return self.create_token(SyntaxKind::ErrorWrongNumber);
+                }
+            } else {
+                let hex = radix == 16;
 
-                    // Exponent is always decimal
-                    if !self.current_char_is_digit() {
-                        return self.create_token(SyntaxKind::ErrorWrongNumber);
-                    }
+                // Decimal point
+                if self.peek_is('.') {
+                    self.advance(); // Consume the decimal point
+                    self.read_radix_digits(radix);
+                }
 
-                    self.read_digits();
+                if !self.read_exponent(hex) {
+                    return self.create_token(SyntaxKind::ErrorWrongNumber);
                 }
             }
         }
@@ -355,71 +385,82 @@ impl<'a> Tokenizer<'a> {
         self.create_token(SyntaxKind::Number)
     }
 
-    /// Read hex digits, including underscore separators
-    fn read_hex_digits(&mut self) {
-        let mut start_of_block = true;
+    /// Read a float that starts with its decimal point, e.g. `.5` or `.5e3`.
+    /// The leading `.` has already been consumed.
+    fn read_leading_dot_number(&mut self) -> Token {
+        self.read_digits();
 
-        while let Some(c) = self.peek() {
-            if c.is_ascii_hexdigit() {
-                self.advance();
-                start_of_block = false;
-            } else if c == '_' && !start_of_block {
-                // Underscore separator is valid only between digits
-                if let Some(next) = self.peek_next() {
-                    if next.is_ascii_hexdigit() {
-                        self.advance();
-                        start_of_block = true;
-                    } else {
-                        break;
-                    }
-                } else {
-                    break;
-                }
-            } else {
-                break;
-            }
+        if !self.read_exponent(false) {
+            return self.create_token(SyntaxKind::ErrorWrongNumber);
+        }
+
+        if self.peek().is_some_and(|c| c.is_alphabetic() || c == '_') {
+            return self.read_identifier_starting_with_number();
         }
+
+        self.create_token(SyntaxKind::Number)
     }
 
-    /// Read decimal digits, including underscore separators
-    fn read_digits(&mut self) {
-        let mut start_of_block = true;
+    /// Read an optional exponent suffix. Returns false if an exponent marker
+    /// is present but not followed by any digits.
+    fn read_exponent(&mut self, hex: bool) -> bool {
+        if let Some(c) = self.peek() {
+            // Hex numbers use 'p'/'P', decimal numbers use 'e'/'E'
+            if (hex && (c == 'p' || c == 'P')) || (!hex && (c == 'e' || c == 'E')) {
+                self.advance(); // Consume e/E/p/P
 
-        while let Some(c) = self.peek() {
-            if c.is_ascii_digit() {
-                self.advance();
-                start_of_block = false;
-            } else if c == '_' && !start_of_block {
-                // Underscore separator is valid only between digits
-                if let Some(next) = self.peek_next() {
-                    if next.is_ascii_digit() {
-                        self.advance();
-                        start_of_block = true;
-                    } else {
-                        break;
-                    }
-                } else {
-                    break;
+                // Optional sign
+                if self.peek_is('+') || self.peek_is('-') {
+                    self.advance();
                 }
-            } else {
-                break;
+
+                // Exponent is always decimal
+                if !self.current_char_is_digit() {
+                    return false;
+                }
+
+                self.read_digits();
+            }
+        }
+        true
+    }
+
+    /// Whether a `.` right after the previous token is a member/tuple access
+    /// (`t.1`, `(a, b).2`, `arr[1].x`) rather than the start of a number.
+    fn prev_allows_member_access(&self) -> bool {
+        let Some(prev) = &self.prev_significant else {
+            return false;
+        };
+        match prev.kind {
+            SyntaxKind::BareWord => {
+                Keyword::from_text(prev.text(self.input))
+                    .is_none_or(|keyword| !EXPRESSION_KEYWORDS.contains(&keyword))
             }
+            SyntaxKind::QuotedIdentifier
+            | SyntaxKind::Number
+            | SyntaxKind::ClosingRoundBracket
+            | SyntaxKind::ClosingSquareBracket => true,
+            _ => false,
         }
     }
 
-    /// Read binary digits (0 and 1), including underscore separators
-    #[allow(dead_code)]
-    fn read_binary_digits(&mut self) {
+    /// Read decimal digits, including underscore separators
+    fn read_digits(&mut self) {
+        self.read_radix_digits(10);
+    }
+
+    /// Read digits in the given base, including underscore separators
+    fn read_radix_digits(&mut self, radix: u32) {
         let mut start_of_block = true;
 
         while let Some(c) = self.peek() {
-            if c == '0' || c == '1' {
+            if c.is_digit(radix) {
                 self.advance();
                 start_of_block = false;
             } else if c == '_' && !start_of_block {
                 // Underscore separator is valid only between digits
                 if let Some(next) = self.peek_next() {
-                    if next == '0' || next == '1' {
+                    if next.is_digit(radix) {
                         self.advance();
                         start_of_block = true;
                     } else {
@@ -513,7 +554,7 @@ impl<'a> Tokenizer<'a> {
             }
         }
 
-        // Check if it's a keyword (for information only, still returns BareWord)
+        // Keywords are barewords too; see `Token::is_keyword`.
         self.create_token(SyntaxKind::BareWord)
     }
 
@@ -588,7 +629,6 @@ impl<'a> Tokenizer<'a> {
     }
 
     /// Tokenize up to a specific position
-    #[allow(dead_code)]
     pub fn tokenize_up_to_position(&mut self, position: usize) -> Vec<Token> {
         let mut tokens = Vec::new();
 
@@ -617,6 +657,12 @@ pub fn tokenize_with_whitespace(sql: &str) -> Vec<Token> {
     tokenizer.tokenize()
 }
 
+/// Like [`tokenize_with_whitespace`], with the given input size limit in
+/// bytes instead of [`DEFAULT_MAX_QUERY_SIZE`], or none at all.
+pub fn tokenize_with_max_query_size(sql: &str, limit: Option<usize>) -> Vec<Token> {
+    Tokenizer::with_max_query_size(sql, limit).tokenize()
+}
+
 /// Helper function to tokenize a SQL string, excluding whitespace
 #[allow(dead_code)]
 pub fn tokenize(sql: &str) -> Vec<Token> {
@@ -625,8 +671,109 @@ pub fn tokenize(sql: &str) -> Vec<Token> {
     tokenizer.tokenize()
 }
 
+/// Tokenize a SQL string, whitespace and comments included, handing each
+/// token to `f` instead of building a `Vec`. Tokens are plain byte ranges
+/// into `sql`, so this path allocates nothing per token; use it where memory
+/// is tight or when tokens are consumed once. Returning `ControlFlow::Break`
+/// stops tokenizing. The final `EndOfStream` token is not passed to `f`.
+pub fn for_each_token(sql: &str, f: impl FnMut(&Token) -> ControlFlow<()>) {
+    Tokenizer::new(sql).tokenize_each(f);
+}
+
+/// A token the tokenizer couldn't read cleanly, e.g. an unterminated string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexError {
+    /// The `Error*` token kind the tokenizer produced.
+    pub kind: SyntaxKind,
+    /// Byte range of the offending token.
+    pub start: u32,
+    pub end: u32,
+}
+
+impl LexError {
+    /// A human-readable description of the error.
+    pub fn message(&self) -> &'static str {
+        match self.kind {
+            SyntaxKind::ErrorMultilineCommentIsNotClosed => "Multi-line comment is not closed",
+            SyntaxKind::ErrorSingleQuoteIsNotClosed => "String literal is not closed",
+            SyntaxKind::ErrorDoubleQuoteIsNotClosed => "Double-quoted identifier is not closed",
+            SyntaxKind::ErrorBackQuoteIsNotClosed => "Backquoted identifier is not closed",
+            SyntaxKind::ErrorHereDocIsNotClosed => "Here-doc string is not closed",
+            SyntaxKind::ErrorSingleExclamationMark => "Unexpected `!`, did you mean `!=`?",
+            SyntaxKind::ErrorSinglePipeMark => "Unexpected `|`, did you mean `||`?",
+            SyntaxKind::ErrorWrongNumber => "Malformed number",
+            SyntaxKind::ErrorMaxQuerySizeExceeded => "Query is too large to tokenize",
+            _ => "Unexpected character",
+        }
+    }
+}
+
+pub(crate) fn is_lex_error(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::ErrorToken
+            | SyntaxKind::ErrorMultilineCommentIsNotClosed
+            | SyntaxKind::ErrorSingleQuoteIsNotClosed
+            | SyntaxKind::ErrorDoubleQuoteIsNotClosed
+            | SyntaxKind::ErrorBackQuoteIsNotClosed
+            | SyntaxKind::ErrorHereDocIsNotClosed
+            | SyntaxKind::ErrorSingleExclamationMark
+            | SyntaxKind::ErrorSinglePipeMark
+            | SyntaxKind::ErrorWrongNumber
+            | SyntaxKind::ErrorMaxQuerySizeExceeded
+    )
+}
+
+/// Tokenize a SQL string, excluding whitespace, and split out lexical errors:
+/// the tokens that lexed cleanly and, separately, every error token. Lets a
+/// validator report lexing problems before parsing.
+pub fn tokenize_checked(sql: &str) -> (Vec<Token>, Vec<LexError>) {
+    let mut tokens = Vec::new();
+    let mut errors = Vec::new();
+    let mut tokenizer = Tokenizer::new(sql);
+    tokenizer.set_include_whitespace(false);
+    tokenizer.tokenize_each(|token| {
+        if is_lex_error(token.kind) {
+            errors.push(LexError {
+                kind: token.kind,
+                start: token.start,
+                end: token.end,
+            });
+        } else if token.kind != SyntaxKind::EndOfStream {
+            tokens.push(token.clone());
+        }
+        ControlFlow::Continue(())
+    });
+    (tokens, errors)
+}
+
+/// Byte ranges of the statements in `sql`, split at top-level `;` and `\G`
+/// without parsing. Semicolons inside strings, quoted identifiers, comments
+/// and here-docs don't split. Each range runs from a statement's first token
+/// to the end of its last one, so it excludes the separator and surrounding
+/// whitespace; empty statements are skipped.
+pub fn split_statements(sql: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+    let mut tokenizer = Tokenizer::new(sql);
+    tokenizer.set_include_whitespace(false);
+    tokenizer.tokenize_each(|token| {
+        match token.kind {
+            SyntaxKind::Semicolon | SyntaxKind::VerticalDelimiter | SyntaxKind::EndOfStream => {
+                ranges.extend(current.take());
+            }
+            _ => {
+                let start = current.map_or(token.start as usize, |(start, _)| start);
+                current = Some((start, token.end as usize));
+            }
+        }
+        ControlFlow::Continue(())
+    });
+    ranges.extend(current);
+    ranges
+}
+
 /// Helper function to tokenize up to a position, excluding whitespace
-#[allow(dead_code)]
 pub fn tokenize_up_to(sql: &str, position: usize) -> Vec<Token> {
     let mut tokenizer = Tokenizer::new(sql);
     tokenizer.set_include_whitespace(false);
@@ -645,41 +792,18 @@ mod tests {
 
         assert_eq!(tokens.len(), 12);
 
-        assert_eq!(tokens[0].kind, SyntaxKind::BareWord);
-        assert_eq!(tokens[0].text(sql), "SELECT");
-
-        assert_eq!(tokens[1].kind, SyntaxKind::Star);
-        assert_eq!(tokens[1].text(sql), "*");
-
-        assert_eq!(tokens[2].kind, SyntaxKind::BareWord);
-        assert_eq!(tokens[2].text(sql), "FROM");
-
-        assert_eq!(tokens[3].kind, SyntaxKind::BareWord);
-        assert_eq!(tokens[3].text(sql), "system");
-
-        assert_eq!(tokens[4].kind, SyntaxKind::Dot);
-        assert_eq!(tokens[4].text(sql), ".");
-
-        assert_eq!(tokens[5].kind, SyntaxKind::BareWord);
-        assert_eq!(tokens[5].text(sql), "numbers");
-
-        assert_eq!(tokens[6].kind, SyntaxKind::BareWord);
-        assert_eq!(tokens[6].text(sql), "WHERE");
-
-        assert_eq!(tokens[7].kind, SyntaxKind::BareWord);
-        assert_eq!(tokens[7].text(sql), "number");
-
-        assert_eq!(tokens[8].kind, SyntaxKind::Greater);
-        assert_eq!(tokens[8].text(sql), ">");
-
-        assert_eq!(tokens[9].kind, SyntaxKind::Number);
-        assert_eq!(tokens[9].text(sql), "1");
-
-        assert_eq!(tokens[10].kind, SyntaxKind::BareWord);
-        assert_eq!(tokens[10].text(sql), "LIMIT");
-
-        assert_eq!(tokens[11].kind, SyntaxKind::Number);
-        assert_eq!(tokens[11].text(sql), "5");
+        assert!(tokens[0].same_as(sql, SyntaxKind::BareWord, "SELECT"));
+        assert!(tokens[1].same_as(sql, SyntaxKind::Star, "*"));
+        assert!(tokens[2].same_as(sql, SyntaxKind::BareWord, "FROM"));
+        assert!(tokens[3].same_as(sql, SyntaxKind::BareWord, "system"));
+        assert!(tokens[4].same_as(sql, SyntaxKind::Dot, "."));
+        assert!(tokens[5].same_as(sql, SyntaxKind::BareWord, "numbers"));
+        assert!(tokens[6].same_as(sql, SyntaxKind::BareWord, "WHERE"));
+        assert!(tokens[7].same_as(sql, SyntaxKind::BareWord, "number"));
+        assert!(tokens[8].same_as(sql, SyntaxKind::Greater, ">"));
+        assert!(tokens[9].same_as(sql, SyntaxKind::Number, "1"));
+        assert!(tokens[10].same_as(sql, SyntaxKind::BareWord, "LIMIT"));
+        assert!(tokens[11].same_as(sql, SyntaxKind::Number, "5"));
     }
 
     #[test]
@@ -701,27 +825,15 @@ mod tests {
 
         let tokens = tokenize(sql);
 
-        // Find string literal
-        let string_token = tokens
-            .iter()
-            .find(|t| t.kind == SyntaxKind::StringToken)
-            .unwrap();
-        assert_eq!(string_token.text(sql), "'string literal'");
-
-        // Find quoted identifiers
-        let quoted_tokens: Vec<&Token> = tokens
-            .iter()
-            .filter(|t| t.kind == SyntaxKind::QuotedIdentifier)
-            .collect();
-
-        assert_eq!(quoted_tokens.len(), 2);
-        assert_eq!(quoted_tokens[0].text(sql), "\"quoted identifier\"");
-        assert_eq!(quoted_tokens[1].text(sql), "`backtick identifier`");
+        assert_eq!(tokens.len(), 6);
+        assert!(tokens[1].same_as(sql, SyntaxKind::StringToken, "'string literal'"));
+        assert!(tokens[3].same_as(sql, SyntaxKind::QuotedIdentifier, "\"quoted identifier\""));
+        assert!(tokens[5].same_as(sql, SyntaxKind::QuotedIdentifier, "`backtick identifier`"));
     }
 
     #[test]
     fn test_tokenize_numbers() {
-        let sql = "SELECT 123, 123.456, 1.23e4, 1.23E-4, 0xFF, 0b101";
+        let sql = "SELECT 123, 123.456, 1.23e4, 1.23E-4, 0xFF, 0b101, 0b1_0, 0o17, 0x1.8p3";
 
         let tokens = tokenize(sql);
 
@@ -730,13 +842,129 @@ mod tests {
             .filter(|t| t.kind == SyntaxKind::Number)
             .collect();
 
-        assert_eq!(number_tokens.len(), 6);
+        assert_eq!(number_tokens.len(), 9);
         assert_eq!(number_tokens[0].text(sql), "123");
         assert_eq!(number_tokens[1].text(sql), "123.456");
         assert_eq!(number_tokens[2].text(sql), "1.23e4");
         assert_eq!(number_tokens[3].text(sql), "1.23E-4");
         assert_eq!(number_tokens[4].text(sql), "0xFF");
         assert_eq!(number_tokens[5].text(sql), "0b101");
+        assert_eq!(number_tokens[6].text(sql), "0b1_0");
+        assert_eq!(number_tokens[7].text(sql), "0o17");
+        assert_eq!(number_tokens[8].text(sql), "0x1.8p3");
+
+        // Digits out of range for the base make the whole literal an error.
+        for sql in ["0b12", "0b102", "0o78"] {
+            let tokens = tokenize(sql);
+            assert_eq!(tokens.len(), 1, "{sql:?} split into {tokens:?}");
+            assert!(tokens[0].same_as(sql, SyntaxKind::ErrorWrongNumber, sql));
+        }
+
+        // A prefix with no digit of its base after it isn't a number prefix.
+        assert!(tokenize("0x").iter().all(|t| t.kind != SyntaxKind::Number));
+        assert!(tokenize("0xg").iter().all(|t| t.kind != SyntaxKind::Number));
+        assert!(tokenize("0b2").iter().all(|t| t.kind != SyntaxKind::Number));
+    }
+
+    #[test]
+    fn test_tokenize_dot_number_forms() {
+        let kinds_and_text = |sql: &'static str| -> Vec<(SyntaxKind, &'static str)> {
+            tokenize(sql).iter().map(|t| (t.kind, t.text(sql))).collect()
+        };
+
+        assert_eq!(kinds_and_text(".5"), [(SyntaxKind::Number, ".5")]);
+        assert_eq!(kinds_and_text(".5e-3"), [(SyntaxKind::Number, ".5e-3")]);
+        assert_eq!(kinds_and_text("1."), [(SyntaxKind::Number, "1.")]);
+        assert_eq!(kinds_and_text("1.e-2"), [(SyntaxKind::Number, "1.e-2")]);
+        assert_eq!(
+            kinds_and_text("SELECT -.5, (.5)"),
+            [
+                (SyntaxKind::BareWord, "SELECT"),
+                (SyntaxKind::Minus, "-"),
+                (SyntaxKind::Number, ".5"),
+                (SyntaxKind::Comma, ","),
+                (SyntaxKind::OpeningRoundBracket, "("),
+                (SyntaxKind::Number, ".5"),
+                (SyntaxKind::ClosingRoundBracket, ")"),
+            ]
+        );
+
+        // After an identifier, number or closing bracket, `.` is element access.
+        assert_eq!(
+            kinds_and_text("a.5"),
+            [
+                (SyntaxKind::BareWord, "a"),
+                (SyntaxKind::Dot, "."),
+                (SyntaxKind::Number, "5"),
+            ]
+        );
+        assert_eq!(
+            kinds_and_text("t.1.2"),
+            [
+                (SyntaxKind::BareWord, "t"),
+                (SyntaxKind::Dot, "."),
+                (SyntaxKind::Number, "1"),
+                (SyntaxKind::Dot, "."),
+                (SyntaxKind::Number, "2"),
+            ]
+        );
+        assert_eq!(
+            kinds_and_text("(x).1 /* c */ .2"),
+            [
+                (SyntaxKind::OpeningRoundBracket, "("),
+                (SyntaxKind::BareWord, "x"),
+                (SyntaxKind::ClosingRoundBracket, ")"),
+                (SyntaxKind::Dot, "."),
+                (SyntaxKind::Number, "1"),
+                (SyntaxKind::Dot, "."),
+                (SyntaxKind::Number, "2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_tuple_access_vs_decimal() {
+        let kinds_and_text = |sql: &'static str| -> Vec<(SyntaxKind, &'static str)> {
+            tokenize(sql).iter().map(|t| (t.kind, t.text(sql))).collect()
+        };
+
+        assert_eq!(
+            kinds_and_text("x.1"),
+            [(SyntaxKind::BareWord, "x"), (SyntaxKind::Dot, "."), (SyntaxKind::Number, "1")]
+        );
+        assert_eq!(kinds_and_text("1.5"), [(SyntaxKind::Number, "1.5")]);
+        assert_eq!(
+            kinds_and_text("x.1.2"),
+            [
+                (SyntaxKind::BareWord, "x"),
+                (SyntaxKind::Dot, "."),
+                (SyntaxKind::Number, "1"),
+                (SyntaxKind::Dot, "."),
+                (SyntaxKind::Number, "2"),
+            ]
+        );
+        assert_eq!(kinds_and_text("7"), [(SyntaxKind::Number, "7")]);
+        assert_eq!(
+            kinds_and_text("1.5 + x. 2"),
+            [
+                (SyntaxKind::Number, "1.5"),
+                (SyntaxKind::Plus, "+"),
+                (SyntaxKind::BareWord, "x"),
+                (SyntaxKind::Dot, "."),
+                (SyntaxKind::Number, "2"),
+            ]
+        );
+        // Multibyte characters right before the access.
+        assert_eq!(
+            kinds_and_text("`имя`.1.5"),
+            [
+                (SyntaxKind::QuotedIdentifier, "`имя`"),
+                (SyntaxKind::Dot, "."),
+                (SyntaxKind::Number, "1"),
+                (SyntaxKind::Dot, "."),
+                (SyntaxKind::Number, "5"),
+            ]
+        );
     }
 
     #[test]
@@ -794,6 +1022,15 @@ mod tests {
         assert_eq!(not_equals_token.text(sql), "!=");
     }
 
+    #[test]
+    fn test_tokenize_arrows() {
+        let sql = "x -> x, a ->> 'k'";
+        let tokens = tokenize(sql);
+
+        assert!(tokens[1].same_as(sql, SyntaxKind::Arrow, "->"));
+        assert!(tokens[5].same_as(sql, SyntaxKind::JsonExtractArrow, "->>"));
+    }
+
     #[test]
     fn test_tokenize_errors() {
         // Unterminated string
@@ -854,13 +1091,52 @@ mod tests {
             .find(|t| t.kind == SyntaxKind::VerticalDelimiter)
             .unwrap();
         assert_eq!(vdelim_token.text(sql), "\\G");
+    }
 
-        // Here-doc (if your implementation supports it)
-        // let sql = "SELECT <<<EOF\nsome text\nEOF";
-        // let tokens = tokenize(sql);
-        //
-        // let heredoc_token = tokens.iter().find(|t| t.kind == TokenType::HereDoc).unwrap();
-        // assert!(heredoc_token.value.starts_with("<<<EOF"));
+    #[test]
+    fn test_heredoc() {
+        let sql = "SELECT $$it's '$' raw$$, $tag$ a $$ b $tag$, $$open";
+        let tokens = tokenize(sql);
+
+        let heredocs: Vec<&str> = tokens
+            .iter()
+            .filter(|t| matches!(t.kind, SyntaxKind::HereDoc | SyntaxKind::ErrorHereDocIsNotClosed))
+            .map(|t| t.text(sql))
+            .collect();
+        assert_eq!(heredocs, ["$$it's '$' raw$$", "$tag$ a $$ b $tag$", "$$open"]);
+        assert_eq!(tokens.last().unwrap().kind, SyntaxKind::ErrorHereDocIsNotClosed);
+
+        // A `$` that opens no delimiter stays an operator.
+        assert_eq!(tokenize("$x")[0].kind, SyntaxKind::DollarSign);
+        // Nor does a tag that starts with a digit.
+        assert_eq!(tokenize("$1abc$ x $1abc$")[0].kind, SyntaxKind::DollarSign);
+    }
+
+    #[test]
+    fn test_heredoc_tags_must_match() {
+        let sql = "$q$it's \"quoted\" $a$ and $$ inside$q$";
+        let tokens = tokenize(sql);
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].kind, SyntaxKind::HereDoc);
+        assert_eq!(tokens[0].text(sql), sql);
+
+        // A different tag, or a prefix of the tag, doesn't close it.
+        let tokens = tokenize("$a$ body $b$ $ab$ $a");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].kind, SyntaxKind::ErrorHereDocIsNotClosed);
+    }
+
+    #[test]
+    fn test_keyword_barewords() {
+        let sql = "SELECT my_col, `select` FROM t";
+        let tokens = tokenize(sql);
+        assert!(tokens[0].same_as(sql, SyntaxKind::BareWord, "SELECT"));
+        assert!(tokens[0].is_keyword(sql));
+        assert!(tokens[1].same_as(sql, SyntaxKind::BareWord, "my_col"));
+        assert!(!tokens[1].is_keyword(sql));
+        // Quoted identifiers are never keywords.
+        assert!(!tokens[3].is_keyword(sql));
+        assert!(tokens[4].is_keyword(sql));
     }
 
     #[test]
@@ -894,4 +1170,175 @@ mod tests {
         assert_eq!(string_literals[0].text(sql), "'it\\'s a string'");
         assert_eq!(string_literals[1].text(sql), "'it''s another string'");
     }
+
+    #[test]
+    fn test_tokenize_checked_separates_errors() {
+        let sql = "SELECT a | b, 'open";
+        let (tokens, errors) = tokenize_checked(sql);
+
+        let texts: Vec<&str> = tokens.iter().map(|t| t.text(sql)).collect();
+        assert_eq!(texts, ["SELECT", "a", "b", ","]);
+        let errors: Vec<(&str, &str)> = errors
+            .iter()
+            .map(|e| (&sql[e.start as usize..e.end as usize], e.message()))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                ("|", "Unexpected `|`, did you mean `||`?"),
+                ("'open", "String literal is not closed"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_checked_clean_input() {
+        let (tokens, errors) = tokenize_checked("SELECT 1 || 'x'");
+        assert_eq!(tokens.len(), 4);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_each_stops_on_break() {
+        let sql = "SELECT a, b FROM t WHERE c";
+        let mut seen = Vec::new();
+        Tokenizer::new(sql).tokenize_each(|token| {
+            seen.push(token.text(sql).to_string());
+            if token.text(sql).eq_ignore_ascii_case("FROM") {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(seen, ["SELECT", " ", "a", ",", " ", "b", " ", "FROM"]);
+    }
+
+    #[test]
+    fn test_tokenize_multi_line_string() {
+        let sql = "SELECT 'line one\nline two' AS s\nFROM t";
+        let tokens = tokenize(sql);
+        assert!(tokens[1].same_as(sql, SyntaxKind::StringToken, "'line one\nline two'"));
+        assert_eq!((tokens[1].start, tokens[1].end), (7, 26));
+        assert!(tokens[2].same_as(sql, SyntaxKind::BareWord, "AS"));
+        assert!(tokens[4].same_as(sql, SyntaxKind::BareWord, "FROM"));
+        assert_eq!(tokens[4].start, 32);
+    }
+
+    #[test]
+    fn test_tokenize_every_operator() {
+        for &(text, kind) in OPERATORS {
+            let tokens = tokenize(text);
+            assert_eq!(tokens.len(), 1, "{text:?} split into {tokens:?}");
+            assert!(tokens[0].same_as(text, kind, text), "{text:?} gave {:?}", tokens[0].kind);
+        }
+    }
+
+    #[test]
+    fn test_tokenize_equals_variants() {
+        let kinds = |sql: &str| -> Vec<SyntaxKind> {
+            tokenize(sql).iter().map(|t| t.kind).collect()
+        };
+        assert_eq!(kinds("<=>"), [SyntaxKind::Spaceship]);
+        assert_eq!(kinds("="), [SyntaxKind::Equals]);
+        assert_eq!(kinds("=="), [SyntaxKind::Equals]);
+        assert_eq!(kinds("=>"), [SyntaxKind::Equals, SyntaxKind::Greater]);
+        assert_eq!(kinds("=><"), [SyntaxKind::Equals, SyntaxKind::Greater, SyntaxKind::Less]);
+        assert_eq!(kinds("->"), [SyntaxKind::Arrow]);
+    }
+
+    #[test]
+    fn test_tokenize_operators_longest_match() {
+        let sql = "a<=>b<=c<>d<e->>f->g-h::i:j";
+        let kinds: Vec<SyntaxKind> = tokenize(sql)
+            .iter()
+            .map(|t| t.kind)
+            .filter(|k| *k != SyntaxKind::BareWord)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                SyntaxKind::Spaceship,
+                SyntaxKind::LessOrEquals,
+                SyntaxKind::NotEquals,
+                SyntaxKind::Less,
+                SyntaxKind::JsonExtractArrow,
+                SyntaxKind::Arrow,
+                SyntaxKind::Minus,
+                SyntaxKind::DoubleColon,
+                SyntaxKind::Colon,
+            ]
+        );
+        let tokens = tokenize("\\x ~");
+        assert_eq!(tokens[0].kind, SyntaxKind::ErrorToken);
+        assert_eq!(tokens[0].text("\\x ~"), "\\");
+        assert_eq!(tokens[2].kind, SyntaxKind::ErrorToken);
+    }
+
+    #[test]
+    fn split_statements_at_top_level_semicolons() {
+        let sql = "SELECT 1; SELECT 2;\n\nSELECT 3";
+        let statements: Vec<&str> = split_statements(sql)
+            .into_iter()
+            .map(|(start, end)| &sql[start..end])
+            .collect();
+        assert_eq!(statements, ["SELECT 1", "SELECT 2", "SELECT 3"]);
+    }
+
+    #[test]
+    fn split_statements_ignores_quoted_and_commented_semicolons() {
+        let sql = "SELECT ';' AS `a;b`; -- one; two\nSELECT $$x;y$$ /* ; */; ;;SELECT 3\\G";
+        let statements: Vec<&str> = split_statements(sql)
+            .into_iter()
+            .map(|(start, end)| &sql[start..end])
+            .collect();
+        assert_eq!(
+            statements,
+            ["SELECT ';' AS `a;b`", "SELECT $$x;y$$", "SELECT 3"]
+        );
+    }
+
+    #[test]
+    fn for_each_token_matches_collected_tokens() {
+        let sql = "SELECT a, 'x;y' -- note\n FROM t /* c */ WHERE b >= .5 AND c = $$h$$\\G";
+        let mut streamed = Vec::new();
+        for_each_token(sql, |token| {
+            streamed.push((token.kind, token.start, token.end));
+            ControlFlow::Continue(())
+        });
+        let collected: Vec<_> = tokenize_with_whitespace(sql)
+            .into_iter()
+            .filter(|token| token.kind != SyntaxKind::EndOfStream)
+            .map(|token| (token.kind, token.start, token.end))
+            .collect();
+        assert_eq!(streamed, collected);
+    }
+
+    #[test]
+    fn max_query_size_boundary() {
+        let sql = "SELECT 1"; // 8 bytes
+        let kinds = |limit| -> Vec<SyntaxKind> {
+            tokenize_with_max_query_size(sql, limit).iter().map(|t| t.kind).collect()
+        };
+        assert_eq!(
+            kinds(Some(8)),
+            [SyntaxKind::BareWord, SyntaxKind::Whitespace, SyntaxKind::Number]
+        );
+        assert_eq!(
+            kinds(Some(7)),
+            [SyntaxKind::ErrorMaxQuerySizeExceeded, SyntaxKind::EndOfStream]
+        );
+        assert_eq!(kinds(None), kinds(Some(8)));
+    }
+
+    #[test]
+    fn max_query_size_default_and_override() {
+        let sql = format!("SELECT '{}'", "x".repeat(DEFAULT_MAX_QUERY_SIZE));
+        assert_eq!(tokenize_with_whitespace(&sql)[0].kind, SyntaxKind::ErrorMaxQuerySizeExceeded);
+
+        let mut tokenizer = Tokenizer::new(&sql);
+        tokenizer.set_max_query_size(None);
+        let tokens = tokenizer.tokenize();
+        assert_eq!(tokens[2].kind, SyntaxKind::StringToken);
+        assert_eq!(tokens[2].end as usize, sql.len());
+    }
 }
@@ -9,17 +9,23 @@ pub mod metadata;
 #[cfg(any(feature = "lsp", feature = "codegen"))]
 pub mod connection;
 
-#[cfg(feature = "lsp")]
 pub mod analysis;
 
 #[cfg(feature = "lsp")]
 pub mod lsp;
 
 pub use diagnostics::{enrich_diagnostics, Diagnostic, RelatedSpan, Severity, Suggestion};
-pub use formatter::{format, FormatConfig};
+pub use formatter::{format, format_minimal, FormatConfig};
 pub use lexer::token::Token;
-pub use parser::diagnostic::{Parse, SyntaxError};
-pub use parser::parse;
+pub use lexer::tokenizer::{
+    for_each_token, split_statements, tokenize_checked, LexError, DEFAULT_MAX_QUERY_SIZE,
+};
+pub use parser::diagnostic::{Parse, StatementParse, SyntaxError};
+pub use parser::keyword::{non_reserved_keywords, reserved_keywords};
+pub use parser::{
+    parse, parse_column_list, parse_statements, parse_where_clause, parse_with_options,
+    parse_with_tokens, ParseOptions,
+};
 pub use parser::syntax_kind::SyntaxKind;
 pub use parser::syntax_tree::{SyntaxChild, SyntaxTree};
 
@@ -43,6 +49,27 @@ mod wasm {
         buf
     }
 
+    /// Parse SQL and return just the CST as JSON, in the same shape as the
+    /// `tree` field of `parse_sql`.
+    #[wasm_bindgen]
+    pub fn get_tree_json(sql: &str) -> String {
+        match serde_json::to_string(&parse(sql).tree) {
+            Ok(json) => json,
+            Err(e) => format!("{{\"error\":\"serialization failed: {}\"}}", e),
+        }
+    }
+
+    /// Tokenize SQL and return a JSON array of
+    /// `{ kind, start, end, line, column }`, whitespace and comments included.
+    /// Lines and columns are 1-based; columns count characters.
+    #[wasm_bindgen]
+    pub fn get_tokens(sql: &str) -> String {
+        match serde_json::to_string(&analysis::tokens::tokens_with_positions(sql)) {
+            Ok(json) => json,
+            Err(e) => format!("{{\"error\":\"serialization failed: {}\"}}", e),
+        }
+    }
+
     #[wasm_bindgen]
     pub fn format_sql(sql: &str) -> String {
         let result = parse(sql);
@@ -59,6 +86,67 @@ mod wasm {
         }
     }
 
+    /// Parse and analyze SQL in one call and return a JSON summary:
+    /// `{ ok: boolean, errors: Diagnostic[], warnings: Diagnostic[], tables: string[], dictionaries: string[], columns: string[], samples: Sample[] }`.
+    /// This is the main entry point for the web playground.
+    #[wasm_bindgen]
+    pub fn validate(sql: &str) -> String {
+        match serde_json::to_string(&analysis::validate::validate(sql)) {
+            Ok(json) => json,
+            Err(e) => format!("{{\"error\":\"serialization failed: {}\"}}", e),
+        }
+    }
+
+    /// Like `validate`, but drops diagnostics less severe than `min_severity`
+    /// (`"error"`, `"warning"` or `"hint"`).
+    #[wasm_bindgen]
+    pub fn validate_with_min_severity(sql: &str, min_severity: &str) -> String {
+        let Some(min_severity) = Severity::from_name(min_severity) else {
+            // The name is user input, so let serde_json escape it.
+            let error = format!("unknown severity: {min_severity}");
+            return serde_json::json!({ "error": error }).to_string();
+        };
+        let validation = analysis::validate::validate_with_min_severity(sql, min_severity);
+        match serde_json::to_string(&validation) {
+            Ok(json) => json,
+            Err(e) => format!("{{\"error\":\"serialization failed: {}\"}}", e),
+        }
+    }
+
+    /// Return the tables and selected columns of each statement as JSON:
+    /// `{ statements: [{ kind, span, tables: string[], columns: string[], errors }] }`,
+    /// where `errors` holds `{ message, span }` for unknown column qualifiers.
+    #[wasm_bindgen]
+    pub fn analyze_query(sql: &str) -> String {
+        let result = parse(sql);
+        match serde_json::to_string(&analysis::references::analyze(&result.tree, sql)) {
+            Ok(json) => json,
+            Err(e) => format!("{{\"error\":\"serialization failed: {}\"}}", e),
+        }
+    }
+
+    /// Completion candidates at byte offset `cursor` as a JSON array of
+    /// `{ text, kind }`, where `kind` is `keyword` or `identifier`.
+    #[wasm_bindgen]
+    pub fn get_completions(sql: &str, cursor: usize) -> String {
+        match serde_json::to_string(&analysis::completions::completions(sql, cursor)) {
+            Ok(json) => json,
+            Err(e) => format!("{{\"error\":\"serialization failed: {}\"}}", e),
+        }
+    }
+
+    /// Categorize the tokens of `sql` for syntax highlighting and return a
+    /// JSON array of `{ start, end, category }`, where `category` is one of
+    /// `keyword`, `identifier`, `string`, `number`, `comment`, `operator` or
+    /// `error`. Whitespace is omitted.
+    #[wasm_bindgen]
+    pub fn highlight(sql: &str) -> String {
+        match serde_json::to_string(&analysis::highlight::highlight(sql)) {
+            Ok(json) => json,
+            Err(e) => format!("{{\"error\":\"serialization failed: {}\"}}", e),
+        }
+    }
+
     /// Parse SQL and return the full CST as JSON.
     ///
     /// Returns a JSON object: `{ tree: SyntaxTree, errors: SyntaxError[], source: string }`
@@ -66,7 +154,19 @@ mod wasm {
     /// and SyntaxChild is either `{ Token: { kind, start, end } }` or `{ Tree: { ... } }`.
     #[wasm_bindgen]
     pub fn parse_sql(sql: &str) -> String {
-        let result = parse(sql);
+        parse_result_json(&parse(sql))
+    }
+
+    /// Like `parse_sql`, but stop after `max_tokens` tokens, whitespace and
+    /// comments included, so the playground can bail out on pathological
+    /// input. A stopped parse ends with a "Token budget exceeded" error.
+    #[wasm_bindgen]
+    pub fn parse_sql_with_budget(sql: &str, max_tokens: usize) -> String {
+        let options = ParseOptions { max_tokens: Some(max_tokens), ..Default::default() };
+        parse_result_json(&parse_with_options(sql, &options))
+    }
+
+    fn parse_result_json(result: &Parse) -> String {
         match serde_json::to_string(&ParseResult {
             tree: &result.tree,
             errors: &result.errors,
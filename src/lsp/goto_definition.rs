@@ -1,6 +1,7 @@
 use tower_lsp::lsp_types::*;
 
-use crate::analysis::scope::{build_scope, find_enclosing_statement};
+use crate::analysis::scope::{build_scope_at, normalize_identifier};
+use crate::lexer::token::Token;
 use crate::parser::diagnostic::Parse;
 use crate::parser::syntax_kind::SyntaxKind;
 use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
@@ -15,18 +16,18 @@ pub fn handle_goto_definition(
 ) -> Option<GotoDefinitionResponse> {
     let offset = line_index.offset(position);
 
-    // Find the token at cursor
-    let (text, _parent, _start, _end) = find_token_at(&parse.tree, &parse.source, offset)?;
+    // Find the token at cursor and its lookup key
+    let token = find_token_at(&parse.tree, offset)?;
+    let name = normalize_identifier(token, &parse.source);
 
-    // Find the enclosing statement and build scope
-    let stmt = find_enclosing_statement(&parse.tree, offset)?;
-    let scope = build_scope(stmt, &parse.source);
+    // Build the scope visible at the cursor
+    let scope = build_scope_at(&parse.tree, &parse.source, offset)?;
 
     // Check CTEs
     if let Some(cte) = scope
         .ctes
         .iter()
-        .find(|c| c.name.eq_ignore_ascii_case(text))
+        .find(|c| c.name == name)
     {
         return Some(GotoDefinitionResponse::Scalar(Location {
             uri: uri.clone(),
@@ -38,7 +39,7 @@ pub fn handle_goto_definition(
     if let Some(alias) = scope
         .table_aliases
         .iter()
-        .find(|a| a.name.eq_ignore_ascii_case(text))
+        .find(|a| a.name == name)
     {
         return Some(GotoDefinitionResponse::Scalar(Location {
             uri: uri.clone(),
@@ -50,7 +51,7 @@ pub fn handle_goto_definition(
     if let Some(alias) = scope
         .column_aliases
         .iter()
-        .find(|a| a.name.eq_ignore_ascii_case(text))
+        .find(|a| a.name == name)
     {
         return Some(GotoDefinitionResponse::Scalar(Location {
             uri: uri.clone(),
@@ -61,11 +62,7 @@ pub fn handle_goto_definition(
     None
 }
 
-fn find_token_at<'a>(
-    tree: &'a SyntaxTree,
-    source: &'a str,
-    offset: u32,
-) -> Option<(&'a str, SyntaxKind, u32, u32)> {
+fn find_token_at(tree: &SyntaxTree, offset: u32) -> Option<&Token> {
     for child in &tree.children {
         match child {
             SyntaxChild::Token(token) => {
@@ -73,7 +70,7 @@ fn find_token_at<'a>(
                     continue;
                 }
                 if token.start <= offset && offset <= token.end {
-                    return Some((token.text(source), token.kind, token.start, token.end));
+                    return Some(token);
                 }
             }
             SyntaxChild::Tree(subtree) => {
@@ -81,7 +78,7 @@ fn find_token_at<'a>(
                     continue;
                 }
                 if subtree.start <= offset && offset <= subtree.end {
-                    if let Some(result) = find_token_at(subtree, source, offset) {
+                    if let Some(result) = find_token_at(subtree, offset) {
                         return Some(result);
                     }
                 }
@@ -118,4 +118,22 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn multi_line_string_literal() {
+        // The string starts on line 0 and ends on line 1; tokens after it
+        // are placed by their own offsets, not by the string's start line.
+        let text = "SELECT 'one\ntwo', x\nFROM t";
+        let idx = LineIndex::new(text);
+        let tokens = crate::lexer::tokenizer::tokenize(text);
+        let string = &tokens[1];
+        assert_eq!(string.kind, crate::parser::syntax_kind::SyntaxKind::StringToken);
+        assert_eq!(idx.position(string.start), tower_lsp::lsp_types::Position { line: 0, character: 7 });
+        assert_eq!(idx.position(string.end), tower_lsp::lsp_types::Position { line: 1, character: 4 });
+        let x = &tokens[3];
+        assert_eq!(x.text(text), "x");
+        assert_eq!(idx.position(x.start), tower_lsp::lsp_types::Position { line: 1, character: 6 });
+        let from = &tokens[4];
+        assert_eq!(idx.position(from.start), tower_lsp::lsp_types::Position { line: 2, character: 0 });
+    }
 }
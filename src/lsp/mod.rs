@@ -16,12 +16,11 @@ use tower_lsp::jsonrpc::Result;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
 use crate::connection::client::ConnectionConfig;
-use crate::diagnostics::{self, Severity as OurSeverity};
+use crate::diagnostics;
 use crate::formatter::{self, FormatConfig};
 use crate::metadata::cache::{MetadataCache, SharedMetadata};
 use crate::parser;
 use crate::parser::diagnostic::Parse;
-use crate::parser::syntax_kind::SyntaxKind;
 use crate::parser::syntax_tree::SyntaxChild;
 
 /// Maximum number of simultaneously open documents.
@@ -83,40 +82,7 @@ impl Backend {
 
         let mut diags: Vec<Diagnostic> = enriched
             .iter()
-            .map(|d| {
-                let range = line_index.range(d.range.0 as u32, d.range.1 as u32);
-                let severity = Some(match d.severity {
-                    OurSeverity::Error => DiagnosticSeverity::ERROR,
-                    OurSeverity::Warning => DiagnosticSeverity::WARNING,
-                    OurSeverity::Hint => DiagnosticSeverity::HINT,
-                });
-                let related_information = if d.related.is_empty() {
-                    None
-                } else {
-                    Some(
-                        d.related
-                            .iter()
-                            .map(|r| DiagnosticRelatedInformation {
-                                location: Location {
-                                    uri: uri.clone(),
-                                    range: line_index
-                                        .range(r.range.0 as u32, r.range.1 as u32),
-                                },
-                                message: r.message.clone(),
-                            })
-                            .collect(),
-                    )
-                };
-                Diagnostic {
-                    range,
-                    severity,
-                    code: d.code.map(|c| NumberOrString::String(c.to_owned())),
-                    source: Some("clickhouse-analyzer".to_owned()),
-                    message: d.message.clone(),
-                    related_information,
-                    ..Default::default()
-                }
-            })
+            .map(|d| d.to_lsp(line_index, &uri))
             .collect();
 
         // Server-side validation via EXPLAIN PLAN (Tier 2+3)
@@ -123,7 +123,8 @@ fn classify_token(
         | SyntaxKind::Spaceship
         | SyntaxKind::Concatenation
         | SyntaxKind::DoubleColon
-        | SyntaxKind::Arrow => Some(TT_OPERATOR),
+        | SyntaxKind::Arrow
+        | SyntaxKind::JsonExtractArrow => Some(TT_OPERATOR),
 
         // Quoted identifiers — context-dependent
         SyntaxKind::QuotedIdentifier => classify_identifier_by_parent(parent),
@@ -156,10 +157,8 @@ fn classify_bareword(text: &str, parent: SyntaxKind) -> Option<u32> {
     if matches!(
         parent,
         SyntaxKind::FunctionCall | SyntaxKind::AggregateFunction | SyntaxKind::TableFunction
-    ) {
-        if !is_keyword(text) {
-            return Some(TT_FUNCTION);
-        }
+    ) && !is_keyword(text) {
+        return Some(TT_FUNCTION);
     }
 
     // Table/database identifiers
@@ -173,31 +172,28 @@ fn classify_bareword(text: &str, parent: SyntaxKind) -> Option<u32> {
             | SyntaxKind::MaterializedViewDefinition
             | SyntaxKind::DictionaryDefinition
             | SyntaxKind::TableAlias
-    ) {
-        if !is_keyword(text) {
-            return Some(TT_PROPERTY);
-        }
+    ) && !is_keyword(text) {
+        return Some(TT_PROPERTY);
     }
 
     // Column aliases
-    if matches!(parent, SyntaxKind::ColumnAlias) {
-        if !is_keyword(text) {
-            return Some(TT_VARIABLE);
-        }
+    if matches!(parent, SyntaxKind::ColumnAlias) && !is_keyword(text) {
+        return Some(TT_VARIABLE);
     }
 
     // Column references
     if matches!(
         parent,
         SyntaxKind::ColumnReference | SyntaxKind::QualifiedName | SyntaxKind::Identifier
-    ) {
-        if !is_keyword(text) {
-            return Some(TT_VARIABLE);
-        }
+    ) && !is_keyword(text) {
+        return Some(TT_VARIABLE);
     }
 
     // Query parameters
-    if matches!(parent, SyntaxKind::QueryParameterExpression) {
+    if matches!(
+        parent,
+        SyntaxKind::QueryParameterExpression | SyntaxKind::PositionalParameter
+    ) {
         return Some(TT_PARAMETER);
     }
 
@@ -242,7 +238,7 @@ fn is_keyword(text: &str) -> bool {
     if text.len() < 2 {
         return false;
     }
-    KEYWORDS.contains(&text.to_ascii_uppercase().as_str())
+    KEYWORDS.contains(text.to_ascii_uppercase().as_str())
 }
 
 /// All known ClickHouse SQL keywords. This set is used purely for syntax highlighting
@@ -1,3 +1,5 @@
+use super::syntax_kind::SyntaxKind;
+
 /// A parse error: a message and the byte range in the source where it occurred.
 ///
 /// This is the parser-level error type. It intentionally stores only byte offsets —
@@ -8,6 +10,10 @@
 pub struct SyntaxError {
     pub message: String,
     pub range: (usize, usize),
+    /// The token kind the parser required, for errors from `expect`.
+    pub expected: Option<SyntaxKind>,
+    /// The kind of the token at `range`, or `None` at end of input.
+    pub found: Option<SyntaxKind>,
 }
 
 /// Result of parsing: the syntax tree plus any errors collected.
@@ -18,6 +24,19 @@ pub struct Parse {
     pub source: String,
 }
 
+/// One top-level statement of a multi-statement script, see
+/// [`parse_statements`](crate::parser::parse_statements).
+#[derive(Clone)]
+pub struct StatementParse {
+    pub tree: super::syntax_tree::SyntaxTree,
+    /// Byte range of the statement, excluding trailing trivia and the `;`.
+    pub range: (usize, usize),
+    /// Errors reported inside this statement.
+    pub errors: Vec<SyntaxError>,
+    /// True if the statement ends with `\G` (vertical output) instead of `;`.
+    pub vertical: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parser::parse;
@@ -51,6 +70,40 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn missing_comma_between_arguments() {
+        check_errors("SELECT f(a b), g(1 2, 3)", expect![[r#"
+            11..12: Missing comma between arguments
+            19..20: Missing comma between arguments
+        "#]]);
+    }
+
+    #[test]
+    fn output_clauses_out_of_order() {
+        check_errors("SELECT a FROM t FORMAT CSV INTO OUTFILE 'f'", expect![[r#"
+            27..31: INTO OUTFILE must come before FORMAT
+        "#]]);
+        check_errors("SELECT a FROM t FORMAT CSV SETTINGS x = 1 FORMAT TSV", expect![[r#"
+            42..48: Duplicate FORMAT clause
+        "#]]);
+        check_errors("SELECT a FROM t SETTINGS x = 1 SETTINGS y = 2", expect![[r#"
+            31..39: Duplicate SETTINGS clause
+        "#]]);
+    }
+
+    #[test]
+    fn duplicate_and_misplaced_body_clauses() {
+        check_errors("SELECT a FROM t WHERE x WHERE y ORDER BY a", expect![[r#"
+            24..29: Duplicate WHERE clause
+        "#]]);
+        check_errors("SELECT a FROM t ORDER BY a WHERE y LIMIT 1", expect![[r#"
+            27..32: WHERE must come before ORDER BY
+        "#]]);
+        check_errors("SELECT a FROM t ORDER BY a LIMIT 1 ORDER BY b", expect![[r#"
+            35..40: Duplicate ORDER BY clause
+        "#]]);
+    }
+
     #[test]
     fn bad_interval_unit() {
         check_errors("SELECT INTERVAL 5 POTATO", expect![[r#"
@@ -79,4 +132,16 @@ mod tests {
             14..14: Expected table reference
         "#]]);
     }
+
+    #[test]
+    fn expected_and_found_kinds() {
+        use crate::parser::syntax_kind::SyntaxKind;
+
+        let result = parse("SELECT (1 FROM");
+        let kinds: Vec<_> = result.errors.iter().map(|e| (e.expected, e.found)).collect();
+        assert_eq!(
+            kinds,
+            [(Some(SyntaxKind::ClosingRoundBracket), Some(SyntaxKind::BareWord)), (None, None)]
+        );
+    }
 }
@@ -341,7 +341,7 @@ fn parse_drop_command(p: &mut Parser) {
         p.complete(m, SyntaxKind::AlterDropConstraint);
     } else if p.at_keyword(Keyword::Partition) || p.at_keyword(Keyword::Part) {
         p.advance(); // consume PARTITION or PART
-        parse_partition_expression(p);
+        common::parse_partition_expression(p);
         p.complete(m, SyntaxKind::AlterDropPartition);
     } else {
         p.advance_with_error("Expected COLUMN, INDEX, PROJECTION, CONSTRAINT, PARTITION, or PART after DROP");
@@ -443,7 +443,7 @@ fn parse_clear_command(p: &mut Parser) {
         if p.at_keyword(Keyword::In) {
             p.advance();
             p.expect_keyword(Keyword::Partition);
-            parse_partition_expression(p);
+            common::parse_partition_expression(p);
         }
 
         p.complete(m, SyntaxKind::AlterClearColumn);
@@ -462,7 +462,7 @@ fn parse_clear_command(p: &mut Parser) {
         if p.at_keyword(Keyword::In) {
             p.advance();
             p.expect_keyword(Keyword::Partition);
-            parse_partition_expression(p);
+            common::parse_partition_expression(p);
         }
 
         p.complete(m, SyntaxKind::AlterClearIndex);
@@ -519,7 +519,7 @@ fn parse_materialize_command(p: &mut Parser) {
         if p.at_keyword(Keyword::In) {
             p.advance();
             p.expect_keyword(Keyword::Partition);
-            parse_partition_expression(p);
+            common::parse_partition_expression(p);
         }
 
         p.complete(m, SyntaxKind::AlterMaterializeIndex);
@@ -538,7 +538,7 @@ fn parse_materialize_command(p: &mut Parser) {
         if p.at_keyword(Keyword::In) {
             p.advance();
             p.expect_keyword(Keyword::Partition);
-            parse_partition_expression(p);
+            common::parse_partition_expression(p);
         }
 
         p.complete(m, SyntaxKind::AlterMaterializeProjection);
@@ -549,7 +549,7 @@ fn parse_materialize_command(p: &mut Parser) {
         if p.at_keyword(Keyword::In) {
             p.advance();
             p.expect_keyword(Keyword::Partition);
-            parse_partition_expression(p);
+            common::parse_partition_expression(p);
         }
 
         p.complete(m, SyntaxKind::AlterMaterializeTtl);
@@ -571,16 +571,6 @@ fn parse_materialize_command(p: &mut Parser) {
 
 // ---- Partition commands ----
 
-fn parse_partition_expression(p: &mut Parser) {
-    let m = p.start();
-    // PARTITION ID 'string' — partition by ID string
-    if p.at_keyword(Keyword::Id) {
-        p.advance(); // consume ID
-    }
-    parse_expression(p);
-    p.complete(m, SyntaxKind::PartitionExpression);
-}
-
 fn parse_detach_partition(p: &mut Parser) {
     let m = p.start();
     p.expect_keyword(Keyword::Detach);
@@ -591,7 +581,7 @@ fn parse_detach_partition(p: &mut Parser) {
         p.recover_with_error("Expected PARTITION or PART after DETACH");
     }
 
-    parse_partition_expression(p);
+    common::parse_partition_expression(p);
 
     p.complete(m, SyntaxKind::AlterDetachPartition);
 }
@@ -606,7 +596,7 @@ fn parse_attach_partition(p: &mut Parser) {
         p.recover_with_error("Expected PARTITION or PART after ATTACH");
     }
 
-    parse_partition_expression(p);
+    common::parse_partition_expression(p);
 
     // Optional FROM [db.]table
     if p.at_keyword(Keyword::From) {
@@ -624,17 +614,18 @@ fn parse_freeze_partition(p: &mut Parser) {
     // PARTITION is optional for FREEZE
     if p.at_keyword(Keyword::Partition) {
         p.advance();
-        parse_partition_expression(p);
+        common::parse_partition_expression(p);
     }
 
     p.complete(m, SyntaxKind::AlterFreezePartition);
 }
 
-// ---- DELETE WHERE ----
+// ---- DELETE [IN PARTITION p] WHERE ----
 
 fn parse_delete_where(p: &mut Parser) {
     let m = p.start();
     p.expect_keyword(Keyword::Delete);
+    common::parse_optional_in_partition(p);
 
     if p.at_keyword(Keyword::Where) {
         p.expect_keyword(Keyword::Where);
@@ -646,7 +637,7 @@ fn parse_delete_where(p: &mut Parser) {
     p.complete(m, SyntaxKind::AlterDeleteWhere);
 }
 
-// ---- UPDATE ... WHERE ----
+// ---- UPDATE ... [IN PARTITION p] WHERE ----
 
 fn parse_update_where(p: &mut Parser) {
     let m = p.start();
@@ -654,6 +645,7 @@ fn parse_update_where(p: &mut Parser) {
 
     // Parse assignment list: col = expr [, col = expr ...]
     parse_assignment_list(p);
+    common::parse_optional_in_partition(p);
 
     if p.at_keyword(Keyword::Where) {
         p.expect_keyword(Keyword::Where);
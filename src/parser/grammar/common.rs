@@ -1,4 +1,5 @@
 use crate::parser::grammar::expressions::parse_expression;
+use crate::parser::grammar::select::parse_table_reference;
 use crate::parser::grammar::types::parse_column_type;
 use crate::parser::keyword::Keyword;
 use crate::parser::parser::Parser;
@@ -60,6 +61,33 @@ pub fn parse_on_cluster(p: &mut Parser) {
     }
 }
 
+/// Parse a partition expression (`tuple(...)`, `'2024-01'`, `ID 'id'`),
+/// wrapping in PartitionExpression.
+pub fn parse_partition_expression(p: &mut Parser) {
+    let m = p.start();
+    // PARTITION ID 'string' — partition by ID string
+    if p.at_keyword(Keyword::Id) {
+        p.advance(); // consume ID
+    }
+    parse_expression(p);
+    p.complete(m, SyntaxKind::PartitionExpression);
+}
+
+/// True at `IN PARTITION`, which limits a mutation to one partition.
+pub fn at_in_partition(p: &mut Parser) -> bool {
+    p.at_keyword(Keyword::In) && p.nth_keyword(1, Keyword::Partition)
+}
+
+/// Parse optional `IN PARTITION expr` of DELETE FROM and ALTER ... DELETE /
+/// UPDATE.
+pub fn parse_optional_in_partition(p: &mut Parser) {
+    if at_in_partition(p) {
+        p.advance(); // IN
+        p.expect_keyword(Keyword::Partition);
+        parse_partition_expression(p);
+    }
+}
+
 /// Parse [db.]name, wrapping in TableIdentifier.
 /// Each name slot can be a bare identifier, quoted identifier, or query parameter.
 pub fn parse_table_identifier(p: &mut Parser) {
@@ -89,13 +117,85 @@ fn parse_identifier_or_param(p: &mut Parser) {
     }
 }
 
+/// Parse a standalone table function, `func_name(args)`, into a TableFunction
+/// node: the target of `INSERT INTO FUNCTION` or `CREATE TABLE ... AS`.
+pub fn parse_table_function(p: &mut Parser) {
+    let m = p.start();
+
+    if p.at_identifier() {
+        p.advance();
+    } else {
+        p.advance_with_error("Expected function name");
+    }
+
+    if p.at(SyntaxKind::OpeningRoundBracket) {
+        parse_table_function_arguments(p);
+    }
+
+    p.complete(m, SyntaxKind::TableFunction);
+}
+
+/// Parse a table function's parenthesized argument list. The list may end
+/// with a SETTINGS clause, as in `mysql('host', db, tbl, 'user', '', SETTINGS
+/// connect_timeout = 100)`, and an argument may be followed by `ELSE` and a
+/// fallback table, as in `viewIfPermitted(SELECT ... ELSE null('a UInt8'))`.
+pub fn parse_table_function_arguments(p: &mut Parser) {
+    p.expect(SyntaxKind::OpeningRoundBracket);
+    let mut first = true;
+    while !p.end_of_statement() {
+        if at_settings_argument(p) {
+            parse_optional_settings_clause(p);
+            break;
+        }
+        if !first {
+            p.expect(SyntaxKind::Comma);
+            if at_settings_argument(p) {
+                parse_optional_settings_clause(p);
+                break;
+            }
+        }
+        first = false;
+        parse_table_function_argument(p);
+        if p.at_keyword(Keyword::Else) {
+            p.advance();
+            parse_table_reference(p);
+        }
+    }
+    p.expect(SyntaxKind::ClosingRoundBracket);
+}
+
+/// True at `SETTINGS name =`, a SETTINGS clause inside the argument list
+/// rather than an argument named `settings`.
+fn at_settings_argument(p: &mut Parser) -> bool {
+    p.at_keyword(Keyword::Settings)
+        && (p.nth(1) == SyntaxKind::BareWord || p.nth(1) == SyntaxKind::QuotedIdentifier)
+        && p.nth(2) == SyntaxKind::Equals
+}
+
+/// Parse one table function argument. A named argument, `name = value`, is
+/// parsed as a comparison with a ColumnReference on the left even when the
+/// name is a keyword, as in `s3(named_collection, format = CSV)`.
+pub fn parse_table_function_argument(p: &mut Parser) {
+    if !(p.at(SyntaxKind::BareWord) && p.nth(1) == SyntaxKind::Equals) {
+        parse_expression(p);
+        return;
+    }
+    let m = p.start();
+    let name = p.start();
+    p.advance();
+    p.complete(name, SyntaxKind::ColumnReference);
+    p.expect(SyntaxKind::Equals);
+    parse_expression(p);
+    p.complete(m, SyntaxKind::BinaryExpression);
+}
+
 // ---------------------------------------------------------------------------
 // Error recovery infrastructure
 // ---------------------------------------------------------------------------
 
 /// True if the current token exactly matches any keyword in the set.
 pub fn at_any_keyword(p: &mut Parser, keywords: &[Keyword]) -> bool {
-    keywords.iter().any(|kw| p.at_keyword(*kw))
+    p.at_any_keyword(keywords)
 }
 
 /// Skip unexpected tokens until we reach a recognized keyword, end of statement,
@@ -110,17 +210,30 @@ pub fn skip_to_keywords(p: &mut Parser, keywords: &[Keyword]) {
 // Setting parsing
 // ---------------------------------------------------------------------------
 
-/// Parse a single setting: `key = value`.
+/// Parse a single setting: `key = value`, or `key value` without the `=`.
 pub fn parse_setting_item(p: &mut Parser) {
     let m = p.start();
 
     if p.at_identifier() {
         p.advance();
+        // Dotted names, e.g. `s3.max_connections` or `custom_x.y`
+        while p.at(SyntaxKind::Dot) {
+            p.advance();
+            if p.at_identifier() {
+                p.advance();
+            } else {
+                p.recover_with_error("Expected name after dot");
+                break;
+            }
+        }
     } else {
         p.recover_with_error("Expected setting name");
     }
 
-    p.expect(SyntaxKind::Equals);
+    // `=` is optional (`SETTINGS max_threads 8`); without it a value must follow.
+    if p.at(SyntaxKind::Equals) || p.at(SyntaxKind::Comma) || p.end_of_statement() {
+        p.expect(SyntaxKind::Equals);
+    }
     parse_expression(p);
 
     p.complete(m, SyntaxKind::SettingItem);
@@ -156,3 +269,21 @@ pub fn parse_optional_settings_clause(p: &mut Parser) -> bool {
     p.complete(m, SyntaxKind::SettingsClause);
     true
 }
+
+/// Parse optional trailing `FORMAT format_name`, shared by every statement
+/// that returns a result set. Returns true if a FORMAT clause was parsed.
+pub fn parse_optional_format_clause(p: &mut Parser) -> bool {
+    if !p.at_keyword(Keyword::Format) {
+        return false;
+    }
+
+    let m = p.start();
+    p.expect_keyword(Keyword::Format);
+    if p.at_identifier() {
+        p.advance();
+    } else {
+        p.recover_with_error("Expected format name after FORMAT");
+    }
+    p.complete(m, SyntaxKind::FormatClause);
+    true
+}
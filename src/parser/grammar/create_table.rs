@@ -52,21 +52,15 @@ pub fn parse_create_statement(p: &mut Parser) {
         parse_create_role(p);
     } else if p.at_keyword(Keyword::Quota) {
         parse_create_quota(p);
-    } else if p.at_keyword(Keyword::Row) {
+    } else if p.at_keyword(Keyword::Row) || p.at_keyword(Keyword::Policy) {
         parse_create_row_policy(p);
-    } else if p.at_keyword(Keyword::Policy) {
-        parse_create_row_policy(p);
-    } else if p.at_keyword(Keyword::Settings) && !is_temporary {
-        parse_create_settings_profile(p);
-    } else if p.at_keyword(Keyword::Profile) {
+    } else if p.at_keyword(Keyword::Settings) && !is_temporary || p.at_keyword(Keyword::Profile) {
         parse_create_settings_profile(p);
+    } else if is_temporary {
+        // TEMPORARY only valid with TABLE
+        p.recover_with_error("Expected TABLE after TEMPORARY");
     } else {
-        if is_temporary {
-            // TEMPORARY only valid with TABLE
-            p.recover_with_error("Expected TABLE after TEMPORARY");
-        } else {
-            p.advance_with_error("Expected TABLE, DATABASE, VIEW, MATERIALIZED VIEW, FUNCTION, or DICTIONARY");
-        }
+        p.advance_with_error("Expected TABLE, DATABASE, VIEW, MATERIALIZED VIEW, FUNCTION, or DICTIONARY");
     }
 
     p.complete(m, SyntaxKind::CreateStatement);
@@ -459,7 +453,7 @@ fn consume_balanced_parens(p: &mut Parser) {
     p.expect(SyntaxKind::ClosingRoundBracket);
 }
 
-/// Parse AS clause: AS SELECT ... or AS [db.]table
+/// Parse AS clause: AS SELECT ..., AS [db.]table or AS table_function(...)
 fn parse_as_clause(p: &mut Parser) {
     let m = p.start();
     p.expect_keyword(Keyword::As);
@@ -471,10 +465,14 @@ fn parse_as_clause(p: &mut Parser) {
         p.advance(); // consume (
         parse_select_statement(p);
         p.expect(SyntaxKind::ClosingRoundBracket);
+    } else if p.at_identifier() && p.at_followed_by_paren() {
+        // Schema and data from a table function: AS s3('url', 'CSV')
+        common::parse_table_function(p);
     } else if p.at_identifier() {
+        // Schema copied from another table: AS [db.]table
         common::parse_table_identifier(p);
     } else {
-        p.recover_with_error("Expected SELECT or table name after AS");
+        p.recover_with_error("Expected SELECT, table or table function after AS");
     }
 
     p.complete(m, SyntaxKind::AsClause);
@@ -847,10 +845,8 @@ fn parse_settings_clause(p: &mut Parser) {
             break;
         }
 
-        if !first {
-            if !p.eat(SyntaxKind::Comma) {
-                break;
-            }
+        if !first && !p.eat(SyntaxKind::Comma) {
+            break;
         }
         first = false;
 
@@ -1004,6 +1000,7 @@ fn parse_create_settings_profile(p: &mut Parser) {
 
 #[cfg(test)]
 mod tests {
+    use crate::analysis::table_columns::{table_columns, table_comments};
     use crate::parser::parse;
 
     #[test]
@@ -1054,6 +1051,15 @@ mod tests {
         assert!(buf.contains("ColumnComment"));
     }
 
+    #[test]
+    fn test_create_table_table_comment() {
+        let result = parse("CREATE TABLE test (id UInt64 COMMENT 'primary key') ENGINE = MergeTree() ORDER BY id COMMENT 'users'");
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let columns = table_columns(&result.tree, &result.source);
+        assert_eq!(columns[0].comment.as_deref(), Some("primary key"));
+        assert_eq!(table_comments(&result.tree, &result.source), ["users"]);
+    }
+
     #[test]
     fn test_create_table_index() {
         let result = parse("CREATE TABLE test (id UInt64, INDEX idx id TYPE minmax GRANULARITY 3) ENGINE = MergeTree() ORDER BY id");
@@ -12,7 +12,7 @@ pub fn at_delete_statement(p: &mut Parser) -> bool {
 /// Parse a DELETE statement.
 ///
 /// ```text
-/// DELETE FROM [db.]table [ON CLUSTER cluster] WHERE expr
+/// DELETE FROM [db.]table [ON CLUSTER cluster] [IN PARTITION expr] WHERE expr
 /// ```
 ///
 /// ClickHouse requires a WHERE clause for lightweight deletes.
@@ -34,6 +34,9 @@ pub fn parse_delete_statement(p: &mut Parser) {
         common::parse_on_cluster(p);
     }
 
+    // Optional: IN PARTITION expr
+    common::parse_optional_in_partition(p);
+
     // WHERE is required for ClickHouse lightweight deletes
     if p.at_keyword(Keyword::Where) {
         let mw = p.start();
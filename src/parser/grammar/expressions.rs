@@ -1,5 +1,5 @@
 use crate::parser::syntax_kind::SyntaxKind;
-use crate::parser::grammar::common::parse_optional_settings_clause;
+use crate::parser::grammar::common::{self, parse_optional_settings_clause};
 use crate::parser::grammar::select::{at_end_of_column_list, at_select_statement, parse_select_statement};
 use crate::parser::grammar::types::parse_column_type;
 use crate::parser::interval_unit::IntervalUnit;
@@ -32,6 +32,10 @@ enum BinOp {
     Asterisk,
     Slash,
     Percent,
+    /// Integer division, `a DIV b`.
+    Div,
+    /// `a MOD b`, same as `a % b`.
+    Mod,
     // String concatenation
     Concatenation,
 }
@@ -46,6 +50,9 @@ impl BinOp {
         if p.at_keyword(Keyword::Or) {
             return Some(BinOp::Or);
         }
+        if (p.at_keyword(Keyword::Div) || p.at_keyword(Keyword::Mod)) && at_operand(p, 1) {
+            return Some(if p.at_keyword(Keyword::Div) { BinOp::Div } else { BinOp::Mod });
+        }
         match p.nth(0) {
             SyntaxKind::Plus => Some(BinOp::Plus),
             SyntaxKind::Minus => Some(BinOp::Minus),
@@ -67,11 +74,50 @@ impl BinOp {
         match self {
             BinOp::Or => 1,
             BinOp::And => 2,
-            BinOp::Equals | BinOp::NotEquals => 3,
-            BinOp::Less | BinOp::Greater | BinOp::LessOrEquals | BinOp::GreaterOrEquals => 4,
+            // All comparisons share a level, as in ClickHouse, so
+            // `a = b < c` is `(a = b) < c`.
+            BinOp::Equals
+            | BinOp::NotEquals
+            | BinOp::Less
+            | BinOp::Greater
+            | BinOp::LessOrEquals
+            | BinOp::GreaterOrEquals => 4,
             BinOp::Plus | BinOp::Minus | BinOp::Concatenation => 5,
-            BinOp::Asterisk | BinOp::Slash | BinOp::Percent => 6,
+            BinOp::Asterisk | BinOp::Slash | BinOp::Percent | BinOp::Div | BinOp::Mod => 6,
+        }
+    }
+}
+
+/// True if the token at `n` can start an operand, so a preceding `DIV` or
+/// `MOD` is an operator rather than an alias: `SELECT 7 mod FROM t`.
+fn at_operand(p: &mut Parser, n: usize) -> bool {
+    match p.nth(n) {
+        SyntaxKind::Number
+        | SyntaxKind::StringToken
+        | SyntaxKind::QuotedIdentifier
+        | SyntaxKind::OpeningRoundBracket
+        | SyntaxKind::OpeningSquareBracket
+        | SyntaxKind::Minus
+        | SyntaxKind::Plus => true,
+        SyntaxKind::BareWord => {
+            Keyword::from_text(p.nth_text(n)).is_none_or(|kw| !kw.is_reserved())
         }
+        _ => false,
+    }
+}
+
+/// True at a reserved keyword that isn't called as a function, so it can't
+/// start a column reference: `case` in `SELECT a, case FROM t`. LEFT, RIGHT,
+/// FULL and GLOBAL are left to `at_end_of_column_list`, which treats them as
+/// join words only where a join can follow, so `Join(ANY, LEFT, k)` engine
+/// arguments still read them as names.
+fn at_reserved_name(p: &mut Parser) -> bool {
+    if p.nth(0) != SyntaxKind::BareWord || p.at_followed_by_paren() {
+        return false;
+    }
+    match Keyword::from_text(p.nth_text(0)) {
+        Some(Keyword::Left | Keyword::Right | Keyword::Full | Keyword::Global) | None => false,
+        Some(keyword) => keyword.is_reserved() && p.at_keyword(keyword),
     }
 }
 
@@ -96,30 +142,42 @@ fn parse_expression_alias(p: &mut Parser) {
 }
 
 fn parse_expression_rec(p: &mut Parser, min_bp: u8) {
-    // Handle prefix NOT: binding power 3 (between AND=2 and comparisons=3..4)
-    // NOT binds tighter than AND/OR but at the same level as comparisons
+    // Handle prefix NOT. As in ClickHouse, NOT binds looser than comparisons
+    // and tighter than AND/OR: `NOT a = b` is `NOT (a = b)` and `NOT a AND b`
+    // is `(NOT a) AND b`. The operand is parsed with AND's binding power so
+    // it stops there.
     if p.at_keyword(Keyword::Not) {
         let m = p.start();
         p.advance(); // consume NOT
-        parse_expression_rec(p, 3);
+        parse_expression_rec(p, BinOp::And.binding_power());
         let lhs = p.complete(m, SyntaxKind::UnaryExpression);
         // Continue with binary operators after the NOT expression
         parse_expression_postfix(p, lhs, min_bp);
         return;
     }
 
-    // Handle prefix unary minus: highest precedence (7)
-    if p.at(SyntaxKind::Minus) {
+    // Handle prefix unary minus and plus: highest precedence (7)
+    if p.at(SyntaxKind::Minus) || p.at(SyntaxKind::Plus) {
         let m = p.start();
-        p.advance(); // consume -
+        p.advance(); // consume - or +
         parse_expression_rec(p, UNARY_PREFIX_BP);
         let lhs = p.complete(m, SyntaxKind::UnaryExpression);
         parse_expression_postfix(p, lhs, min_bp);
         return;
     }
 
+    // `COLUMNS('regexp')` is a matcher, and takes transformers like `*` does.
+    let columns_matcher =
+        p.at_keyword(Keyword::Columns) && p.nth(1) == SyntaxKind::OpeningRoundBracket;
+
     let Some(mut lhs) = expr_delimited(p) else {
-        p.advance_with_error("Expected expression");
+        // A colon is only valid in `a ? b : c` and `{k: v}`, which consume it
+        // themselves; name it rather than reporting a generic error.
+        if p.at(SyntaxKind::Colon) {
+            p.advance_with_error("Unexpected ':' in expression");
+        } else {
+            p.advance_with_error("Expected expression");
+        }
         return;
     };
 
@@ -181,8 +239,27 @@ fn parse_expression_rec(p: &mut Parser, min_bp: u8) {
             if !p.at(SyntaxKind::ClosingSquareBracket) {
                 parse_expression(p);
             }
+            // ClickHouse has no slice syntax: report `a[i:j]` once, at the
+            // colon, and still consume the upper bound.
+            if p.at(SyntaxKind::Colon) {
+                p.advance_with_error(
+                    "Array slices are not supported, use arraySlice(arr, offset, length)",
+                );
+                if !p.at(SyntaxKind::ClosingSquareBracket) {
+                    parse_expression(p);
+                }
+            }
             p.expect(SyntaxKind::ClosingSquareBracket);
             lhs = p.complete(m, SyntaxKind::ArrayAccessExpression);
+        } else if p.at(SyntaxKind::JsonExtractArrow) {
+            // MySQL-compatible JSON extract: expr ->> 'path'. Binds like the
+            // other postfix accessors, so `a ->> 'k' = 'v'` compares the result.
+            let m = p.precede(lhs);
+            p.advance(); // consume ->>
+            if expr_delimited(p).is_none() {
+                p.advance_with_error("Expected expression after '->>'");
+            }
+            lhs = p.complete(m, SyntaxKind::BinaryExpression);
         } else if p.at(SyntaxKind::DoubleColon) {
             // Cast: expr::Type
             let m = p.precede(lhs);
@@ -223,24 +300,19 @@ fn parse_expression_rec(p: &mut Parser, min_bp: u8) {
             }
         } else if (p.kind_of(lhs) == SyntaxKind::Asterisk
             || p.kind_of(lhs) == SyntaxKind::QualifiedAsterisk
-            || p.kind_of(lhs) == SyntaxKind::ColumnTransformer)
-            && (p.at_keyword(Keyword::Apply)
-                || p.at_keyword(Keyword::Except)
-                || p.at_keyword(Keyword::Replace))
-            && (p.nth(1) == SyntaxKind::OpeningRoundBracket
-                // APPLY can also be followed by a bare function name without parens:
-                // e.g. `* APPLY toString`, `alias_value.* APPLY toString`
-                || (p.at_keyword(Keyword::Apply) && (p.nth(1) == SyntaxKind::BareWord || p.nth(1) == SyntaxKind::QuotedIdentifier)))
+            || p.kind_of(lhs) == SyntaxKind::ColumnTransformer
+            || (columns_matcher && p.kind_of(lhs) == SyntaxKind::FunctionCall))
+            && at_column_transformer(p)
         {
             // Column transformers: * APPLY(func), * EXCEPT(col), * REPLACE(expr AS name)
             // Can chain: * EXCEPT(id) APPLY(toString)
-            // APPLY also supports bare form: * APPLY func
+            // APPLY and EXCEPT also support bare forms: * APPLY func, * EXCEPT col
             let m = p.precede(lhs);
             p.advance(); // consume APPLY/EXCEPT/REPLACE
             if p.at(SyntaxKind::OpeningRoundBracket) {
                 parse_column_transformer_args(p);
             } else {
-                // Bare function name form: APPLY func
+                // Bare form: APPLY func, EXCEPT col
                 let args = p.start();
                 parse_expression(p);
                 p.complete(args, SyntaxKind::ExpressionList);
@@ -315,8 +387,12 @@ fn parse_expression_postfix(p: &mut Parser, mut lhs: CompletedMarker, min_bp: u8
             }
         }
 
-        // [GLOBAL] [NOT] IN (...)
-        if p.at_keyword(Keyword::Global) && 4 > min_bp {
+        // [GLOBAL] [NOT] IN (...). A GLOBAL followed by anything else starts
+        // a join after an ON condition: `ON a.id = b.id GLOBAL JOIN c`.
+        if p.at_keyword(Keyword::Global)
+            && (p.nth_keyword(1, Keyword::In) || p.nth_keyword(1, Keyword::Not))
+            && 4 > min_bp
+        {
             let m = p.precede(lhs);
             p.advance(); // consume GLOBAL
             p.eat_keyword(Keyword::Not); // optional NOT
@@ -326,7 +402,8 @@ fn parse_expression_postfix(p: &mut Parser, mut lhs: CompletedMarker, min_bp: u8
             continue;
         }
 
-        if p.at_keyword(Keyword::In) && 4 > min_bp {
+        // `IN PARTITION` ends an UPDATE assignment, it isn't an IN operator.
+        if p.at_keyword(Keyword::In) && 4 > min_bp && !common::at_in_partition(p) {
             let m = p.precede(lhs);
             p.advance(); // consume IN
             parse_in_rhs(p);
@@ -359,7 +436,7 @@ fn parse_expression_postfix(p: &mut Parser, mut lhs: CompletedMarker, min_bp: u8
 
     // Ternary operator: expr ? expr : expr
     // Lowest precedence — handled after all binary operators.
-    if p.at(SyntaxKind::QuestionMark) && 0 >= min_bp {
+    if p.at(SyntaxKind::QuestionMark) && min_bp == 0 {
         let m = p.precede(lhs);
         p.advance(); // consume ?
         parse_expression(p); // middle ("then") expression
@@ -432,7 +509,7 @@ fn expr_delimited(p: &mut Parser) -> Option<CompletedMarker> {
             p.advance();
             p.complete(m, SyntaxKind::Asterisk)
         }
-        SyntaxKind::StringToken => {
+        SyntaxKind::StringToken | SyntaxKind::HereDoc => {
             let m = p.start();
             p.advance();
             p.complete(m, SyntaxKind::StringLiteral)
@@ -494,7 +571,7 @@ fn expr_delimited(p: &mut Parser) -> Option<CompletedMarker> {
             // json.path.field).  Stops when the segment after a dot is NOT an
             // identifier — numeric tuple indices (a.1) are left for the
             // DotAccessExpression postfix handler, matching ClickHouse semantics.
-            else if !at_end_of_column_list(p) {
+            else if !at_end_of_column_list(p) && !at_reserved_name(p) {
                 let m = p.start();
                 p.advance();
                 while p.at(SyntaxKind::Dot)
@@ -514,29 +591,33 @@ fn expr_delimited(p: &mut Parser) -> Option<CompletedMarker> {
         SyntaxKind::OpeningRoundBracket => {
             let m = p.start();
             p.expect(SyntaxKind::OpeningRoundBracket);
-            let mut count = 0;
-            if !p.at(SyntaxKind::ClosingRoundBracket) {
+            // `()` is the empty tuple and any comma makes a tuple, so `(1,)`
+            // is a one-element tuple while `(1)` is just a parenthesized 1.
+            let mut is_tuple = p.at(SyntaxKind::ClosingRoundBracket);
+            if !is_tuple {
                 parse_expression(p);
                 // ClickHouse allows expression aliases inside parens:
                 // (expr AS alias).field
                 parse_expression_alias(p);
-                count += 1;
                 while p.at(SyntaxKind::Comma) && !p.eof() {
                     p.advance();
+                    is_tuple = true;
+                    if p.at(SyntaxKind::ClosingRoundBracket) {
+                        break;
+                    }
                     parse_expression(p);
                     parse_expression_alias(p);
-                    count += 1;
                 }
             }
 
             p.expect(SyntaxKind::ClosingRoundBracket);
-            if count > 1 {
+            if is_tuple {
                 p.complete(m, SyntaxKind::TupleExpression)
             } else {
                 p.complete(m, SyntaxKind::Expression)
             }
         }
-        // Array literal: [expr, expr, ...] or []
+        // Array literal: [expr, expr, ...], [expr,] or []
         SyntaxKind::OpeningSquareBracket => {
             let m = p.start();
             p.expect(SyntaxKind::OpeningSquareBracket);
@@ -546,6 +627,10 @@ fn expr_delimited(p: &mut Parser) -> Option<CompletedMarker> {
 
                 while p.at(SyntaxKind::Comma) && !p.eof() {
                     p.advance();
+                    // Trailing comma: [1, 2,]
+                    if p.at(SyntaxKind::ClosingSquareBracket) {
+                        break;
+                    }
                     parse_expression(p);
                 }
             }
@@ -588,6 +673,19 @@ fn expr_delimited(p: &mut Parser) -> Option<CompletedMarker> {
                 p.complete(m, SyntaxKind::MapExpression)
             }
         }
+        // Positional parameter: `?`
+        SyntaxKind::QuestionMark => {
+            let m = p.start();
+            p.advance();
+            p.complete(m, SyntaxKind::PositionalParameter)
+        }
+        // Numbered parameter: `$1`, with no space after the dollar sign
+        SyntaxKind::DollarSign if p.nth_with_trivia(1) == SyntaxKind::Number => {
+            let m = p.start();
+            p.advance(); // $
+            p.advance(); // number
+            p.complete(m, SyntaxKind::PositionalParameter)
+        }
         _ => return None,
     };
 
@@ -600,11 +698,18 @@ fn parse_case_expression(p: &mut Parser) -> CompletedMarker {
     p.expect_keyword(Keyword::Case);
 
     // Optional operand for simple CASE (CASE x WHEN 1 THEN ...)
-    if !p.at_keyword(Keyword::When) && !p.at_keyword(Keyword::End) && !p.eof() {
+    if !p.at_keyword(Keyword::When)
+        && !p.at_keyword(Keyword::Else)
+        && !p.at_keyword(Keyword::End)
+        && !p.eof()
+    {
         parse_expression(p);
     }
 
-    // WHEN ... THEN ... clauses
+    // WHEN ... THEN ... clauses, at least one
+    if !p.at_keyword(Keyword::When) {
+        p.recover_with_error("Expected WHEN in CASE expression");
+    }
     while p.at_keyword(Keyword::When) && !p.eof() {
         let w = p.start();
         p.advance(); // consume WHEN
@@ -677,7 +782,11 @@ fn arg_list(p: &mut Parser) {
             break;
         }
         if !first {
-            p.expect(SyntaxKind::Comma);
+            // `f(a b)`: report the gap and keep parsing `b` as the next
+            // argument rather than misreading it.
+            if !p.eat(SyntaxKind::Comma) {
+                p.recover_with_error("Missing comma between arguments");
+            }
             // Check for SETTINGS after a comma:
             // e.g. func(arg1, SETTINGS key = value)
             if p.at_keyword(Keyword::Settings)
@@ -711,6 +820,26 @@ fn arg(p: &mut Parser) {
     p.complete(m, SyntaxKind::Expression);
 }
 
+/// At APPLY, EXCEPT or REPLACE starting a column transformer. APPLY and
+/// EXCEPT can also take a bare name, but `* EXCEPT SELECT ...` is a set
+/// operation.
+fn at_column_transformer(p: &mut Parser) -> bool {
+    if !p.at_any_keyword(&[Keyword::Apply, Keyword::Except, Keyword::Replace]) {
+        return false;
+    }
+    if p.nth(1) == SyntaxKind::OpeningRoundBracket {
+        return true;
+    }
+    if !matches!(p.nth(1), SyntaxKind::BareWord | SyntaxKind::QuotedIdentifier) {
+        return false;
+    }
+    p.at_keyword(Keyword::Apply)
+        || (p.at_keyword(Keyword::Except)
+            && !p.nth_keyword(1, Keyword::Select)
+            && !p.nth_keyword(1, Keyword::All)
+            && !p.nth_keyword(1, Keyword::Distinct))
+}
+
 /// Parses a parenthesized argument list for column transformers (APPLY, EXCEPT, REPLACE).
 /// Handles the special `expr AS name` syntax used by REPLACE.
 fn parse_column_transformer_args(p: &mut Parser) {
@@ -818,11 +947,12 @@ fn at_window_frame_keyword(p: &mut Parser) -> bool {
     p.at_keyword(Keyword::Rows) || p.at_keyword(Keyword::Range) || p.at_keyword(Keyword::Groups)
 }
 
-/// Parses a window frame bound:
+/// Parses a window frame bound into a `WindowFrameBound` node:
 ///   UNBOUNDED PRECEDING | UNBOUNDED FOLLOWING
 ///   CURRENT ROW
 ///   expr PRECEDING | expr FOLLOWING
 fn parse_window_frame_bound(p: &mut Parser) {
+    let m = p.start();
     if p.at_keyword(Keyword::Unbounded) {
         p.advance(); // UNBOUNDED
         if p.at_keyword(Keyword::Preceding) || p.at_keyword(Keyword::Following) {
@@ -842,6 +972,7 @@ fn parse_window_frame_bound(p: &mut Parser) {
             p.recover_with_error("Expected PRECEDING or FOLLOWING");
         }
     }
+    p.complete(m, SyntaxKind::WindowFrameBound);
 }
 
 #[cfg(test)]
@@ -886,6 +1017,83 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn concatenation_binds_tighter_than_comparison() {
+        check("SELECT a || b = c", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    BinaryExpression
+                      BinaryExpression
+                        ColumnReference
+                          'a'
+                        '||'
+                        ColumnReference
+                          'b'
+                      '='
+                      ColumnReference
+                        'c'
+        "#]]);
+    }
+
+    #[test]
+    fn comparisons_share_a_level() {
+        check("SELECT a = b < c", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    BinaryExpression
+                      BinaryExpression
+                        ColumnReference
+                          'a'
+                        '='
+                        ColumnReference
+                          'b'
+                      '<'
+                      ColumnReference
+                        'c'
+        "#]]);
+    }
+
+    #[test]
+    fn div_and_mod_operators() {
+        check("SELECT 7 DIV 2 + 7 MOD 2 % 3", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    BinaryExpression
+                      BinaryExpression
+                        NumberLiteral
+                          '7'
+                        'DIV'
+                        NumberLiteral
+                          '2'
+                      '+'
+                      BinaryExpression
+                        BinaryExpression
+                          NumberLiteral
+                            '7'
+                          'MOD'
+                          NumberLiteral
+                            '2'
+                        '%'
+                        NumberLiteral
+                          '3'
+        "#]]);
+    }
+
+    #[test]
+    fn caret_is_not_an_operator() {
+        // ClickHouse spells exponentiation `pow(a, b)`; there is no `^`.
+        assert!(!parse("SELECT 2 ^ 3").errors.is_empty());
+    }
+
     #[test]
     fn function_call() {
         check("SELECT now()", expect![[r#"
@@ -903,6 +1111,94 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn if_call_in_arithmetic() {
+        // `if` is a keyword elsewhere but an ordinary function name here.
+        check("SELECT if(x > 0, 1, 2) + 1", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    BinaryExpression
+                      FunctionCall
+                        Identifier
+                          'if'
+                        ExpressionList
+                          '('
+                          Expression
+                            BinaryExpression
+                              ColumnReference
+                                'x'
+                              '>'
+                              NumberLiteral
+                                '0'
+                          ','
+                          Expression
+                            NumberLiteral
+                              '1'
+                          ','
+                          Expression
+                            NumberLiteral
+                              '2'
+                          ')'
+                      '+'
+                      NumberLiteral
+                        '1'
+        "#]]);
+    }
+
+    #[test]
+    fn nested_multi_if() {
+        check("SELECT multiIf(a = 1, 'one', multiIf(b, 'x', 'y'), 'other')", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    FunctionCall
+                      Identifier
+                        'multiIf'
+                      ExpressionList
+                        '('
+                        Expression
+                          BinaryExpression
+                            ColumnReference
+                              'a'
+                            '='
+                            NumberLiteral
+                              '1'
+                        ','
+                        Expression
+                          StringLiteral
+                            ''one''
+                        ','
+                        Expression
+                          FunctionCall
+                            Identifier
+                              'multiIf'
+                            ExpressionList
+                              '('
+                              Expression
+                                ColumnReference
+                                  'b'
+                              ','
+                              Expression
+                                StringLiteral
+                                  ''x''
+                              ','
+                              Expression
+                                StringLiteral
+                                  ''y''
+                              ')'
+                        ','
+                        Expression
+                          StringLiteral
+                            ''other''
+                        ')'
+        "#]]);
+    }
+
     #[test]
     fn parametric_function() {
         check("SELECT quantile(0.9)(x)", expect![[r#"
@@ -1093,6 +1389,48 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn json_extract_arrow() {
+        check("SELECT a ->> 'k' = 'v', arrayMap(x -> x ->> 'id', arr)", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    BinaryExpression
+                      BinaryExpression
+                        ColumnReference
+                          'a'
+                        '->>'
+                        StringLiteral
+                          ''k''
+                      '='
+                      StringLiteral
+                        ''v''
+                    ','
+                    FunctionCall
+                      Identifier
+                        'arrayMap'
+                      ExpressionList
+                        '('
+                        LambdaExpression
+                          ColumnReference
+                            'x'
+                          '->'
+                          BinaryExpression
+                            ColumnReference
+                              'x'
+                            '->>'
+                            StringLiteral
+                              ''id''
+                        ','
+                        Expression
+                          ColumnReference
+                            'arr'
+                        ')'
+        "#]]);
+    }
+
     #[test]
     fn lambda_in_function() {
         check("SELECT arrayMap(x -> x + 1, arr)", expect![[r#"
@@ -1182,6 +1520,63 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn qualified_function_call() {
+        check("SELECT a.b.c(x) FROM t", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    FunctionCall
+                      Identifier
+                        'a'
+                        '.'
+                        'b'
+                        '.'
+                        'c'
+                      ExpressionList
+                        '('
+                        Expression
+                          ColumnReference
+                            'x'
+                        ')'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+        "#]]);
+    }
+
+    #[test]
+    fn qualified_call_vs_tuple_access() {
+        // `a.b(x)` calls the qualified name; `a.1` is tuple element access.
+        check("SELECT a.b(x), a.1", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    FunctionCall
+                      Identifier
+                        'a'
+                        '.'
+                        'b'
+                      ExpressionList
+                        '('
+                        Expression
+                          ColumnReference
+                            'x'
+                        ')'
+                    ','
+                    DotAccessExpression
+                      ColumnReference
+                        'a'
+                      '.'
+                      '1'
+        "#]]);
+    }
+
     // === New expression type tests ===
 
     #[test]
@@ -1216,6 +1611,60 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn not_binds_looser_than_comparison() {
+        check("SELECT * FROM t WHERE NOT a = b", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                WhereClause
+                  'WHERE'
+                  UnaryExpression
+                    'NOT'
+                    BinaryExpression
+                      ColumnReference
+                        'a'
+                      '='
+                      ColumnReference
+                        'b'
+        "#]]);
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and() {
+        check("SELECT * FROM t WHERE NOT a AND b", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                WhereClause
+                  'WHERE'
+                  BinaryExpression
+                    UnaryExpression
+                      'NOT'
+                      ColumnReference
+                        'a'
+                    'AND'
+                    ColumnReference
+                      'b'
+        "#]]);
+    }
+
     #[test]
     fn unary_minus() {
         check("SELECT -1", expect![[r#"
@@ -1253,6 +1702,37 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn unary_minus_binds_tighter_than_binary() {
+        check("SELECT -a + b, - -x, +y", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    BinaryExpression
+                      UnaryExpression
+                        '-'
+                        ColumnReference
+                          'a'
+                      '+'
+                      ColumnReference
+                        'b'
+                    ','
+                    UnaryExpression
+                      '-'
+                      UnaryExpression
+                        '-'
+                        ColumnReference
+                          'x'
+                    ','
+                    UnaryExpression
+                      '+'
+                      ColumnReference
+                        'y'
+        "#]]);
+    }
+
     #[test]
     fn between_expression() {
         check("SELECT x BETWEEN 1 AND 10", expect![[r#"
@@ -1294,6 +1774,89 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn between_and_binds_before_or() {
+        check("SELECT a BETWEEN b AND c OR d", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    BinaryExpression
+                      BetweenExpression
+                        ColumnReference
+                          'a'
+                        'BETWEEN'
+                        ColumnReference
+                          'b'
+                        'AND'
+                        ColumnReference
+                          'c'
+                      'OR'
+                      ColumnReference
+                        'd'
+        "#]]);
+    }
+
+    #[test]
+    fn not_in_subquery() {
+        check("SELECT x NOT IN (SELECT y FROM t) AND z", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    BinaryExpression
+                      InExpression
+                        ColumnReference
+                          'x'
+                        'NOT'
+                        'IN'
+                        '('
+                        SubqueryExpression
+                          SelectStatement
+                            SelectClause
+                              'SELECT'
+                              ColumnList
+                                ColumnReference
+                                  'y'
+                            FromClause
+                              'FROM'
+                              TableIdentifier
+                                't'
+                        ')'
+                      'AND'
+                      ColumnReference
+                        'z'
+        "#]]);
+    }
+
+    #[test]
+    fn in_array_literal() {
+        check("SELECT x IN [1, 2, 3]", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    InExpression
+                      ColumnReference
+                        'x'
+                      'IN'
+                      ArrayExpression
+                        '['
+                        NumberLiteral
+                          '1'
+                        ','
+                        NumberLiteral
+                          '2'
+                        ','
+                        NumberLiteral
+                          '3'
+                        ']'
+        "#]]);
+    }
+
     #[test]
     fn in_expression() {
         check("SELECT x IN (1, 2, 3)", expect![[r#"
@@ -1496,6 +2059,57 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn case_nested_in_then_without_else() {
+        check("SELECT CASE WHEN a THEN CASE b WHEN 1 THEN 'x' END END", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    CaseExpression
+                      'CASE'
+                      WhenClause
+                        'WHEN'
+                        ColumnReference
+                          'a'
+                        'THEN'
+                        CaseExpression
+                          'CASE'
+                          ColumnReference
+                            'b'
+                          WhenClause
+                            'WHEN'
+                            NumberLiteral
+                              '1'
+                            'THEN'
+                            StringLiteral
+                              ''x''
+                          'END'
+                      'END'
+        "#]]);
+    }
+
+    #[test]
+    fn case_without_when() {
+        check("SELECT CASE x ELSE 1 END", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    CaseExpression
+                      'CASE'
+                      ColumnReference
+                        'x'
+                      Error
+                      'ELSE'
+                      NumberLiteral
+                        '1'
+                      'END'
+        "#]]);
+    }
+
     #[test]
     fn null_literal() {
         check("SELECT NULL", expect![[r#"
@@ -1582,6 +2196,40 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn positional_parameters() {
+        check("SELECT ?, $1 FROM t WHERE x = {x:String}", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    PositionalParameter
+                      '?'
+                    ','
+                    PositionalParameter
+                      '$'
+                      '1'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                WhereClause
+                  'WHERE'
+                  BinaryExpression
+                    ColumnReference
+                      'x'
+                    '='
+                    QueryParameterExpression
+                      '{'
+                      'x'
+                      ':'
+                      DataType
+                        'String'
+                      '}'
+        "#]]);
+    }
+
     #[test]
     fn query_parameter_complex_type() {
         check("SELECT {ts:DateTime64(3)}", expect![[r#"
@@ -1963,4 +2611,86 @@ mod tests {
                     't'
         "#]]);
     }
+
+    #[test]
+    fn bare_transformers_on_qualified_asterisk() {
+        check("SELECT t.* EXCEPT secret APPLY toString FROM t", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnTransformer
+                      ColumnTransformer
+                        QualifiedAsterisk
+                          ColumnReference
+                            't'
+                          '.'
+                          '*'
+                        'EXCEPT'
+                        ExpressionList
+                          ColumnReference
+                            'secret'
+                      'APPLY'
+                      ExpressionList
+                        ColumnReference
+                          'toString'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+        "#]]);
+    }
+
+    #[test]
+    fn columns_matcher_transformer() {
+        check("SELECT COLUMNS('^a') APPLY(toString) FROM t", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnTransformer
+                      FunctionCall
+                        Identifier
+                          'COLUMNS'
+                        ExpressionList
+                          '('
+                          Expression
+                            StringLiteral
+                              ''^a''
+                          ')'
+                      'APPLY'
+                      ExpressionList
+                        '('
+                        ColumnReference
+                          'toString'
+                        ')'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+        "#]]);
+    }
+
+    #[test]
+    fn except_select_after_asterisk_is_set_operation() {
+        check("SELECT * EXCEPT SELECT 1", expect![[r#"
+            File
+              UnionClause
+                SelectStatement
+                  SelectClause
+                    'SELECT'
+                    ColumnList
+                      Asterisk
+                        '*'
+                'EXCEPT'
+                SelectStatement
+                  SelectClause
+                    'SELECT'
+                    ColumnList
+                      NumberLiteral
+                        '1'
+        "#]]);
+    }
 }
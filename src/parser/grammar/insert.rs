@@ -25,7 +25,7 @@ pub fn parse_insert_statement(p: &mut Parser) {
 
     // Check for FUNCTION keyword (INSERT INTO FUNCTION s3(...) ...)
     if p.eat_keyword(Keyword::Function) {
-        parse_table_function(p);
+        common::parse_table_function(p);
     } else {
         common::parse_table_identifier(p);
     }
@@ -59,48 +59,6 @@ pub fn parse_insert_statement(p: &mut Parser) {
     p.complete(m, SyntaxKind::InsertStatement);
 }
 
-// Parse a table function: func_name(args)
-fn parse_table_function(p: &mut Parser) {
-    let m = p.start();
-
-    if p.at_identifier() {
-        p.advance();
-    } else {
-        p.advance_with_error("Expected function name");
-    }
-
-    // Parse argument list
-    if p.at(SyntaxKind::OpeningRoundBracket) {
-        p.expect(SyntaxKind::OpeningRoundBracket);
-        let mut first = true;
-        while !p.at(SyntaxKind::ClosingRoundBracket) && !p.eof() {
-            // SETTINGS clause inside table function arguments
-            if p.at_keyword(Keyword::Settings)
-                && (p.nth(1) == SyntaxKind::BareWord || p.nth(1) == SyntaxKind::QuotedIdentifier)
-                && p.nth(2) == SyntaxKind::Equals
-            {
-                common::parse_optional_settings_clause(p);
-                break;
-            }
-            if !first {
-                p.expect(SyntaxKind::Comma);
-                if p.at_keyword(Keyword::Settings)
-                    && (p.nth(1) == SyntaxKind::BareWord || p.nth(1) == SyntaxKind::QuotedIdentifier)
-                    && p.nth(2) == SyntaxKind::Equals
-                {
-                    common::parse_optional_settings_clause(p);
-                    break;
-                }
-            }
-            first = false;
-            parse_expression(p);
-        }
-        p.expect(SyntaxKind::ClosingRoundBracket);
-    }
-
-    p.complete(m, SyntaxKind::TableFunction);
-}
-
 // Parse optional column list: (col1, col2, ...)
 fn parse_insert_columns(p: &mut Parser) {
     let m = p.start();
@@ -357,6 +315,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn insert_select_from_input() {
+        check(
+            "INSERT INTO t SELECT * FROM INPUT('x UInt8')",
+            expect![[r#"
+                File
+                  InsertStatement
+                    'INSERT'
+                    'INTO'
+                    TableIdentifier
+                      't'
+                    SelectStatement
+                      SelectClause
+                        'SELECT'
+                        ColumnList
+                          Asterisk
+                            '*'
+                      FromClause
+                        'FROM'
+                        TableFunction
+                          'INPUT'
+                          '('
+                          StringLiteral
+                            ''x UInt8''
+                          ')'
+            "#]],
+        );
+    }
+
     #[test]
     fn insert_format() {
         check(
@@ -425,6 +412,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn insert_null_function() {
+        check(
+            "INSERT INTO FUNCTION null('x UInt8') VALUES (1)",
+            expect![[r#"
+                File
+                  InsertStatement
+                    'INSERT'
+                    'INTO'
+                    'FUNCTION'
+                    TableFunction
+                      'null'
+                      '('
+                      StringLiteral
+                        ''x UInt8''
+                      ')'
+                    InsertValuesClause
+                      'VALUES'
+                      ValueRow
+                        '('
+                        NumberLiteral
+                          '1'
+                        ')'
+            "#]],
+        );
+    }
+
+    #[test]
+    fn insert_table_function_with_qualified_argument() {
+        check(
+            "INSERT INTO TABLE FUNCTION remote('host', db.t) SELECT 1",
+            expect![[r#"
+                File
+                  InsertStatement
+                    'INSERT'
+                    'INTO'
+                    'TABLE'
+                    'FUNCTION'
+                    TableFunction
+                      'remote'
+                      '('
+                      StringLiteral
+                        ''host''
+                      ','
+                      ColumnReference
+                        'db'
+                        '.'
+                        't'
+                      ')'
+                    SelectStatement
+                      SelectClause
+                        'SELECT'
+                        ColumnList
+                          NumberLiteral
+                            '1'
+            "#]],
+        );
+    }
+
     #[test]
     fn insert_settings() {
         check(
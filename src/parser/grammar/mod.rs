@@ -14,7 +14,9 @@ use crate::parser::grammar::alter::{at_alter_statement, parse_alter_statement};
 use crate::parser::grammar::create_table::{at_create_statement, parse_create_statement};
 use crate::parser::grammar::delete::{at_delete_statement, parse_delete_statement};
 use crate::parser::grammar::insert::{at_insert_statement, parse_insert_statement};
-use crate::parser::grammar::select::{at_select_statement, parse_select_statement};
+use crate::parser::grammar::expressions::parse_expression;
+use crate::parser::grammar::select::{at_select_statement, parse_column_list, parse_select_statement};
+use crate::parser::keyword::Keyword;
 use crate::parser::grammar::show::{
     at_describe_statement, at_explain_statement, at_show_statement, parse_describe_statement,
     parse_explain_statement, parse_show_statement,
@@ -22,78 +24,105 @@ use crate::parser::grammar::show::{
 use crate::parser::grammar::statements::*;
 use crate::parser::parser::Parser;
 
+/// An `at_*` predicate paired with the parser for that statement.
+type StatementRule = (fn(&mut Parser) -> bool, fn(&mut Parser));
+
+/// Statement kinds in dispatch order: the first `at_*` check that matches
+/// decides which parser runs.
+const STATEMENTS: &[StatementRule] = &[
+    (at_insert_statement, parse_insert_statement),
+    (at_explain_statement, parse_explain_statement),
+    (at_describe_statement, parse_describe_statement),
+    (at_show_statement, parse_show_statement),
+    (at_alter_statement, parse_alter_statement),
+    (at_delete_statement, parse_delete_statement),
+    (at_create_statement, parse_create_statement),
+    (at_use_statement, parse_use_statement),
+    (at_set_statement, parse_set_statement),
+    (at_drop_statement, parse_drop_statement),
+    (at_truncate_statement, parse_truncate_statement),
+    (at_rename_statement, parse_rename_statement),
+    (at_exists_statement, parse_exists_statement),
+    (at_check_statement, parse_check_statement),
+    (at_optimize_statement, parse_optimize_statement),
+    (at_attach_statement, parse_attach_statement),
+    (at_detach_statement, parse_detach_statement),
+    (at_exchange_statement, parse_exchange_statement),
+    (at_undrop_statement, parse_undrop_statement),
+    (at_backup_statement, parse_backup_statement),
+    (at_restore_statement, parse_restore_statement),
+    (at_grant_statement, parse_grant_statement),
+    (at_revoke_statement, parse_revoke_statement),
+    (at_system_statement, parse_system_statement),
+    (at_kill_statement, parse_kill_statement),
+    (at_begin_statement, parse_begin_statement),
+    (at_commit_statement, parse_commit_statement),
+    (at_rollback_statement, parse_rollback_statement),
+    (at_select_statement, parse_select_statement),
+];
+
+/// True if the parser is positioned at the start of any statement.
+fn at_statement_start(p: &mut Parser) -> bool {
+    STATEMENTS.iter().any(|(at, _)| at(p))
+}
+
 /// Top-level grammar entry point. Parses a full source file containing
 /// one or more semicolon-separated SQL statements.
 pub fn parse_source(p: &mut Parser) {
     let m = p.start();
 
     while !p.eof() {
-        if at_insert_statement(p) {
-            parse_insert_statement(p);
-        } else if at_explain_statement(p) {
-            parse_explain_statement(p);
-        } else if at_describe_statement(p) {
-            parse_describe_statement(p);
-        } else if at_show_statement(p) {
-            parse_show_statement(p);
-        } else if at_alter_statement(p) {
-            parse_alter_statement(p);
-        } else if at_delete_statement(p) {
-            parse_delete_statement(p);
-        } else if at_create_statement(p) {
-            parse_create_statement(p);
-        } else if at_use_statement(p) {
-            parse_use_statement(p);
-        } else if at_set_statement(p) {
-            parse_set_statement(p);
-        } else if at_drop_statement(p) {
-            parse_drop_statement(p);
-        } else if at_truncate_statement(p) {
-            parse_truncate_statement(p);
-        } else if at_rename_statement(p) {
-            parse_rename_statement(p);
-        } else if at_exists_statement(p) {
-            parse_exists_statement(p);
-        } else if at_check_statement(p) {
-            parse_check_statement(p);
-        } else if at_optimize_statement(p) {
-            parse_optimize_statement(p);
-        } else if at_attach_statement(p) {
-            parse_attach_statement(p);
-        } else if at_detach_statement(p) {
-            parse_detach_statement(p);
-        } else if at_exchange_statement(p) {
-            parse_exchange_statement(p);
-        } else if at_undrop_statement(p) {
-            parse_undrop_statement(p);
-        } else if at_backup_statement(p) {
-            parse_backup_statement(p);
-        } else if at_restore_statement(p) {
-            parse_restore_statement(p);
-        } else if at_grant_statement(p) {
-            parse_grant_statement(p);
-        } else if at_revoke_statement(p) {
-            parse_revoke_statement(p);
-        } else if at_system_statement(p) {
-            parse_system_statement(p);
-        } else if at_kill_statement(p) {
-            parse_kill_statement(p);
-        } else if at_begin_statement(p) {
-            parse_begin_statement(p);
-        } else if at_commit_statement(p) {
-            parse_commit_statement(p);
-        } else if at_rollback_statement(p) {
-            parse_rollback_statement(p);
-        } else if at_select_statement(p) {
-            parse_select_statement(p);
-        } else if p.at(SyntaxKind::Semicolon) {
+        if p.at_statement_separator() {
             p.advance();
+        } else if let Some((_, parse)) = STATEMENTS.iter().find(|(at, _)| at(p)) {
+            parse(p);
+            // A statement parser stops at the first token it can't place. If
+            // that isn't a separator or the start of the next statement, the
+            // rest up to `;` is garbage: report it once rather than per token.
+            // `at` skips trivia first, so trailing whitespace doesn't count.
+            if !p.at_statement_separator() && !p.eof() && !at_statement_start(p) {
+                p.advance_to_semicolon_with_error("Expected end of statement");
+            }
+        } else if p.is_strict() {
+            p.advance_to_end_with_error("Unknown statement");
         } else if !p.eof() {
-            p.advance_with_error("Unexpected token");
+            // Skip the unrecognized statement as one error so the ones after
+            // the next `;` still parse.
+            p.advance_to_semicolon_with_error("Unknown statement");
         }
     }
 
+    p.skip_rest_after_error_limit();
     p.skip_trivia();
 
     p.complete(m, SyntaxKind::File);
 }
+
+/// Grammar entry point for a standalone filter condition: `[WHERE] expr`.
+pub fn parse_where_fragment(p: &mut Parser) {
+    let m = p.start();
+    let clause = p.start();
+    p.eat_keyword(Keyword::Where);
+    parse_expression(p);
+    p.complete(clause, SyntaxKind::WhereClause);
+    finish_fragment(p, "condition");
+    p.complete(m, SyntaxKind::File);
+}
+
+/// Grammar entry point for a standalone SELECT list: `a, b AS x, *`.
+pub fn parse_column_list_fragment(p: &mut Parser) {
+    let m = p.start();
+    parse_column_list(p);
+    finish_fragment(p, "column list");
+    p.complete(m, SyntaxKind::File);
+}
+
+/// Report anything after a fragment as one error, then attach trailing trivia.
+fn finish_fragment(p: &mut Parser, what: &str) {
+    p.skip_trivia();
+    if !p.eof() {
+        p.advance_to_end_with_error(&format!("Unexpected input after {what}"));
+    }
+    p.skip_rest_after_error_limit();
+    p.skip_trivia();
+}
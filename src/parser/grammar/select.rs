@@ -6,9 +6,9 @@ use crate::parser::keyword::Keyword;
 use crate::parser::parser::Parser;
 
 /// Parses a full SELECT statement:
-///   [WITH ...] [FROM ... [FINAL] [AS alias]] [JOIN ...]
+///   [WITH ...] [FROM ... [AS alias] [FINAL] [SAMPLE ...]] [JOIN ...]
 ///   SELECT [DISTINCT [ON (...)]] ...
-///   [FROM ... [FINAL] [AS alias]] [JOIN ...]
+///   [FROM ... [AS alias] [FINAL] [SAMPLE ...]] [JOIN ...]
 ///   [PREWHERE expr]
 ///   [WHERE expr]
 ///   [GROUP BY expr, ... [WITH TOTALS|ROLLUP|CUBE]]
@@ -26,11 +26,15 @@ pub fn parse_select_statement(p: &mut Parser) {
         parse_with_clause(p);
     }
 
+    // Body clauses parsed so far, for reporting misplaced ones.
+    let mut seen = Vec::new();
+
     let mut parsed_early_from = false;
     if p.at_keyword(Keyword::From) {
         parse_from_clause(p);
         parse_join_clauses(p);
         parsed_early_from = true;
+        seen.push(BodyClause::From);
     }
 
     parse_select_clause(p);
@@ -42,26 +46,23 @@ pub fn parse_select_statement(p: &mut Parser) {
         if parsed_early_from {
             p.recover_with_error("Duplicate FROM clause");
         }
+        seen.push(BodyClause::From);
     }
 
     skip_to_clause_keyword(p);
 
     // PREWHERE
     if p.at_keyword(Keyword::Prewhere) {
-        let m = p.start();
-        p.expect_keyword(Keyword::Prewhere);
-        parse_expression(p);
-        p.complete(m, SyntaxKind::PrewhereClause);
+        parse_expression_clause(p, Keyword::Prewhere, SyntaxKind::PrewhereClause);
+        seen.push(BodyClause::Prewhere);
     }
 
     skip_to_clause_keyword(p);
 
     // WHERE
     if p.at_keyword(Keyword::Where) {
-        let m = p.start();
-        p.expect_keyword(Keyword::Where);
-        parse_expression(p);
-        p.complete(m, SyntaxKind::WhereClause);
+        parse_expression_clause(p, Keyword::Where, SyntaxKind::WhereClause);
+        seen.push(BodyClause::Where);
     }
 
     skip_to_clause_keyword(p);
@@ -69,6 +70,7 @@ pub fn parse_select_statement(p: &mut Parser) {
     // GROUP BY
     if p.at_keyword(Keyword::Group) {
         parse_group_by_clause(p);
+        seen.push(BodyClause::GroupBy);
     }
 
     // WITH TOTALS can appear even without an explicit GROUP BY clause
@@ -86,10 +88,8 @@ pub fn parse_select_statement(p: &mut Parser) {
 
     // HAVING
     if p.at_keyword(Keyword::Having) {
-        let m = p.start();
-        p.expect_keyword(Keyword::Having);
-        parse_expression(p);
-        p.complete(m, SyntaxKind::HavingClause);
+        parse_expression_clause(p, Keyword::Having, SyntaxKind::HavingClause);
+        seen.push(BodyClause::Having);
     }
 
     skip_to_clause_keyword(p);
@@ -97,16 +97,15 @@ pub fn parse_select_statement(p: &mut Parser) {
     // WINDOW (named window definitions)
     if p.at_keyword(Keyword::Window) {
         parse_window_clause(p);
+        seen.push(BodyClause::Window);
     }
 
     skip_to_clause_keyword(p);
 
     // QUALIFY — filters on window function results, like HAVING for GROUP BY
     if p.at_keyword(Keyword::Qualify) {
-        let m = p.start();
-        p.expect_keyword(Keyword::Qualify);
-        parse_expression(p);
-        p.complete(m, SyntaxKind::QualifyClause);
+        parse_expression_clause(p, Keyword::Qualify, SyntaxKind::QualifyClause);
+        seen.push(BodyClause::Qualify);
     }
 
     skip_to_clause_keyword(p);
@@ -114,6 +113,7 @@ pub fn parse_select_statement(p: &mut Parser) {
     // ORDER BY
     if p.at_keyword(Keyword::Order) {
         parse_order_by_clause(p);
+        seen.push(BodyClause::OrderBy);
     }
 
     skip_to_clause_keyword(p);
@@ -123,6 +123,7 @@ pub fn parse_select_statement(p: &mut Parser) {
     // So we parse LIMIT, then check for BY.
     if p.at_keyword(Keyword::Limit) {
         parse_limit_or_limit_by(p);
+        seen.push(BodyClause::Limit);
     }
 
     skip_to_clause_keyword(p);
@@ -134,29 +135,18 @@ pub fn parse_select_statement(p: &mut Parser) {
 
     skip_to_clause_keyword(p);
 
+    parse_misplaced_clauses(p, &mut seen);
+
     // SETTINGS
-    if p.at_keyword(Keyword::Settings) {
+    let has_settings = p.at_keyword(Keyword::Settings);
+    if has_settings {
         parse_settings_clause(p);
     }
 
     skip_to_clause_keyword(p);
 
-    // FORMAT
-    if p.at_keyword(Keyword::Format) {
-        let m = p.start();
-        p.expect_keyword(Keyword::Format);
-        if p.at_identifier() {
-            p.advance();
-        } else {
-            p.recover_with_error("Expected format name after FORMAT");
-        }
-        p.complete(m, SyntaxKind::FormatClause);
-    }
-
-    // SETTINGS can also appear after FORMAT
-    if p.at_keyword(Keyword::Settings) {
-        parse_settings_clause(p);
-    }
+    // INTO OUTFILE, FORMAT, and SETTINGS again after them
+    parse_output_clauses(p, has_settings);
 
     let completed = p.complete(m, SyntaxKind::SelectStatement);
 
@@ -177,6 +167,179 @@ pub fn parse_select_statement(p: &mut Parser) {
     }
 }
 
+/// The clauses between the SELECT list and SETTINGS, in the order ClickHouse
+/// accepts them.
+pub(crate) const BODY_CLAUSES: [&str; 9] = [
+    "FROM", "PREWHERE", "WHERE", "GROUP BY", "HAVING", "WINDOW", "QUALIFY", "ORDER BY", "LIMIT",
+];
+
+/// One of `BODY_CLAUSES`, in the same order.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BodyClause {
+    From,
+    Prewhere,
+    Where,
+    GroupBy,
+    Having,
+    Window,
+    Qualify,
+    OrderBy,
+    Limit,
+}
+
+impl BodyClause {
+    const ALL: [BodyClause; BODY_CLAUSES.len()] = [
+        BodyClause::From,
+        BodyClause::Prewhere,
+        BodyClause::Where,
+        BodyClause::GroupBy,
+        BodyClause::Having,
+        BodyClause::Window,
+        BodyClause::Qualify,
+        BodyClause::OrderBy,
+        BodyClause::Limit,
+    ];
+
+    /// The keyword the clause starts with.
+    fn keyword(self) -> Keyword {
+        match self {
+            BodyClause::From => Keyword::From,
+            BodyClause::Prewhere => Keyword::Prewhere,
+            BodyClause::Where => Keyword::Where,
+            BodyClause::GroupBy => Keyword::Group,
+            BodyClause::Having => Keyword::Having,
+            BodyClause::Window => Keyword::Window,
+            BodyClause::Qualify => Keyword::Qualify,
+            BodyClause::OrderBy => Keyword::Order,
+            BodyClause::Limit => Keyword::Limit,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        BODY_CLAUSES[self as usize]
+    }
+}
+
+/// Parses body clauses that turn up after their place has passed: a second
+/// WHERE, or a WHERE after ORDER BY. Each is reported and still parsed, so
+/// it keeps its own node instead of ending up in an Error.
+fn parse_misplaced_clauses(p: &mut Parser, seen: &mut Vec<BodyClause>) {
+    while let Some(clause) = BodyClause::ALL.into_iter().find(|c| p.at_keyword(c.keyword())) {
+        let later = BodyClause::ALL[clause as usize + 1..].iter().find(|c| seen.contains(c));
+        if seen.contains(&clause) {
+            p.recover_with_error(&format!("Duplicate {} clause", clause.name()));
+        } else if let Some(later) = later {
+            p.recover_with_error(&format!("{} must come before {}", clause.name(), later.name()));
+        }
+        match clause {
+            BodyClause::From => {
+                parse_from_clause(p);
+                parse_join_clauses(p);
+            }
+            BodyClause::Prewhere => {
+                parse_expression_clause(p, Keyword::Prewhere, SyntaxKind::PrewhereClause)
+            }
+            BodyClause::Where => {
+                parse_expression_clause(p, Keyword::Where, SyntaxKind::WhereClause)
+            }
+            BodyClause::GroupBy => parse_group_by_clause(p),
+            BodyClause::Having => {
+                parse_expression_clause(p, Keyword::Having, SyntaxKind::HavingClause)
+            }
+            BodyClause::Window => parse_window_clause(p),
+            BodyClause::Qualify => {
+                parse_expression_clause(p, Keyword::Qualify, SyntaxKind::QualifyClause)
+            }
+            BodyClause::OrderBy => parse_order_by_clause(p),
+            BodyClause::Limit => parse_limit_or_limit_by(p),
+        }
+        seen.push(clause);
+        skip_to_clause_keyword(p);
+    }
+}
+
+/// Parses `keyword expr` into a `kind` node: PREWHERE, WHERE, HAVING and
+/// QUALIFY.
+fn parse_expression_clause(p: &mut Parser, keyword: Keyword, kind: SyntaxKind) {
+    let m = p.start();
+    p.expect_keyword(keyword);
+    parse_expression(p);
+    p.complete(m, kind);
+}
+
+/// The output clauses that end a SELECT, in the order ClickHouse accepts
+/// them. The query's own SETTINGS comes first and may be repeated last.
+pub(crate) const OUTPUT_CLAUSES: [&str; 4] = ["SETTINGS", "INTO OUTFILE", "FORMAT", "SETTINGS"];
+
+/// Parses `[INTO OUTFILE ...] [FORMAT name] [SETTINGS ...]` after the query
+/// SETTINGS. A clause out of that order is reported and still parsed, so it
+/// keeps its own node instead of ending up in an Error.
+fn parse_output_clauses(p: &mut Parser, has_settings: bool) {
+    let mut last = has_settings.then_some(0);
+    let mut seen = [has_settings, false, false, false];
+    loop {
+        let rank = if p.at_keyword(Keyword::Settings) {
+            3
+        } else if at_into_outfile(p) {
+            1
+        } else if p.at_keyword(Keyword::Format) && !p.at_followed_by_paren() {
+            2
+        } else {
+            break;
+        };
+        if let Some(prev) = last {
+            if rank <= prev || (rank == 3 && prev == 0) {
+                let message = if seen[rank] || (rank == 3 && prev == 0) {
+                    format!("Duplicate {} clause", OUTPUT_CLAUSES[rank])
+                } else {
+                    format!("{} must come before {}", OUTPUT_CLAUSES[rank], OUTPUT_CLAUSES[prev])
+                };
+                p.recover_with_error(&message);
+            }
+        }
+        match rank {
+            1 => parse_into_outfile_clause(p),
+            2 => {
+                common::parse_optional_format_clause(p);
+            }
+            _ => parse_settings_clause(p),
+        }
+        seen[rank] = true;
+        last = Some(last.map_or(rank, |prev| prev.max(rank)));
+    }
+}
+
+fn at_into_outfile(p: &mut Parser) -> bool {
+    p.at_keyword(Keyword::Into) && p.nth_keyword(1, Keyword::Outfile)
+}
+
+/// Parses: INTO OUTFILE 'file' [AND STDOUT] [APPEND | TRUNCATE]
+///         [COMPRESSION 'type' [LEVEL n]]
+fn parse_into_outfile_clause(p: &mut Parser) {
+    let m = p.start();
+    p.expect_keyword(Keyword::Into);
+    p.expect_keyword(Keyword::Outfile);
+    if p.at(SyntaxKind::StringToken) {
+        p.advance();
+    } else {
+        p.recover_with_error("Expected file name after INTO OUTFILE");
+    }
+    if p.at_keyword(Keyword::And) && p.nth_keyword(1, Keyword::Stdout) {
+        p.expect_keyword(Keyword::And);
+        p.expect_keyword(Keyword::Stdout);
+    }
+    if p.at_keyword(Keyword::Append) || p.at_keyword(Keyword::Truncate) {
+        p.advance();
+    }
+    if p.eat_keyword(Keyword::Compression) {
+        p.expect(SyntaxKind::StringToken);
+        if p.eat_keyword(Keyword::Level) {
+            p.expect(SyntaxKind::Number);
+        }
+    }
+    p.complete(m, SyntaxKind::IntoOutfileClause);
+}
+
 /// True if the current position marks the end of a column list
 /// (i.e. we've hit a clause keyword or statement boundary).
 pub fn at_end_of_column_list(p: &mut Parser) -> bool {
@@ -208,7 +371,7 @@ fn at_clause_keyword(p: &mut Parser) -> bool {
     if p.at_keyword(Keyword::With) && p.nth_text(1).eq_ignore_ascii_case("TOTALS") {
         return true;
     }
-    common::at_any_keyword(p, SELECT_CLAUSE_KEYWORDS) || at_join_keyword(p)
+    common::at_any_keyword(p, SELECT_CLAUSE_KEYWORDS) || at_join_keyword(p) || at_into_outfile(p)
 }
 
 /// True if the parser is positioned at a keyword that starts a JOIN clause.
@@ -233,17 +396,36 @@ fn at_join_keyword(p: &mut Parser) -> bool {
         && (p.at_keyword(Keyword::Left)
             || p.at_keyword(Keyword::Right)
             || p.at_keyword(Keyword::Full)
-            || p.at_keyword(Keyword::Global)
-            || p.at_keyword(Keyword::Any)
-            || p.at_keyword(Keyword::All)
-            || p.at_keyword(Keyword::Asof)
-            || p.at_keyword(Keyword::Semi)
-            || p.at_keyword(Keyword::Anti));
-
-    at_unambiguous || at_ambiguous
+            || p.at_keyword(Keyword::Global));
+
+    // Strictness words aren't reserved (`SELECT any FROM t` reads a column),
+    // so they start a join only when the modifiers that follow reach JOIN.
+    let at_strictness = (p.at_keyword(Keyword::Any)
+        || p.at_keyword(Keyword::All)
+        || p.at_keyword(Keyword::Asof)
+        || p.at_keyword(Keyword::Semi)
+        || p.at_keyword(Keyword::Anti))
+        && at_join_modifiers_then_join(p);
+
+    at_unambiguous || at_ambiguous || at_strictness
         || p.at_keyword(Keyword::Array)
 }
 
+/// True if the words from the current position are join modifiers followed
+/// by JOIN, e.g. `ANY LEFT OUTER JOIN` or `SEMI JOIN`.
+fn at_join_modifiers_then_join(p: &mut Parser) -> bool {
+    const MODIFIERS: &[Keyword] = &[
+        Keyword::Global, Keyword::Local, Keyword::Any, Keyword::All, Keyword::Asof,
+        Keyword::Semi, Keyword::Anti, Keyword::Left, Keyword::Right, Keyword::Full,
+        Keyword::Inner, Keyword::Outer, Keyword::Cross,
+    ];
+    let mut n = 0;
+    while MODIFIERS.iter().any(|kw| p.nth_keyword(n, *kw)) {
+        n += 1;
+    }
+    n > 0 && p.nth_keyword(n, Keyword::Join)
+}
+
 /// True if the parser is positioned at the start of a SELECT statement.
 pub fn at_select_statement(p: &mut Parser) -> bool {
     p.at_keyword(Keyword::With) || p.at_keyword(Keyword::Select) || p.at_keyword(Keyword::From)
@@ -252,7 +434,12 @@ pub fn at_select_statement(p: &mut Parser) -> bool {
 /// Skips unexpected tokens until we reach a clause keyword or end of statement.
 /// Wraps each skipped token in an Error node.
 fn skip_to_clause_keyword(p: &mut Parser) {
-    while !p.eof() && !p.end_of_statement() && !at_clause_keyword(p) {
+    // ELSE never continues a SELECT; it ends the one in viewIfPermitted(...).
+    while !p.eof()
+        && !p.end_of_statement()
+        && !at_clause_keyword(p)
+        && !p.at_keyword(Keyword::Else)
+    {
         p.advance_with_error("Unexpected token");
     }
 }
@@ -313,10 +500,13 @@ fn parse_with_items(p: &mut Parser) {
                 || p.at(SyntaxKind::QuotedIdentifier)
             {
                 let am = p.start();
-                if p.at_keyword(Keyword::As) {
+                let explicit = p.at_keyword(Keyword::As);
+                if explicit {
                     p.expect_keyword(Keyword::As);
                 }
-                if !at_end_of_column_list(p) {
+                if (p.at_identifier() && !at_end_of_column_list(p))
+                    || (explicit && at_keyword_alias_after_as(p))
+                {
                     p.advance();
                 } else {
                     p.recover_with_error("Expected alias");
@@ -329,27 +519,39 @@ fn parse_with_items(p: &mut Parser) {
     p.complete(m, SyntaxKind::ColumnList);
 }
 
+/// After `AS`, a clause keyword is still the alias name when it cannot be
+/// starting its clause: `SELECT x AS select`, `SELECT x AS from, y FROM t`.
+fn at_keyword_alias_after_as(p: &mut Parser) -> bool {
+    p.at(SyntaxKind::BareWord)
+        && (matches!(
+            p.nth(1),
+            SyntaxKind::Comma
+                | SyntaxKind::ClosingRoundBracket
+                | SyntaxKind::Semicolon
+                | SyntaxKind::VerticalDelimiter
+                | SyntaxKind::EndOfStream
+        ) || SELECT_CLAUSE_KEYWORDS.iter().any(|kw| p.nth_keyword(1, *kw)))
+}
+
 /// Parses: SELECT [DISTINCT [ON (col, ...)]] expr [, expr ...]
 fn parse_select_clause(p: &mut Parser) {
     let m = p.start();
     p.expect_keyword(Keyword::Select);
 
     // DISTINCT [ON (...)]
-    if p.eat_keyword(Keyword::Distinct) {
-        if p.at_keyword(Keyword::On) {
-            p.advance(); // consume ON
-            p.expect(SyntaxKind::OpeningRoundBracket);
-            // parse comma-separated column list inside parens
-            let mut first = true;
-            while !p.at(SyntaxKind::ClosingRoundBracket) && !p.eof() && !p.end_of_statement() {
-                if !first {
-                    p.expect(SyntaxKind::Comma);
-                }
-                first = false;
-                parse_expression(p);
+    if p.eat_keyword(Keyword::Distinct) && p.at_keyword(Keyword::On) {
+        p.advance(); // consume ON
+        p.expect(SyntaxKind::OpeningRoundBracket);
+        // parse comma-separated column list inside parens
+        let mut first = true;
+        while !p.at(SyntaxKind::ClosingRoundBracket) && !p.eof() && !p.end_of_statement() {
+            if !first {
+                p.expect(SyntaxKind::Comma);
             }
-            p.expect(SyntaxKind::ClosingRoundBracket);
+            first = false;
+            parse_expression(p);
         }
+        p.expect(SyntaxKind::ClosingRoundBracket);
     }
 
     parse_column_list(p);
@@ -375,12 +577,19 @@ pub fn parse_column_list(p: &mut Parser) {
             || p.at(SyntaxKind::QuotedIdentifier)
         {
             let m = p.start();
-            if p.at_keyword(Keyword::As) {
+            let explicit = p.at_keyword(Keyword::As);
+            if explicit {
                 p.expect_keyword(Keyword::As);
             }
 
-            if !at_end_of_column_list(p) {
+            if (p.at_identifier() && !at_end_of_column_list(p))
+                || (explicit && at_keyword_alias_after_as(p))
+            {
                 p.advance()
+            } else if explicit && p.at(SyntaxKind::OpeningRoundBracket) {
+                // `expr AS (a, b)` names the elements of a tuple result.
+                // Only after AS: without it `(a, b)` would be a tuple.
+                parse_column_alias_list(p);
             } else {
                 p.recover_with_error("Expected column alias");
             }
@@ -392,7 +601,7 @@ pub fn parse_column_list(p: &mut Parser) {
     p.complete(m, SyntaxKind::ColumnList);
 }
 
-/// Parses: FROM table_reference [, table_reference ...] [FINAL] [AS alias | alias]
+/// Parses: FROM table_reference [, table_reference ...]
 /// Commas produce implicit cross joins (ClickHouse comma-join syntax).
 fn parse_from_clause(p: &mut Parser) {
     let m = p.start();
@@ -406,14 +615,16 @@ fn parse_from_clause(p: &mut Parser) {
 }
 
 /// Parses a table reference:
-///   - identifier [. identifier] [FINAL] [[AS] alias]
+///   - identifier [. identifier] [[AS] alias] [FINAL] [SAMPLE n [OFFSET m]]
 ///   - (SELECT ...) [[AS] alias]
 ///   - identifier(args) [[AS] alias]  (table function)
-fn parse_table_reference(p: &mut Parser) {
+pub fn parse_table_reference(p: &mut Parser) {
     // Subquery: (SELECT ...)
     if p.at(SyntaxKind::OpeningRoundBracket) {
         parse_subquery_table_ref(p);
-        parse_optional_table_alias(p);
+        if parse_optional_table_alias(p) && p.at(SyntaxKind::OpeningRoundBracket) {
+            parse_column_alias_list(p);
+        }
         return;
     }
 
@@ -442,49 +653,18 @@ fn parse_table_reference(p: &mut Parser) {
 
         // Check for table function: identifier(...)
         if p.at(SyntaxKind::OpeningRoundBracket) {
-            // Table function call
-            p.expect(SyntaxKind::OpeningRoundBracket);
-            let mut first = true;
-            while !p.at(SyntaxKind::ClosingRoundBracket) && !p.eof() && !p.end_of_statement() {
-                // SETTINGS clause inside table function arguments:
-                // e.g. mysql('host', db, tbl, 'user', '', SETTINGS connect_timeout = 100)
-                if p.at_keyword(Keyword::Settings)
-                    && (p.nth(1) == SyntaxKind::BareWord || p.nth(1) == SyntaxKind::QuotedIdentifier)
-                    && p.nth(2) == SyntaxKind::Equals
-                {
-                    common::parse_optional_settings_clause(p);
-                    break;
-                }
-                if !first {
-                    p.expect(SyntaxKind::Comma);
-                    // Check for SETTINGS after comma
-                    if p.at_keyword(Keyword::Settings)
-                        && (p.nth(1) == SyntaxKind::BareWord || p.nth(1) == SyntaxKind::QuotedIdentifier)
-                        && p.nth(2) == SyntaxKind::Equals
-                    {
-                        common::parse_optional_settings_clause(p);
-                        break;
-                    }
-                }
-                first = false;
-                parse_expression(p);
-            }
-            p.expect(SyntaxKind::ClosingRoundBracket);
+            common::parse_table_function_arguments(p);
             p.complete(m, SyntaxKind::TableFunction);
         } else {
             p.complete(m, SyntaxKind::TableIdentifier);
         }
 
-        // SAMPLE n [OFFSET m]
-        if p.at_keyword(Keyword::Sample) {
-            parse_sample_clause(p);
-        }
-
-        // FINAL
-        p.eat_keyword(Keyword::Final);
-
-        // Optional alias
+        // [[AS] alias] [FINAL] [SAMPLE n [OFFSET m]]
         parse_optional_table_alias(p);
+        if parse_table_modifiers(p) && at_table_alias(p) {
+            p.recover_with_error("Table alias must come before FINAL and SAMPLE");
+            parse_optional_table_alias(p);
+        }
     } else {
         let m = p.start();
         p.advance_with_error("Expected table reference");
@@ -492,6 +672,45 @@ fn parse_table_reference(p: &mut Parser) {
     }
 }
 
+/// Parses the table modifiers `[FINAL] [SAMPLE n [OFFSET m]]`, which
+/// ClickHouse accepts only in that order. A FINAL after SAMPLE is still
+/// consumed, with an error. Returns true if any modifier was present.
+fn parse_table_modifiers(p: &mut Parser) -> bool {
+    let mut seen_final = false;
+    let mut seen_sample = false;
+    loop {
+        if p.at_keyword(Keyword::Final) {
+            if seen_sample {
+                p.recover_with_error("FINAL must come before SAMPLE");
+            } else if seen_final {
+                p.recover_with_error("Duplicate FINAL");
+            }
+            p.advance();
+            seen_final = true;
+        } else if p.at_keyword(Keyword::Sample) {
+            if seen_sample {
+                p.recover_with_error("Duplicate SAMPLE");
+            }
+            parse_sample_clause(p);
+            seen_sample = true;
+        } else {
+            return seen_final || seen_sample;
+        }
+    }
+}
+
+/// True if the next tokens would be parsed as a table alias.
+fn at_table_alias(p: &mut Parser) -> bool {
+    p.at_keyword(Keyword::As)
+        || (p.at(SyntaxKind::BareWord)
+            && !at_clause_keyword(p)
+            && !at_join_keyword(p)
+            && !p.at_keyword(Keyword::On)
+            && !p.at_keyword(Keyword::Using)
+            && !p.at_keyword(Keyword::Final)
+            && !p.at_keyword(Keyword::Else))
+}
+
 /// Parses: SAMPLE expr [OFFSET expr]
 fn parse_sample_clause(p: &mut Parser) {
     let m = p.start();
@@ -524,8 +743,8 @@ fn parse_subquery_table_ref(p: &mut Parser) {
 ///
 /// When AS is present, it's unambiguous — any bareword is accepted as an alias
 /// (including keywords like LEFT, RIGHT). Without AS, we reject keywords that
-/// could start a JOIN or other clause.
-fn parse_optional_table_alias(p: &mut Parser) {
+/// could start a JOIN or other clause. Returns true if an alias was parsed.
+fn parse_optional_table_alias(p: &mut Parser) -> bool {
     if p.at_keyword(Keyword::As) {
         let m = p.start();
         p.advance(); // consume AS
@@ -535,11 +754,37 @@ fn parse_optional_table_alias(p: &mut Parser) {
             p.recover_with_error("Expected table alias");
         }
         p.complete(m, SyntaxKind::TableAlias);
-    } else if p.at(SyntaxKind::BareWord) && !at_clause_keyword(p) && !at_join_keyword(p) && !p.at_keyword(Keyword::On) && !p.at_keyword(Keyword::Using) && !p.at_keyword(Keyword::Final) {
+        true
+    } else if at_table_alias(p) {
         let m = p.start();
         p.advance();
         p.complete(m, SyntaxKind::TableAlias);
+        true
+    } else {
+        false
+    }
+}
+
+/// Parses a parenthesized list of column aliases into an IdentifierList: the
+/// columns of a derived table, `(SELECT ...) AS t (a, b)`, or the elements of
+/// a tuple result, `expr AS (a, b)`.
+fn parse_column_alias_list(p: &mut Parser) {
+    let m = p.start();
+    p.expect(SyntaxKind::OpeningRoundBracket);
+    let mut first = true;
+    while !p.at(SyntaxKind::ClosingRoundBracket) && !p.eof() && !p.end_of_statement() {
+        if !first {
+            p.expect(SyntaxKind::Comma);
+        }
+        first = false;
+        if p.at(SyntaxKind::BareWord) || p.at(SyntaxKind::QuotedIdentifier) {
+            p.advance();
+        } else {
+            p.advance_with_error("Expected column alias");
+        }
     }
+    p.expect(SyntaxKind::ClosingRoundBracket);
+    p.complete(m, SyntaxKind::IdentifierList);
 }
 
 // ========== JOIN ==========
@@ -553,8 +798,19 @@ fn parse_join_clauses(p: &mut Parser) {
 
 /// Parse a single JOIN clause:
 ///   [GLOBAL] [ANY|ALL|ASOF] [INNER|LEFT|RIGHT|FULL|CROSS] [OUTER|SEMI|ANTI] JOIN table_ref (ON expr | USING col_list)
+///
+/// The words up to and including JOIN form a JoinType node, and the ON or
+/// USING part a JoinConstraint node.
 fn parse_join_clause(p: &mut Parser) {
+    if p.at_keyword(Keyword::Array)
+        || (p.at_keyword(Keyword::Left) && p.nth_keyword(1, Keyword::Array))
+    {
+        parse_array_join_clause(p);
+        return;
+    }
+
     let m = p.start();
+    let type_m = p.start();
 
     // Optional GLOBAL
     p.eat_keyword(Keyword::Global);
@@ -571,53 +827,25 @@ fn parse_join_clause(p: &mut Parser) {
         p.advance();
     }
 
-    // ARRAY JOIN is special — supports: [LEFT] ARRAY JOIN expr [AS alias], ...
-    if p.at_keyword(Keyword::Array) {
-        p.advance(); // ARRAY
-        p.expect_keyword(Keyword::Join);
-        // Comma-separated expression list with optional aliases
-        let mut first = true;
-        while !p.eof() && !p.end_of_statement() && !at_clause_keyword(p) && !at_join_keyword(p) {
-            if !first {
-                p.expect(SyntaxKind::Comma);
-            }
-            first = false;
-            parse_expression(p);
-            // Optional alias: AS alias or bare identifier alias
-            if p.at_keyword(Keyword::As) {
-                let am = p.start();
-                p.advance(); // AS
-                if p.at_identifier() && !at_clause_keyword(p) {
-                    p.advance();
-                } else {
-                    p.recover_with_error("Expected alias after AS");
-                }
-                p.complete(am, SyntaxKind::ColumnAlias);
-            } else if p.at(SyntaxKind::BareWord) && !at_clause_keyword(p) && !at_join_keyword(p) {
-                let am = p.start();
-                p.advance();
-                p.complete(am, SyntaxKind::ColumnAlias);
-            }
-        }
-        p.complete(m, SyntaxKind::ArrayJoinClause);
-        return;
-    }
-
     // Optional OUTER | SEMI | ANTI
     if p.at_keyword(Keyword::Outer) || p.at_keyword(Keyword::Semi) || p.at_keyword(Keyword::Anti) {
         p.advance();
     }
 
     p.expect_keyword(Keyword::Join);
+    p.complete(type_m, SyntaxKind::JoinType);
 
     // Table reference
     parse_table_reference(p);
 
     // Join constraint: ON expr | USING col_list
     if p.at_keyword(Keyword::On) {
+        let cm = p.start();
         p.advance();
         parse_expression(p);
+        p.complete(cm, SyntaxKind::JoinConstraint);
     } else if p.at_keyword(Keyword::Using) {
+        let cm = p.start();
         p.advance();
         // USING (col, col) or USING col
         if p.at(SyntaxKind::OpeningRoundBracket) {
@@ -635,12 +863,46 @@ fn parse_join_clause(p: &mut Parser) {
             // USING col (without parens)
             parse_expression(p);
         }
+        p.complete(cm, SyntaxKind::JoinConstraint);
     }
     // CROSS JOIN has no constraint
 
     p.complete(m, SyntaxKind::JoinClause);
 }
 
+/// Parses: [LEFT] ARRAY JOIN expr [AS alias], ...
+fn parse_array_join_clause(p: &mut Parser) {
+    let m = p.start();
+    p.eat_keyword(Keyword::Left);
+    p.expect_keyword(Keyword::Array);
+    p.expect_keyword(Keyword::Join);
+    // Comma-separated expression list with optional aliases
+    let mut first = true;
+    while !p.eof() && !p.end_of_statement() && !at_clause_keyword(p) && !at_join_keyword(p) {
+        if !first {
+            p.expect(SyntaxKind::Comma);
+        }
+        first = false;
+        parse_expression(p);
+        // Optional alias: AS alias or bare identifier alias
+        if p.at_keyword(Keyword::As) {
+            let am = p.start();
+            p.advance(); // AS
+            if p.at_identifier() && !at_clause_keyword(p) {
+                p.advance();
+            } else {
+                p.recover_with_error("Expected alias after AS");
+            }
+            p.complete(am, SyntaxKind::ColumnAlias);
+        } else if p.at(SyntaxKind::BareWord) && !at_clause_keyword(p) && !at_join_keyword(p) {
+            let am = p.start();
+            p.advance();
+            p.complete(am, SyntaxKind::ColumnAlias);
+        }
+    }
+    p.complete(m, SyntaxKind::ArrayJoinClause);
+}
+
 // ========== GROUP BY ==========
 
 /// Parses: GROUP BY expr, ... [WITH TOTALS|ROLLUP|CUBE]
@@ -655,7 +917,7 @@ fn parse_group_by_clause(p: &mut Parser) {
     if p.at_keyword(Keyword::All) && !p.at_followed_by_paren() {
         let cm = p.start();
         p.advance();
-        p.complete(cm, SyntaxKind::ColumnReference);
+        p.complete(cm, SyntaxKind::GroupByAll);
     // GROUPING SETS ((...), (...), ...)
     } else if p.at_keyword(Keyword::Grouping) {
         parse_grouping_sets(p);
@@ -672,11 +934,7 @@ fn parse_group_by_clause(p: &mut Parser) {
         // WITH TOTALS | WITH ROLLUP | WITH CUBE
         if p.at_keyword(Keyword::With) {
             p.advance(); // consume WITH
-            if p.at_keyword(Keyword::Totals) {
-                p.advance();
-            } else if p.at_keyword(Keyword::Rollup) {
-                p.advance();
-            } else if p.at_keyword(Keyword::Cube) {
+            if common::at_any_keyword(p, &[Keyword::Totals, Keyword::Rollup, Keyword::Cube]) {
                 p.advance();
             } else {
                 p.recover_with_error("Expected TOTALS, ROLLUP, or CUBE after WITH");
@@ -720,21 +978,10 @@ fn parse_grouping_sets(p: &mut Parser) {
     p.complete(m, SyntaxKind::GroupingSetsClause);
 }
 
-/// Keywords that terminate a GROUP BY expression list.
+/// Keywords that terminate a GROUP BY expression list: any later clause,
+/// a set operation, or `WITH` (ROLLUP / CUBE / TOTALS).
 fn at_group_by_terminator(p: &mut Parser) -> bool {
-    p.at_keyword(Keyword::Having)
-        || p.at_keyword(Keyword::Order)
-        || p.at_keyword(Keyword::Limit)
-        || p.at_keyword(Keyword::Settings)
-        || p.at_keyword(Keyword::Format)
-        || p.at_keyword(Keyword::Select)
-        || p.at_keyword(Keyword::From)
-        || p.at_keyword(Keyword::Where)
-        || p.at_keyword(Keyword::Prewhere)
-        || p.at_keyword(Keyword::With)
-        || p.at_keyword(Keyword::Window)
-        || p.at_keyword(Keyword::Qualify)
-        || at_join_keyword(p)
+    at_clause_keyword(p) || p.at_keyword(Keyword::With)
 }
 
 // ========== WINDOW ==========
@@ -745,21 +992,19 @@ fn parse_window_clause(p: &mut Parser) {
     p.expect_keyword(Keyword::Window);
 
     let mut first = true;
-    while !p.eof() && !p.end_of_statement() && !at_order_by_terminator(p)
-        && !p.at_keyword(Keyword::Window)
-    {
+    while !p.eof() && !p.end_of_statement() && !at_order_by_terminator(p) {
         if !first {
             p.expect(SyntaxKind::Comma);
         }
         first = false;
 
-        let wm = p.start();
-        // window name
-        if p.at_identifier() {
-            p.advance();
-        } else {
-            p.recover_with_error("Expected window name");
+        // Consume a stray token so a malformed list can't stall the loop.
+        if !p.at_identifier() {
+            p.advance_with_error("Expected window name");
+            continue;
         }
+        let wm = p.start();
+        p.advance(); // window name
         p.expect_keyword(Keyword::As);
         parse_window_spec(p);
         p.complete(wm, SyntaxKind::WindowDefinition);
@@ -806,7 +1051,7 @@ fn parse_order_by_clause(p: &mut Parser) {
     p.complete(m, SyntaxKind::OrderByClause);
 }
 
-/// Parses: expr [ASC|DESC] [NULLS FIRST|LAST]
+/// Parses: expr [ASC|DESC] [NULLS FIRST|LAST] [COLLATE 'locale']
 /// Also handles ORDER BY ALL (ClickHouse extension).
 fn parse_order_by_item(p: &mut Parser) {
     let m = p.start();
@@ -851,6 +1096,15 @@ fn parse_order_by_item(p: &mut Parser) {
         }
     }
 
+    // COLLATE 'locale'
+    if p.eat_keyword(Keyword::Collate) {
+        if p.at(SyntaxKind::StringToken) {
+            p.advance();
+        } else {
+            p.recover_with_error("Expected locale string after COLLATE");
+        }
+    }
+
     // WITH FILL [FROM expr] [TO expr] [STEP expr] [INTERPOLATE (expr, ...)]
     if p.at_keyword(Keyword::With) && at_with_fill(p) {
         parse_with_fill_clause(p);
@@ -901,7 +1155,11 @@ fn parse_with_fill_clause(p: &mut Parser) {
                     p.expect(SyntaxKind::Comma);
                 }
                 first = false;
+                // column [AS expr]
                 parse_expression(p);
+                if p.eat_keyword(Keyword::As) {
+                    parse_expression(p);
+                }
             }
             p.expect(SyntaxKind::ClosingRoundBracket);
         }
@@ -910,23 +1168,16 @@ fn parse_with_fill_clause(p: &mut Parser) {
     p.complete(m, SyntaxKind::WithFillClause);
 }
 
-/// Keywords that terminate an ORDER BY item list.
+/// Keywords that terminate an ORDER BY item list (or a WINDOW definition
+/// list): any later clause or a set operation.
 fn at_order_by_terminator(p: &mut Parser) -> bool {
-    p.at_keyword(Keyword::Limit)
-        || p.at_keyword(Keyword::Settings)
-        || (p.at_keyword(Keyword::Format) && !p.at_followed_by_paren())
-        || p.at_keyword(Keyword::Select)
-        || p.at_keyword(Keyword::From)
-        || p.at_keyword(Keyword::Where)
-        || p.at_keyword(Keyword::Prewhere)
-        || p.at_keyword(Keyword::Having)
-        || p.at_keyword(Keyword::Group)
-        || at_join_keyword(p)
+    at_clause_keyword(p)
 }
 
 // ========== LIMIT / LIMIT BY ==========
 
 /// Parses LIMIT, detecting whether it's LIMIT BY or plain LIMIT.
+/// LIMIT [m,] n BY expr, ...       => LimitByClause
 /// LIMIT n [OFFSET m] BY expr, ... => LimitByClause
 /// LIMIT n [OFFSET m]              => LimitClause
 /// LIMIT m, n                      => LimitClause
@@ -935,15 +1186,18 @@ fn parse_limit_or_limit_by(p: &mut Parser) {
     p.expect_keyword(Keyword::Limit);
     parse_expression(p);
 
-    // Check for OFFSET before BY
-    if p.at_keyword(Keyword::Offset) {
-        p.advance();
+    // Offset and count, in either form, before BY
+    if p.at(SyntaxKind::Comma) {
+        p.expect(SyntaxKind::Comma);
+        parse_expression(p);
+    } else if p.at_keyword(Keyword::Offset) {
+        p.expect_keyword(Keyword::Offset);
         parse_expression(p);
     }
 
     if p.at_keyword(Keyword::By) {
         // LIMIT BY clause
-        p.advance(); // consume BY
+        p.expect_keyword(Keyword::By);
         let mut first = true;
         while !p.eof() && !p.end_of_statement() && !at_limit_by_terminator(p) {
             if !first {
@@ -953,16 +1207,6 @@ fn parse_limit_or_limit_by(p: &mut Parser) {
             parse_expression(p);
         }
         p.complete(m, SyntaxKind::LimitByClause);
-    } else if p.at(SyntaxKind::Comma) {
-        // LIMIT m, n syntax (offset, count)
-        p.advance(); // consume comma
-        parse_expression(p);
-        // WITH TIES
-        if p.at_keyword(Keyword::With) && p.nth_keyword(1, Keyword::Ties) {
-            p.advance(); // consume WITH
-            p.expect_keyword(Keyword::Ties); // consume TIES (skips trivia)
-        }
-        p.complete(m, SyntaxKind::LimitClause);
     } else {
         // WITH TIES — consume both tokens if present
         if p.at_keyword(Keyword::With) && p.nth_keyword(1, Keyword::Ties) {
@@ -980,10 +1224,7 @@ fn parse_limit_clause(p: &mut Parser) {
     p.expect_keyword(Keyword::Limit);
     parse_expression(p);
 
-    if p.at_keyword(Keyword::Offset) {
-        p.advance();
-        parse_expression(p);
-    } else if p.at(SyntaxKind::Comma) {
+    if p.at_keyword(Keyword::Offset) || p.at(SyntaxKind::Comma) {
         p.advance();
         parse_expression(p);
     }
@@ -1504,6 +1745,43 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn order_by_mixed_directions_with_collate() {
+        check("SELECT a FROM t ORDER BY a DESC, b ASC NULLS FIRST COLLATE 'tr', c", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'a'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                OrderByClause
+                  'ORDER'
+                  'BY'
+                  OrderByItem
+                    ColumnReference
+                      'a'
+                    'DESC'
+                  ','
+                  OrderByItem
+                    ColumnReference
+                      'b'
+                    'ASC'
+                    'NULLS'
+                    'FIRST'
+                    'COLLATE'
+                    ''tr''
+                  ','
+                  OrderByItem
+                    ColumnReference
+                      'c'
+        "#]]);
+    }
+
     #[test]
     fn limit_offset() {
         check("SELECT a FROM t LIMIT 10 OFFSET 5", expect![[r#"
@@ -1640,14 +1918,314 @@ mod tests {
     }
 
     #[test]
-    fn limit_by_then_limit() {
-        check("SELECT a FROM t ORDER BY a LIMIT 3 BY a LIMIT 10", expect![[r#"
+    fn settings_then_next_statement() {
+        check("SELECT 1 SETTINGS a = 1, b = 'x'; SELECT 2", expect![[r#"
             File
               SelectStatement
                 SelectClause
                   'SELECT'
                   ColumnList
-                    ColumnReference
+                    NumberLiteral
+                      '1'
+                SettingsClause
+                  'SETTINGS'
+                  SettingItem
+                    'a'
+                    '='
+                    NumberLiteral
+                      '1'
+                  ','
+                  SettingItem
+                    'b'
+                    '='
+                    StringLiteral
+                      ''x''
+              ';'
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    NumberLiteral
+                      '2'
+        "#]]);
+    }
+
+    #[test]
+    fn settings_dotted_key_and_string_value() {
+        check(
+            "SELECT 1 SETTINGS s3.max_connections = 10, format_csv_delimiter = ';', join_algorithm = hash",
+            expect![[r#"
+                File
+                  SelectStatement
+                    SelectClause
+                      'SELECT'
+                      ColumnList
+                        NumberLiteral
+                          '1'
+                    SettingsClause
+                      'SETTINGS'
+                      SettingItem
+                        's3'
+                        '.'
+                        'max_connections'
+                        '='
+                        NumberLiteral
+                          '10'
+                      ','
+                      SettingItem
+                        'format_csv_delimiter'
+                        '='
+                        StringLiteral
+                          '';''
+                      ','
+                      SettingItem
+                        'join_algorithm'
+                        '='
+                        ColumnReference
+                          'hash'
+            "#]],
+        );
+    }
+
+    #[test]
+    fn settings_without_equals() {
+        check(
+            "SELECT 1 SETTINGS max_threads 8, log_comment 'x'",
+            expect![[r#"
+                File
+                  SelectStatement
+                    SelectClause
+                      'SELECT'
+                      ColumnList
+                        NumberLiteral
+                          '1'
+                    SettingsClause
+                      'SETTINGS'
+                      SettingItem
+                        'max_threads'
+                        NumberLiteral
+                          '8'
+                      ','
+                      SettingItem
+                        'log_comment'
+                        StringLiteral
+                          ''x''
+            "#]],
+        );
+    }
+
+    #[test]
+    fn settings_with_and_without_equals_have_same_shape() {
+        // Node kinds and non-`=` tokens, in order.
+        fn shape(input: &str) -> Vec<String> {
+            let result = parse(input);
+            assert!(result.errors.is_empty(), "{input}: {:?}", result.errors);
+            let mut buf = String::new();
+            result.tree.print(&mut buf, 0, &result.source);
+            buf.lines()
+                .map(str::trim)
+                .filter(|line| *line != "'='")
+                .map(String::from)
+                .collect()
+        }
+        assert_eq!(
+            shape("SELECT 1 SETTINGS max_threads = 8, s3.max_connections = 10"),
+            shape("SELECT 1 SETTINGS max_threads 8, s3.max_connections 10")
+        );
+    }
+
+    #[test]
+    fn non_reserved_keywords_are_column_names() {
+        check(
+            "SELECT key, any, semi FROM t ANY LEFT JOIN u USING (key)",
+            expect![[r#"
+                File
+                  SelectStatement
+                    SelectClause
+                      'SELECT'
+                      ColumnList
+                        ColumnReference
+                          'key'
+                        ','
+                        ColumnReference
+                          'any'
+                        ','
+                        ColumnReference
+                          'semi'
+                    FromClause
+                      'FROM'
+                      TableIdentifier
+                        't'
+                    JoinClause
+                      JoinType
+                        'ANY'
+                        'LEFT'
+                        'JOIN'
+                      TableIdentifier
+                        'u'
+                      JoinConstraint
+                        'USING'
+                        '('
+                        ColumnReference
+                          'key'
+                        ')'
+            "#]],
+        );
+    }
+
+    #[test]
+    fn reserved_keyword_is_not_a_column_name() {
+        let result = parse("SELECT a, from FROM t");
+        assert!(!result.errors.is_empty());
+        assert!(parse("SELECT a, `from` FROM t").errors.is_empty());
+    }
+
+    #[test]
+    fn view_table_function_takes_a_select() {
+        check("SELECT * FROM view(SELECT 1 AS x)", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  TableFunction
+                    'view'
+                    '('
+                    SubqueryExpression
+                      SelectStatement
+                        SelectClause
+                          'SELECT'
+                          ColumnList
+                            NumberLiteral
+                              '1'
+                            ColumnAlias
+                              'AS'
+                              'x'
+                    ')'
+        "#]]);
+    }
+
+    #[test]
+    fn view_if_permitted_with_else_fallback() {
+        check(
+            "SELECT * FROM viewIfPermitted(SELECT a FROM t ELSE null('a UInt8'))",
+            expect![[r#"
+                File
+                  SelectStatement
+                    SelectClause
+                      'SELECT'
+                      ColumnList
+                        Asterisk
+                          '*'
+                    FromClause
+                      'FROM'
+                      TableFunction
+                        'viewIfPermitted'
+                        '('
+                        SubqueryExpression
+                          SelectStatement
+                            SelectClause
+                              'SELECT'
+                              ColumnList
+                                ColumnReference
+                                  'a'
+                            FromClause
+                              'FROM'
+                              TableIdentifier
+                                't'
+                        'ELSE'
+                        TableFunction
+                          'null'
+                          '('
+                          StringLiteral
+                            ''a UInt8''
+                          ')'
+                        ')'
+            "#]],
+        );
+    }
+
+    #[test]
+    fn limit_by_with_comma_offset() {
+        check("SELECT a FROM t LIMIT 1, 2 BY a, b LIMIT 3", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'a'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                LimitByClause
+                  'LIMIT'
+                  NumberLiteral
+                    '1'
+                  ','
+                  NumberLiteral
+                    '2'
+                  'BY'
+                  ColumnReference
+                    'a'
+                  ','
+                  ColumnReference
+                    'b'
+                LimitClause
+                  'LIMIT'
+                  NumberLiteral
+                    '3'
+        "#]]);
+    }
+
+    #[test]
+    fn limit_by_with_offset_keyword() {
+        check("SELECT a FROM t LIMIT 2 OFFSET 1 BY a SETTINGS x = 1", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'a'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                LimitByClause
+                  'LIMIT'
+                  NumberLiteral
+                    '2'
+                  'OFFSET'
+                  NumberLiteral
+                    '1'
+                  'BY'
+                  ColumnReference
+                    'a'
+                SettingsClause
+                  'SETTINGS'
+                  SettingItem
+                    'x'
+                    '='
+                    NumberLiteral
+                      '1'
+        "#]]);
+    }
+
+    #[test]
+    fn limit_by_then_limit() {
+        check("SELECT a FROM t ORDER BY a LIMIT 3 BY a LIMIT 10", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
                       'a'
                 FromClause
                   'FROM'
@@ -1688,21 +2266,23 @@ mod tests {
                   TableIdentifier
                     't1'
                 JoinClause
-                  'INNER'
-                  'JOIN'
+                  JoinType
+                    'INNER'
+                    'JOIN'
                   TableIdentifier
                     't2'
-                  'ON'
-                  BinaryExpression
-                    ColumnReference
-                      't1'
-                      '.'
-                      'id'
-                    '='
-                    ColumnReference
-                      't2'
-                      '.'
-                      'id'
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        't1'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        't2'
+                        '.'
+                        'id'
         "#]]);
     }
 
@@ -1721,21 +2301,59 @@ mod tests {
                   TableIdentifier
                     't1'
                 JoinClause
-                  'LEFT'
-                  'JOIN'
+                  JoinType
+                    'LEFT'
+                    'JOIN'
                   TableIdentifier
                     't2'
-                  'ON'
-                  BinaryExpression
-                    ColumnReference
-                      't1'
-                      '.'
-                      'id'
-                    '='
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        't1'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        't2'
+                        '.'
+                        'id'
+        "#]]);
+    }
+
+    #[test]
+    fn left_outer_join_on() {
+        check("SELECT a FROM t1 LEFT OUTER JOIN t2 ON t1.id = t2.id", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
                     ColumnReference
-                      't2'
-                      '.'
-                      'id'
+                      'a'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't1'
+                JoinClause
+                  JoinType
+                    'LEFT'
+                    'OUTER'
+                    'JOIN'
+                  TableIdentifier
+                    't2'
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        't1'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        't2'
+                        '.'
+                        'id'
         "#]]);
     }
 
@@ -1754,16 +2372,18 @@ mod tests {
                   TableIdentifier
                     't1'
                 JoinClause
-                  'RIGHT'
-                  'OUTER'
-                  'JOIN'
+                  JoinType
+                    'RIGHT'
+                    'OUTER'
+                    'JOIN'
                   TableIdentifier
                     't2'
-                  'USING'
-                  '('
-                  ColumnReference
-                    'id'
-                  ')'
+                  JoinConstraint
+                    'USING'
+                    '('
+                    ColumnReference
+                      'id'
+                    ')'
         "#]]);
     }
 
@@ -1782,8 +2402,9 @@ mod tests {
                   TableIdentifier
                     't1'
                 JoinClause
-                  'CROSS'
-                  'JOIN'
+                  JoinType
+                    'CROSS'
+                    'JOIN'
                   TableIdentifier
                     't2'
         "#]]);
@@ -1804,22 +2425,24 @@ mod tests {
                   TableIdentifier
                     't1'
                 JoinClause
-                  'GLOBAL'
-                  'LEFT'
-                  'JOIN'
+                  JoinType
+                    'GLOBAL'
+                    'LEFT'
+                    'JOIN'
                   TableIdentifier
                     't2'
-                  'ON'
-                  BinaryExpression
-                    ColumnReference
-                      't1'
-                      '.'
-                      'id'
-                    '='
-                    ColumnReference
-                      't2'
-                      '.'
-                      'id'
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        't1'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        't2'
+                        '.'
+                        'id'
         "#]]);
     }
 
@@ -1838,14 +2461,66 @@ mod tests {
                   TableIdentifier
                     't1'
                 JoinClause
-                  'ANY'
-                  'LEFT'
-                  'JOIN'
+                  JoinType
+                    'ANY'
+                    'LEFT'
+                    'JOIN'
                   TableIdentifier
                     't2'
-                  'USING'
-                  ColumnReference
-                    'id'
+                  JoinConstraint
+                    'USING'
+                    ColumnReference
+                      'id'
+        "#]]);
+    }
+
+    #[test]
+    fn three_way_join_on_and_using() {
+        check("SELECT * FROM a JOIN b ON a.id = b.id GLOBAL ANY JOIN c USING (id, k)", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    'a'
+                JoinClause
+                  JoinType
+                    'JOIN'
+                  TableIdentifier
+                    'b'
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        'a'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        'b'
+                        '.'
+                        'id'
+                JoinClause
+                  JoinType
+                    'GLOBAL'
+                    'ANY'
+                    'JOIN'
+                  TableIdentifier
+                    'c'
+                  JoinConstraint
+                    'USING'
+                    '('
+                    ColumnReference
+                      'id'
+                    ','
+                    ColumnReference
+                      'k'
+                    ')'
         "#]]);
     }
 
@@ -1864,38 +2539,84 @@ mod tests {
                   TableIdentifier
                     't1'
                 JoinClause
-                  'JOIN'
+                  JoinType
+                    'JOIN'
                   TableIdentifier
                     't2'
-                  'ON'
-                  BinaryExpression
-                    ColumnReference
-                      't1'
-                      '.'
-                      'id'
-                    '='
-                    ColumnReference
-                      't2'
-                      '.'
-                      'id'
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        't1'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        't2'
+                        '.'
+                        'id'
                 JoinClause
-                  'JOIN'
+                  JoinType
+                    'JOIN'
                   TableIdentifier
                     't3'
-                  'ON'
-                  BinaryExpression
-                    ColumnReference
-                      't2'
-                      '.'
-                      'id'
-                    '='
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        't2'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        't3'
+                        '.'
+                        'id'
+        "#]]);
+    }
+
+    #[test]
+    fn subquery_from_with_column_aliases() {
+        check("SELECT a FROM (SELECT 1, 2) AS t (a, b)", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
                     ColumnReference
-                      't3'
-                      '.'
-                      'id'
+                      'a'
+                FromClause
+                  'FROM'
+                  SubqueryExpression
+                    '('
+                    SelectStatement
+                      SelectClause
+                        'SELECT'
+                        ColumnList
+                          NumberLiteral
+                            '1'
+                          ','
+                          NumberLiteral
+                            '2'
+                    ')'
+                  TableAlias
+                    'AS'
+                    't'
+                  IdentifierList
+                    '('
+                    'a'
+                    ','
+                    'b'
+                    ')'
         "#]]);
     }
 
+    #[test]
+    fn subquery_from_with_column_aliases_without_as() {
+        let result = parse("SELECT a FROM (SELECT 1, 2) t(a, `b`) JOIN u ON t.a = u.a");
+        assert!(result.errors.is_empty(), "unexpected errors: {:?}", result.errors);
+    }
+
     #[test]
     fn subquery_from() {
         check("SELECT a FROM (SELECT 1 AS x) AS sub", expect![[r#"
@@ -1926,6 +2647,193 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn with_scalar_and_cte_items() {
+        check("WITH 1 AS x, c AS (SELECT 2) SELECT x FROM c", expect![[r#"
+            File
+              SelectStatement
+                WithClause
+                  'WITH'
+                  ColumnList
+                    NumberLiteral
+                      '1'
+                    ColumnAlias
+                      'AS'
+                      'x'
+                    ','
+                    WithExpressionItem
+                      'c'
+                      'AS'
+                      '('
+                      SubqueryExpression
+                        SelectStatement
+                          SelectClause
+                            'SELECT'
+                            ColumnList
+                              NumberLiteral
+                                '2'
+                      ')'
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'x'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    'c'
+        "#]]);
+    }
+
+    #[test]
+    fn subquery_from_bare_alias() {
+        check("SELECT * FROM (SELECT 1) x", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  SubqueryExpression
+                    '('
+                    SelectStatement
+                      SelectClause
+                        'SELECT'
+                        ColumnList
+                          NumberLiteral
+                            '1'
+                    ')'
+                  TableAlias
+                    'x'
+        "#]]);
+    }
+
+    #[test]
+    fn qualified_table_bare_alias() {
+        check("SELECT n.number FROM system.numbers n", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'n'
+                      '.'
+                      'number'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    'system'
+                    '.'
+                    'numbers'
+                  TableAlias
+                    'n'
+        "#]]);
+    }
+
+    #[test]
+    fn s3_table_function() {
+        check("SELECT * FROM s3('https://bucket/data.csv', 'CSV', 'a UInt8, b String')", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  TableFunction
+                    's3'
+                    '('
+                    StringLiteral
+                      ''https://bucket/data.csv''
+                    ','
+                    StringLiteral
+                      ''CSV''
+                    ','
+                    StringLiteral
+                      ''a UInt8, b String''
+                    ')'
+        "#]]);
+    }
+
+    #[test]
+    fn mysql_table_function() {
+        check("SELECT * FROM mysql('host:3306', 'db', 'table', 'user', 'pass')", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  TableFunction
+                    'mysql'
+                    '('
+                    StringLiteral
+                      ''host:3306''
+                    ','
+                    StringLiteral
+                      ''db''
+                    ','
+                    StringLiteral
+                      ''table''
+                    ','
+                    StringLiteral
+                      ''user''
+                    ','
+                    StringLiteral
+                      ''pass''
+                    ')'
+        "#]]);
+    }
+
+    #[test]
+    fn table_function_named_arguments_may_be_keywords() {
+        check("SELECT * FROM s3(coll, url = 'https://b/x', format = CSV)", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  TableFunction
+                    's3'
+                    '('
+                    ColumnReference
+                      'coll'
+                    ','
+                    BinaryExpression
+                      ColumnReference
+                        'url'
+                      '='
+                      StringLiteral
+                        ''https://b/x''
+                    ','
+                    BinaryExpression
+                      ColumnReference
+                        'format'
+                      '='
+                      ColumnReference
+                        'CSV'
+                    ')'
+        "#]]);
+        let result = parse(
+            "SELECT * FROM postgresql('host:5432', 'db', 'tbl', 'user', 'pass', 'schema') \
+             UNION ALL SELECT * FROM s3('url', NOSIGN, 'Parquet', 'a UInt8', 'gzip')",
+        );
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
+
     #[test]
     fn table_function() {
         check("SELECT * FROM numbers(10)", expect![[r#"
@@ -1947,6 +2855,33 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn cluster_table_function() {
+        check("SELECT * FROM cluster('default', db, events)", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  TableFunction
+                    'cluster'
+                    '('
+                    StringLiteral
+                      ''default''
+                    ','
+                    ColumnReference
+                      'db'
+                    ','
+                    ColumnReference
+                      'events'
+                    ')'
+        "#]]);
+    }
+
     #[test]
     fn select_from_final() {
         check("SELECT a FROM t FINAL", expect![[r#"
@@ -1983,23 +2918,25 @@ mod tests {
                     'AS'
                     'a'
                 JoinClause
-                  'JOIN'
+                  JoinType
+                    'JOIN'
                   TableIdentifier
                     't2'
                   TableAlias
                     'AS'
                     'b'
-                  'ON'
-                  BinaryExpression
-                    ColumnReference
-                      'a'
-                      '.'
-                      'id'
-                    '='
-                    ColumnReference
-                      'b'
-                      '.'
-                      'id'
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        'a'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        'b'
+                        '.'
+                        'id'
         "#]]);
     }
 
@@ -2018,22 +2955,24 @@ mod tests {
                   TableIdentifier
                     't1'
                 JoinClause
-                  'LEFT'
-                  'SEMI'
-                  'JOIN'
+                  JoinType
+                    'LEFT'
+                    'SEMI'
+                    'JOIN'
                   TableIdentifier
                     't2'
-                  'ON'
-                  BinaryExpression
-                    ColumnReference
-                      't1'
-                      '.'
-                      'id'
-                    '='
-                    ColumnReference
-                      't2'
-                      '.'
-                      'id'
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        't1'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        't2'
+                        '.'
+                        'id'
         "#]]);
     }
 
@@ -2052,22 +2991,24 @@ mod tests {
                   TableIdentifier
                     't1'
                 JoinClause
-                  'LEFT'
-                  'ANTI'
-                  'JOIN'
+                  JoinType
+                    'LEFT'
+                    'ANTI'
+                    'JOIN'
                   TableIdentifier
                     't2'
-                  'ON'
-                  BinaryExpression
-                    ColumnReference
-                      't1'
-                      '.'
-                      'id'
-                    '='
-                    ColumnReference
-                      't2'
-                      '.'
-                      'id'
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        't1'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        't2'
+                        '.'
+                        'id'
         "#]]);
     }
 
@@ -2378,11 +3319,89 @@ mod tests {
                         WindowFrame
                           'ROWS'
                           'BETWEEN'
-                          'UNBOUNDED'
-                          'PRECEDING'
+                          WindowFrameBound
+                            'UNBOUNDED'
+                            'PRECEDING'
+                          'AND'
+                          WindowFrameBound
+                            'CURRENT'
+                            'ROW'
+                        ')'
+        "#]]);
+    }
+
+    #[test]
+    fn window_with_range_frame() {
+        check("SELECT avg(x) OVER (PARTITION BY k ORDER BY d RANGE BETWEEN 3 PRECEDING AND UNBOUNDED FOLLOWING) FROM t", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    WindowExpression
+                      FunctionCall
+                        Identifier
+                          'avg'
+                        ExpressionList
+                          '('
+                          Expression
+                            ColumnReference
+                              'x'
+                          ')'
+                      'OVER'
+                      WindowSpec
+                        '('
+                        'PARTITION'
+                        'BY'
+                        ColumnReference
+                          'k'
+                        'ORDER'
+                        'BY'
+                        ColumnReference
+                          'd'
+                        WindowFrame
+                          'RANGE'
+                          'BETWEEN'
+                          WindowFrameBound
+                            NumberLiteral
+                              '3'
+                            'PRECEDING'
                           'AND'
-                          'CURRENT'
-                          'ROW'
+                          WindowFrameBound
+                            'UNBOUNDED'
+                            'FOLLOWING'
+                        ')'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+        "#]]);
+    }
+
+    #[test]
+    fn window_with_single_bound_frame() {
+        check("SELECT count() OVER (ROWS 2 PRECEDING)", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    WindowExpression
+                      FunctionCall
+                        Identifier
+                          'count'
+                        ExpressionList
+                          '('
+                          ')'
+                      'OVER'
+                      WindowSpec
+                        '('
+                        WindowFrame
+                          'ROWS'
+                          WindowFrameBound
+                            NumberLiteral
+                              '2'
+                            'PRECEDING'
                         ')'
         "#]]);
     }
@@ -2471,6 +3490,53 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn alias_final_sample_canonical_order() {
+        check("SELECT * FROM t AS x FINAL SAMPLE 1 / 10 OFFSET 1 / 2", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                  TableAlias
+                    'AS'
+                    'x'
+                  'FINAL'
+                  SampleClause
+                    'SAMPLE'
+                    BinaryExpression
+                      NumberLiteral
+                        '1'
+                      '/'
+                      NumberLiteral
+                        '10'
+                    'OFFSET'
+                    BinaryExpression
+                      NumberLiteral
+                        '1'
+                      '/'
+                      NumberLiteral
+                        '2'
+        "#]]);
+    }
+
+    #[test]
+    fn out_of_order_table_modifiers() {
+        let errors = |sql: &str| -> Vec<String> {
+            parse(sql).errors.into_iter().map(|e| format!("{}..{}: {}", e.range.0, e.range.1, e.message)).collect()
+        };
+        assert_eq!(errors("SELECT * FROM t SAMPLE 0.1 FINAL"), ["27..32: FINAL must come before SAMPLE"]);
+        assert_eq!(errors("SELECT * FROM t FINAL AS x"), ["22..24: Table alias must come before FINAL and SAMPLE"]);
+        assert_eq!(errors("SELECT * FROM t FINAL FINAL"), ["22..27: Duplicate FINAL"]);
+        assert!(errors("SELECT * FROM t FINAL SAMPLE 0.1 JOIN u USING id").is_empty());
+    }
+
     #[test]
     fn array_join_multiple() {
         check("SELECT * FROM t ARRAY JOIN arr1, arr2", expect![[r#"
@@ -2580,6 +3646,112 @@ mod tests {
         "#]]);
     }
 
+    #[test]
+    fn duplicate_where_clause() {
+        check("SELECT a FROM t WHERE x = 1 WHERE y = 2", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'a'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                WhereClause
+                  'WHERE'
+                  BinaryExpression
+                    ColumnReference
+                      'x'
+                    '='
+                    NumberLiteral
+                      '1'
+                Error
+                WhereClause
+                  'WHERE'
+                  BinaryExpression
+                    ColumnReference
+                      'y'
+                    '='
+                    NumberLiteral
+                      '2'
+        "#]]);
+    }
+
+    #[test]
+    fn output_clauses_in_canonical_order() {
+        check("SELECT a FROM t SETTINGS x = 1 INTO OUTFILE 'out.csv' AND STDOUT FORMAT CSV SETTINGS y = 2", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'a'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                SettingsClause
+                  'SETTINGS'
+                  SettingItem
+                    'x'
+                    '='
+                    NumberLiteral
+                      '1'
+                IntoOutfileClause
+                  'INTO'
+                  'OUTFILE'
+                  ''out.csv''
+                  'AND'
+                  'STDOUT'
+                FormatClause
+                  'FORMAT'
+                  'CSV'
+                SettingsClause
+                  'SETTINGS'
+                  SettingItem
+                    'y'
+                    '='
+                    NumberLiteral
+                      '2'
+        "#]]);
+    }
+
+    #[test]
+    fn limit_offset_with_ties_after_order_by() {
+        check("SELECT a FROM t ORDER BY a LIMIT 5, 10 WITH TIES", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'a'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                OrderByClause
+                  'ORDER'
+                  'BY'
+                  OrderByItem
+                    ColumnReference
+                      'a'
+                LimitClause
+                  'LIMIT'
+                  NumberLiteral
+                    '5'
+                  ','
+                  NumberLiteral
+                    '10'
+                  'WITH'
+                  'TIES'
+        "#]]);
+    }
+
     #[test]
     fn except_set_operation_after_settings() {
         let result = parse("SELECT a FROM t SETTINGS min_hit_rate = 1.0 EXCEPT SELECT a FROM t2");
@@ -2625,4 +3797,88 @@ mod tests {
         assert!(buf.matches("ColumnTransformer").count() >= 2, "should have chained transformers: {}", buf);
     }
 
+    #[test]
+    fn keyword_alias_after_as() {
+        check("SELECT x AS select", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'x'
+                    ColumnAlias
+                      'AS'
+                      'select'
+        "#]]);
+    }
+
+    #[test]
+    fn tuple_alias_list() {
+        check("SELECT f(x) AS (a, b), y AS c FROM t", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    FunctionCall
+                      Identifier
+                        'f'
+                      ExpressionList
+                        '('
+                        Expression
+                          ColumnReference
+                            'x'
+                        ')'
+                    ColumnAlias
+                      'AS'
+                      IdentifierList
+                        '('
+                        'a'
+                        ','
+                        'b'
+                        ')'
+                    ','
+                    ColumnReference
+                      'y'
+                    ColumnAlias
+                      'AS'
+                      'c'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+        "#]]);
+    }
+
+    #[test]
+    fn keyword_after_as_starting_clause_is_not_alias() {
+        let result = parse("SELECT a AS FROM t");
+        let messages: Vec<&str> = result.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["Expected column alias"]);
+    }
+
+    #[test]
+    fn missing_alias_after_as_keeps_from_clause() {
+        let result = parse("SELECT x AS, y AS FROM t");
+        let messages: Vec<&str> = result.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["Expected column alias", "Expected column alias"]);
+
+        check("SELECT x AS FROM t", expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'x'
+                    ColumnAlias
+                      'AS'
+                      Error
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+        "#]]);
+    }
 }
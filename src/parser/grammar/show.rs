@@ -148,9 +148,7 @@ pub fn parse_describe_statement(p: &mut Parser) {
     let m = p.start();
 
     // DESCRIBE or DESC
-    if p.at_keyword(Keyword::Describe) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Desc) {
+    if p.at_keyword(Keyword::Describe) || p.at_keyword(Keyword::Desc) {
         p.advance();
     }
 
@@ -180,9 +178,7 @@ pub fn parse_describe_statement(p: &mut Parser) {
     }
 
     // Optional FORMAT clause
-    if p.at_keyword(Keyword::Format) {
-        parse_format_clause(p);
-    }
+    common::parse_optional_format_clause(p);
 
     p.complete(m, SyntaxKind::DescribeStatement);
 }
@@ -232,6 +228,9 @@ pub fn parse_show_statement(p: &mut Parser) {
         p.recover_with_error("Expected target after SHOW");
     }
 
+    // Any SHOW variant can end in FORMAT
+    common::parse_optional_format_clause(p);
+
     p.complete(m, SyntaxKind::ShowStatement);
 }
 
@@ -324,10 +323,6 @@ fn parse_show_create(p: &mut Parser) {
         p.advance();
     }
 
-    if p.at_keyword(Keyword::Format) {
-        parse_format_clause(p);
-    }
-
     p.complete(m, SyntaxKind::ShowTarget);
 }
 
@@ -367,10 +362,6 @@ fn parse_show_processlist(p: &mut Parser) {
     let m = p.start();
     p.advance(); // PROCESSLIST
 
-    if p.at_keyword(Keyword::Format) {
-        parse_format_clause(p);
-    }
-
     p.complete(m, SyntaxKind::ShowTarget);
 }
 
@@ -461,9 +452,7 @@ fn parse_show_settings(p: &mut Parser) {
 fn parse_like_clause(p: &mut Parser) {
     let m = p.start();
 
-    if p.at_keyword(Keyword::Like) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Ilike) {
+    if p.at_keyword(Keyword::Like) || p.at_keyword(Keyword::Ilike) {
         p.advance();
     }
 
@@ -476,20 +465,6 @@ fn parse_like_clause(p: &mut Parser) {
     p.complete(m, SyntaxKind::LikeClause);
 }
 
-/// FORMAT format_name
-fn parse_format_clause(p: &mut Parser) {
-    let m = p.start();
-    p.expect_keyword(Keyword::Format);
-
-    if p.at_identifier() {
-        p.advance();
-    } else {
-        p.recover_with_error("Expected format name after FORMAT");
-    }
-
-    p.complete(m, SyntaxKind::FormatClause);
-}
-
 /// FROM db_name (used in SHOW TABLES FROM db, SHOW DICTIONARIES FROM db)
 fn parse_from_database(p: &mut Parser) {
     let m = p.start();
@@ -595,10 +570,8 @@ fn parse_settings_list(p: &mut Parser) {
         && !at_show_statement(p)
         && !at_describe_statement(p)
     {
-        if !first {
-            if !p.eat(SyntaxKind::Comma) {
-                break;
-            }
+        if !first && !p.eat(SyntaxKind::Comma) {
+            break;
         }
         first = false;
 
@@ -842,6 +815,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn describe_format_tsv() {
+        check(
+            "DESCRIBE t FORMAT TSV",
+            expect![[r#"
+                File
+                  DescribeStatement
+                    'DESCRIBE'
+                    TableIdentifier
+                      't'
+                    FormatClause
+                      'FORMAT'
+                      'TSV'
+            "#]],
+        );
+    }
+
     #[test]
     fn describe_table_function() {
         check(
@@ -891,6 +881,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn show_tables_format() {
+        check(
+            "SHOW TABLES FORMAT JSON",
+            expect![[r#"
+                File
+                  ShowStatement
+                    'SHOW'
+                    ShowTarget
+                      'TABLES'
+                    FormatClause
+                      'FORMAT'
+                      'JSON'
+            "#]],
+        );
+    }
+
+    #[test]
+    fn show_with_format_after_every_target() {
+        check_no_errors("SHOW TABLES FROM db LIKE 'a%' LIMIT 5 FORMAT JSON");
+        check_no_errors("SHOW DATABASES FORMAT TSV");
+        check_no_errors("SHOW GRANTS FOR u FORMAT Vertical");
+        check_no_errors("SHOW SETTINGS LIKE 'max%' FORMAT JSONEachRow");
+    }
+
     #[test]
     fn show_tables_from_db() {
         check(
@@ -141,15 +141,7 @@ pub fn parse_drop_statement(p: &mut Parser) {
 
     // Object kind: TABLE, DATABASE, VIEW, DICTIONARY, FUNCTION
     // TABLE is optional for DROP TABLE
-    if p.at_keyword(Keyword::Table) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Database) {
-        p.advance();
-    } else if p.at_keyword(Keyword::View) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Dictionary) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Function) {
+    if common::at_any_keyword(p, &[Keyword::Table, Keyword::Database, Keyword::View, Keyword::Dictionary, Keyword::Function]) {
         p.advance();
     }
     // If none matched, that's ok -- DROP [IF EXISTS] name is valid shorthand
@@ -264,17 +256,12 @@ pub fn parse_exists_statement(p: &mut Parser) {
     let _ = p.eat_keyword(Keyword::Temporary);
 
     // Optional object type keyword
-    if p.at_keyword(Keyword::Table) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Database) {
-        p.advance();
-    } else if p.at_keyword(Keyword::View) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Dictionary) {
+    if common::at_any_keyword(p, &[Keyword::Table, Keyword::Database, Keyword::View, Keyword::Dictionary]) {
         p.advance();
     }
 
     common::parse_table_identifier(p);
+    common::parse_optional_format_clause(p);
 
     p.complete(m, SyntaxKind::ExistsStatement);
 }
@@ -362,9 +349,7 @@ pub fn parse_attach_statement(p: &mut Parser) {
     p.expect_keyword(Keyword::Attach);
 
     // Object kind: TABLE, DATABASE
-    if p.at_keyword(Keyword::Table) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Database) {
+    if p.at_keyword(Keyword::Table) || p.at_keyword(Keyword::Database) {
         p.advance();
     }
 
@@ -390,9 +375,7 @@ pub fn parse_detach_statement(p: &mut Parser) {
     p.expect_keyword(Keyword::Detach);
 
     // Object kind: TABLE, DATABASE
-    if p.at_keyword(Keyword::Table) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Database) {
+    if p.at_keyword(Keyword::Table) || p.at_keyword(Keyword::Database) {
         p.advance();
     }
 
@@ -465,9 +448,7 @@ pub fn parse_backup_statement(p: &mut Parser) {
     p.expect_keyword(Keyword::Backup);
 
     // Object kind: TABLE, DATABASE
-    if p.at_keyword(Keyword::Table) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Database) {
+    if p.at_keyword(Keyword::Table) || p.at_keyword(Keyword::Database) {
         p.advance();
     }
 
@@ -511,9 +492,7 @@ pub fn parse_restore_statement(p: &mut Parser) {
     p.expect_keyword(Keyword::Restore);
 
     // Object kind: TABLE, DATABASE
-    if p.at_keyword(Keyword::Table) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Database) {
+    if p.at_keyword(Keyword::Table) || p.at_keyword(Keyword::Database) {
         p.advance();
     }
 
@@ -866,9 +845,7 @@ pub fn parse_system_statement(p: &mut Parser) {
     if is_flush_logs {
         // FLUSH LOGS can have comma-separated log target list: query_log, trace_log
         while !p.end_of_statement() {
-            if p.at_identifier() {
-                p.advance();
-            } else if p.at(SyntaxKind::Comma) {
+            if p.at_identifier() || p.at(SyntaxKind::Comma) {
                 p.advance();
             } else {
                 break;
@@ -907,9 +884,7 @@ pub fn parse_kill_statement(p: &mut Parser) {
 
     // QUERY or MUTATION — wrap in KillTarget
     let target = p.start();
-    if p.at_keyword(Keyword::Query) {
-        p.advance();
-    } else if p.at_keyword(Keyword::Mutation) {
+    if p.at_keyword(Keyword::Query) || p.at_keyword(Keyword::Mutation) {
         p.advance();
     } else {
         p.recover_with_error("Expected QUERY or MUTATION after KILL");
@@ -1337,6 +1312,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_exists_with_format() {
+        check(
+            "EXISTS TABLE t FORMAT JSON",
+            expect![[r#"
+                File
+                  ExistsStatement
+                    'EXISTS'
+                    'TABLE'
+                    TableIdentifier
+                      't'
+                    FormatClause
+                      'FORMAT'
+                      'JSON'
+            "#]],
+        );
+    }
+
     #[test]
     fn test_exists_database() {
         check(
@@ -1704,6 +1697,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_grant_columns_to_users() {
+        check(
+            "GRANT SELECT(a, b), INSERT ON db.t TO alice, bob WITH GRANT OPTION",
+            expect![[r#"
+                File
+                  GrantStatement
+                    'GRANT'
+                    PrivilegeList
+                      Privilege
+                        'SELECT'
+                        '('
+                        'a'
+                        ','
+                        'b'
+                        ')'
+                      ','
+                      Privilege
+                        'INSERT'
+                    GrantTarget
+                      'ON'
+                      'db'
+                      '.'
+                      't'
+                    'TO'
+                    'alice'
+                    ','
+                    'bob'
+                    'WITH'
+                    'GRANT'
+                    'OPTION'
+            "#]],
+        );
+    }
+
+    #[test]
+    fn test_create_user_identified_by() {
+        check(
+            "CREATE USER alice IDENTIFIED BY 'secret'",
+            expect![[r#"
+                File
+                  CreateStatement
+                    'CREATE'
+                    CreateUserStatement
+                      'USER'
+                      'alice'
+                      'IDENTIFIED'
+                      'BY'
+                      ''secret''
+            "#]],
+        );
+    }
+
     #[test]
     fn test_revoke_select() {
         check(
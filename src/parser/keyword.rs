@@ -3,8 +3,10 @@
 /// The lexer emits all identifiers as `SyntaxKind::BareWord`. The parser uses
 /// `Parser::at_keyword()` with case-insensitive comparison to distinguish
 /// keywords from regular identifiers. This enum lists every keyword the
-/// parser currently needs to recognize.
-#[derive(Debug, Copy, Clone)]
+/// parser currently needs to recognize, and is the one keyword list shared by
+/// the lexer ([`Token::is_keyword`](crate::lexer::token::Token::is_keyword)),
+/// the formatter and highlighting.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum Keyword {
     // Clauses
@@ -320,9 +322,98 @@ pub enum Keyword {
     Setting,
     Reset,
     Option,
+    Outfile,
+    Stdout,
+    Append,
+    Compression,
+    Level,
+    Collate,
+    None,
 }
 
+/// Keywords that start a clause, a join or a set operation, or that give an
+/// expression its structure (`CASE ... END`, `AND`, `NOT`), so they can't be
+/// used as a bare column name: `SELECT from FROM t` and `SELECT a, case FROM t`
+/// are errors, while non-reserved keywords such as `key`, `date`, `any` or
+/// `offset` name columns wherever an expression is expected. Reserved words
+/// can still be function names (`left(s, 2)`, `and(a, b)`) and can always be
+/// quoted; as aliases they parse but draw a warning.
+const RESERVED: &[Keyword] = &[
+    Keyword::Select, Keyword::From, Keyword::Where, Keyword::Prewhere, Keyword::Group,
+    Keyword::Having, Keyword::Order, Keyword::Limit, Keyword::Settings, Keyword::Format,
+    Keyword::Union, Keyword::Except, Keyword::Intersect, Keyword::Window, Keyword::Sample,
+    Keyword::Qualify, Keyword::Join, Keyword::Inner, Keyword::Cross, Keyword::Natural,
+    Keyword::Left, Keyword::Right, Keyword::Full, Keyword::Global, Keyword::Array,
+    Keyword::With, Keyword::As, Keyword::On, Keyword::Using, Keyword::By, Keyword::Case,
+    Keyword::When, Keyword::Then, Keyword::Else, Keyword::End, Keyword::And, Keyword::Or,
+    Keyword::Not, Keyword::Distinct, Keyword::Null, Keyword::True, Keyword::False,
+    Keyword::Interval,
+];
+
 impl Keyword {
+    /// Every keyword, in declaration order.
+    pub const ALL: &'static [Keyword] = &[
+        Keyword::Select, Keyword::From, Keyword::Where, Keyword::Order, Keyword::By, Keyword::Group,
+        Keyword::Having, Keyword::Limit, Keyword::Offset, Keyword::With, Keyword::As, Keyword::On,
+        Keyword::Using, Keyword::Between, Keyword::In, Keyword::Like, Keyword::Ilike, Keyword::Is,
+        Keyword::Not, Keyword::Case, Keyword::When, Keyword::Then, Keyword::Else, Keyword::End,
+        Keyword::Cast, Keyword::Distinct, Keyword::All, Keyword::Exists, Keyword::And, Keyword::Or,
+        Keyword::Join, Keyword::Inner, Keyword::Left, Keyword::Right, Keyword::Full, Keyword::Outer,
+        Keyword::Cross, Keyword::Global, Keyword::Any, Keyword::Semi, Keyword::Anti, Keyword::Asof,
+        Keyword::Natural, Keyword::Array, Keyword::Final, Keyword::Asc, Keyword::Desc,
+        Keyword::Nulls, Keyword::First, Keyword::Last, Keyword::Totals, Keyword::Rollup,
+        Keyword::Cube, Keyword::Union, Keyword::Except, Keyword::Intersect, Keyword::Insert,
+        Keyword::Into, Keyword::Values, Keyword::Delete, Keyword::Update, Keyword::Set,
+        Keyword::Create, Keyword::Alter, Keyword::Drop, Keyword::Detach, Keyword::Attach,
+        Keyword::Rename, Keyword::Truncate, Keyword::Show, Keyword::Use, Keyword::Optimize,
+        Keyword::System, Keyword::Exchange, Keyword::Undrop, Keyword::Table, Keyword::View,
+        Keyword::Database, Keyword::Dictionary, Keyword::Function, Keyword::Materialized,
+        Keyword::Temporary, Keyword::If, Keyword::Replace, Keyword::Live, Keyword::Default,
+        Keyword::Codec, Keyword::Ttl, Keyword::Comment, Keyword::Primary, Keyword::Key,
+        Keyword::Alias, Keyword::Ephemeral, Keyword::Prewhere, Keyword::Settings, Keyword::Format,
+        Keyword::Sample, Keyword::Null, Keyword::True, Keyword::False, Keyword::Interval,
+        Keyword::Engine, Keyword::Partition, Keyword::Cluster, Keyword::To, Keyword::Populate,
+        Keyword::Empty, Keyword::Permanently, Keyword::After, Keyword::Column, Keyword::Index,
+        Keyword::Projection, Keyword::Constraint, Keyword::Add, Keyword::Modify, Keyword::Clear,
+        Keyword::Move, Keyword::Granularity, Keyword::Type, Keyword::Deduplicate, Keyword::Explain,
+        Keyword::Describe, Keyword::Ast, Keyword::Plan, Keyword::Pipeline, Keyword::Estimate,
+        Keyword::QueryTree, Keyword::Tables, Keyword::Databases, Keyword::Columns,
+        Keyword::Dictionaries, Keyword::Functions, Keyword::Processlist, Keyword::Privileges,
+        Keyword::Grants, Keyword::Reload, Keyword::Flush, Keyword::Stop, Keyword::Start,
+        Keyword::Merges, Keyword::Replica, Keyword::Replicas, Keyword::Distributed,
+        Keyword::Sending, Keyword::Fetches, Keyword::Moves, Keyword::Logs, Keyword::Cache,
+        Keyword::Dns, Keyword::Mark, Keyword::Uncompressed, Keyword::Compiled, Keyword::Models,
+        Keyword::Disks, Keyword::FilesystemCache, Keyword::Grant, Keyword::Revoke, Keyword::User,
+        Keyword::Role, Keyword::Quota, Keyword::Policy, Keyword::Profile, Keyword::Row,
+        Keyword::Kill, Keyword::Query, Keyword::Mutation, Keyword::Sync, Keyword::Async,
+        Keyword::Test, Keyword::Check, Keyword::Assume, Keyword::Begin, Keyword::Commit,
+        Keyword::Rollback, Keyword::Transaction, Keyword::Backup, Keyword::Restore, Keyword::Config,
+        Keyword::Sends, Keyword::Replicated, Keyword::Min, Keyword::Max, Keyword::Local,
+        Keyword::Freeze, Keyword::Unfreeze, Keyword::Fetch, Keyword::Apply, Keyword::Deleted,
+        Keyword::Source, Keyword::Layout, Keyword::Lifetime, Keyword::Range, Keyword::Hashed,
+        Keyword::Flat, Keyword::Complex, Keyword::Direct, Keyword::Injective, Keyword::Hierarchical,
+        Keyword::Window, Keyword::Over, Keyword::Rows, Keyword::Groups, Keyword::Unbounded,
+        Keyword::Preceding, Keyword::Following, Keyword::Current, Keyword::Isnull, Keyword::Div,
+        Keyword::Mod, Keyword::Fill, Keyword::Step, Keyword::Interpolate, Keyword::Identified,
+        Keyword::Host, Keyword::Keyed, Keyword::Recursive, Keyword::Skip, Keyword::Ignore,
+        Keyword::Respect, Keyword::Grouping, Keyword::Sets, Keyword::Id, Keyword::Ties,
+        Keyword::Qualify, Keyword::Filter, Keyword::Cleanup, Keyword::Syntax, Keyword::Tree,
+        Keyword::Override, Keyword::Engines, Keyword::For, Keyword::Part, Keyword::Materialize,
+        Keyword::Setting, Keyword::Reset, Keyword::Option, Keyword::Outfile, Keyword::Stdout,
+        Keyword::Append, Keyword::Compression, Keyword::Level, Keyword::Collate,
+        Keyword::None,
+    ];
+
+    /// See [`RESERVED`].
+    pub fn is_reserved(self) -> bool {
+        RESERVED.contains(&self)
+    }
+
+    /// The keyword spelled `text`, in any case.
+    pub fn from_text(text: &str) -> Option<Keyword> {
+        Keyword::ALL.iter().copied().find(|keyword| keyword.as_str().eq_ignore_ascii_case(text))
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Keyword::Select => "SELECT",
@@ -560,7 +651,75 @@ impl Keyword {
             Keyword::Setting => "SETTING",
             Keyword::Reset => "RESET",
             Keyword::Option => "OPTION",
+            Keyword::Outfile => "OUTFILE",
+            Keyword::Stdout => "STDOUT",
+            Keyword::Append => "APPEND",
+            Keyword::Compression => "COMPRESSION",
+            Keyword::Level => "LEVEL",
+            Keyword::Collate => "COLLATE",
+            Keyword::None => "NONE",
             Keyword::Cleanup => "CLEANUP",
         }
     }
 }
+
+/// Keywords that can't be bare column names, upper-case.
+pub fn reserved_keywords() -> Vec<&'static str> {
+    RESERVED.iter().map(Keyword::as_str).collect()
+}
+
+/// Keywords that the parser also accepts as bare column names, upper-case.
+pub fn non_reserved_keywords() -> Vec<&'static str> {
+    Keyword::ALL
+        .iter()
+        .filter(|keyword| !keyword.is_reserved())
+        .map(Keyword::as_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::syntax_kind::SyntaxKind;
+
+    #[test]
+    fn reserved_and_non_reserved_partition_all_keywords() {
+        let reserved = reserved_keywords();
+        let non_reserved = non_reserved_keywords();
+        assert_eq!(reserved.len() + non_reserved.len(), Keyword::ALL.len());
+        assert!(reserved.iter().all(|word| !non_reserved.contains(word)));
+        assert!(reserved.contains(&"FROM") && reserved.contains(&"JOIN"));
+        assert!(non_reserved.contains(&"KEY") && non_reserved.contains(&"ANY"));
+    }
+
+    #[test]
+    fn only_non_reserved_keywords_are_column_names() {
+        // Whether `SELECT a, <word> FROM t` parses, and its column names.
+        let columns = |word: &str| {
+            let sql = format!("SELECT a, {} FROM t", word.to_lowercase());
+            let result = crate::parser::parse(&sql);
+            let names: Vec<String> = result
+                .tree
+                .find_all(SyntaxKind::ColumnReference)
+                .iter()
+                .map(|column| column.to_source(&sql).trim().to_uppercase())
+                .collect();
+            (result.errors.is_empty(), names)
+        };
+        for word in non_reserved_keywords() {
+            assert_eq!(columns(word), (true, vec!["A".to_string(), word.to_string()]));
+        }
+        // Reserved words fail to parse or read as something else, such as the
+        // NULL literal or a `SELECT FROM t` subquery.
+        for word in reserved_keywords() {
+            assert!(!columns(word).1.iter().any(|name| name == word), "{word}");
+        }
+    }
+
+    #[test]
+    fn from_text_ignores_case() {
+        assert_eq!(Keyword::from_text("select"), Some(Keyword::Select));
+        assert_eq!(Keyword::from_text("PreWhere"), Some(Keyword::Prewhere));
+        assert_eq!(Keyword::from_text("my_col"), None);
+    }
+}
@@ -10,13 +10,224 @@ pub mod syntax_kind;
 pub mod syntax_tree;
 pub(crate) mod token_set;
 
-use crate::lexer::tokenizer::tokenize_with_whitespace;
-use crate::parser::diagnostic::Parse;
+use crate::lexer::token::Token;
+use crate::lexer::tokenizer::{
+    tokenize_with_max_query_size, tokenize_with_whitespace, DEFAULT_MAX_QUERY_SIZE,
+};
+use crate::parser::diagnostic::{Parse, StatementParse};
+use crate::parser::syntax_kind::SyntaxKind;
+use crate::parser::syntax_tree::{SyntaxChild, SyntaxTree};
+
+/// Options for [`parse_with_options`].
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// Keywords to read as plain identifiers, e.g. `["interval", "left"]` for
+    /// a schema with columns of those names. Matching is case-insensitive.
+    ///
+    /// A listed word is never recognized as a keyword, so only list words the
+    /// queries don't also use in their keyword role (`LEFT JOIN` stops
+    /// parsing once `left` is listed).
+    pub identifier_keywords: Vec<String>,
+    /// Stop after this many syntax errors. The parser then skips the rest of
+    /// the input as one Error node and reports a final "Too many errors"
+    /// error in place of everything it would have found there.
+    pub max_errors: Option<usize>,
+    /// Abort at the first statement that doesn't start with a known keyword.
+    /// By default (tolerant) the parser reports the stray tokens and resumes
+    /// at the next statement, which suits editors; strict mode reports one
+    /// "Unknown statement" error and skips the rest of the input as one Error
+    /// node, which suits CI checks where nothing after it can be trusted.
+    pub strict: bool,
+    /// Reject input longer than this many bytes with a single error, or
+    /// accept any length with `None`. Defaults to
+    /// [`DEFAULT_MAX_QUERY_SIZE`](crate::DEFAULT_MAX_QUERY_SIZE).
+    pub max_query_size: Option<usize>,
+    /// Stop after consuming this many tokens, whitespace and comments
+    /// included, so an interactive caller can bail out on pathological input
+    /// instead of blocking on it. The rest of the input becomes one Error
+    /// node, and a final "Token budget exceeded" error spans it.
+    pub max_tokens: Option<usize>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            identifier_keywords: Vec::new(),
+            max_errors: None,
+            strict: false,
+            max_query_size: Some(DEFAULT_MAX_QUERY_SIZE),
+            max_tokens: None,
+        }
+    }
+}
 
 pub fn parse(text: &str) -> Parse {
-    let tokens = tokenize_with_whitespace(text);
-    let source = text.to_string();
-    let mut p = parser::Parser::new(tokens, source);
+    parse_with_options(text, &ParseOptions::default())
+}
+
+pub fn parse_with_options(text: &str, options: &ParseOptions) -> Parse {
+    let tokens = tokenize_with_max_query_size(text, options.max_query_size);
+    parse_tokens(tokens, text, options)
+}
+
+/// Like [`parse_with_options`], but also return the token stream, whitespace
+/// and comments included, so callers that need both don't tokenize twice.
+pub fn parse_with_tokens(text: &str, options: &ParseOptions) -> (Parse, Vec<Token>) {
+    let tokens = tokenize_with_max_query_size(text, options.max_query_size);
+    (parse_tokens(tokens.clone(), text, options), tokens)
+}
+
+fn parse_tokens(tokens: Vec<Token>, text: &str, options: &ParseOptions) -> Parse {
+    let mut p = parser::Parser::new(tokens, text.to_string());
+    p.set_identifier_keywords(&options.identifier_keywords);
+    p.set_max_errors(options.max_errors);
+    p.set_strict(options.strict);
+    p.set_token_budget(options.max_tokens);
     grammar::parse_source(&mut p);
     p.build_tree()
 }
+
+/// Parse a standalone filter condition, such as one typed into a UI, into a
+/// `WhereClause` under the `File` root. A leading `WHERE` is optional.
+pub fn parse_where_clause(text: &str) -> Parse {
+    parse_fragment(text, grammar::parse_where_fragment)
+}
+
+/// Parse a standalone SELECT list, e.g. `a, b AS x, *`, into a `ColumnList`
+/// under the `File` root.
+pub fn parse_column_list(text: &str) -> Parse {
+    parse_fragment(text, grammar::parse_column_list_fragment)
+}
+
+fn parse_fragment(text: &str, entry: fn(&mut parser::Parser)) -> Parse {
+    let tokens = tokenize_with_whitespace(text);
+    let mut p = parser::Parser::new(tokens, text.to_string());
+    entry(&mut p);
+    p.build_tree()
+}
+
+/// Parse a script and return each top-level statement separately, with its
+/// span in the source and the errors that fall inside it. Statement
+/// boundaries are the ones `parse` already finds, so the trees are identical
+/// to the children of the `File` node.
+///
+/// Stray tokens that belong to no statement, such as the `)` in
+/// `SELECT 1 ) ; SELECT 2`, are merged into the statement of the same
+/// `;`-separated segment: its range grows to cover them and it gets their
+/// errors. A segment with no statement, such as `FROBNICATE t;`, is returned
+/// as its own `Error` entry. Every error of the parse ends up in exactly one
+/// entry.
+pub fn parse_statements(text: &str) -> Vec<StatementParse> {
+    let Parse { tree, errors, .. } = parse(text);
+
+    let mut statements: Vec<StatementParse> = Vec::new();
+    // Error nodes of the current segment not yet merged into a statement.
+    let mut stray: Vec<SyntaxTree> = Vec::new();
+    let mut segment_start = 0;
+    for child in tree.children {
+        let stmt = match child {
+            SyntaxChild::Tree(stmt) => stmt,
+            SyntaxChild::Token(token) => {
+                if matches!(token.kind, SyntaxKind::Semicolon | SyntaxKind::VerticalDelimiter) {
+                    statements.extend(stray.drain(..).map(error_entry));
+                    if token.kind == SyntaxKind::VerticalDelimiter
+                        && statements.len() > segment_start
+                    {
+                        statements.last_mut().unwrap().vertical = true;
+                    }
+                    segment_start = statements.len();
+                }
+                continue;
+            }
+        };
+        let Some((_, end)) = stmt.significant_span() else { continue };
+        let in_segment = statements.len() > segment_start;
+        if stmt.kind == SyntaxKind::Error {
+            match statements.last_mut() {
+                Some(last) if in_segment => last.range.1 = end as usize,
+                _ => stray.push(stmt),
+            }
+            continue;
+        }
+        let start = stray.first().map_or(stmt.start, |first| first.start);
+        stray.clear();
+        let range = (start as usize, end as usize);
+        statements.push(StatementParse { tree: stmt, range, errors: Vec::new(), vertical: false });
+    }
+    statements.extend(stray.into_iter().map(error_entry));
+
+    // An error belongs to the statement whose range contains it. One past
+    // the end, such as a missing token at the end of input, goes to the
+    // closest statement before it.
+    for error in errors {
+        let at = error.range.0;
+        let index = statements
+            .iter()
+            .position(|s| s.range.0 <= at && at < s.range.1)
+            .or_else(|| statements.iter().rposition(|s| s.range.0 <= at))
+            .unwrap_or(0);
+        if let Some(statement) = statements.get_mut(index) {
+            statement.errors.push(error);
+        }
+    }
+    statements
+}
+
+/// A top-level `Error` node with no statement in its segment, as its own entry.
+fn error_entry(tree: SyntaxTree) -> StatementParse {
+    let (start, end) = tree.significant_span().unwrap_or(tree.span());
+    let range = (start as usize, end as usize);
+    StatementParse { tree, range, errors: Vec::new(), vertical: false }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_with_tokens_returns_the_full_token_stream() {
+        let sql = "SELECT a, -- note\n b FROM t WHERE x = 'y';";
+        let (result, tokens) = parse_with_tokens(sql, &ParseOptions::default());
+        assert_eq!(tokens.len(), tokenize_with_whitespace(sql).len());
+        assert_eq!(tokens.len(), result.tree.tokens_in_order().len());
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
+
+    #[test]
+    fn parse_with_tokens_applies_options() {
+        let sql = "SELECT 1; FROBNICATE t; SELECT 2";
+        let options = ParseOptions { strict: true, ..Default::default() };
+        let (result, tokens) = parse_with_tokens(sql, &options);
+        assert_eq!(result.errors, parse_with_options(sql, &options).errors);
+        assert_eq!(result.tree.find_all(SyntaxKind::SelectStatement).len(), 1);
+        assert_eq!(tokens.len(), tokenize_with_whitespace(sql).len());
+    }
+
+    #[test]
+    fn max_tokens_option_stops_on_large_input() {
+        let sql = "SELECT 1 + 2 + 3 FROM t;\n".repeat(1000);
+        let limited = ParseOptions { max_tokens: Some(100), ..Default::default() };
+        let result = parse_with_options(&sql, &limited);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].message, "Token budget exceeded, stopped parsing");
+        assert_eq!(result.errors[0].range.1, sql.len());
+        assert_eq!(result.tree.to_source(&sql), sql);
+
+        let tokens = tokenize_with_whitespace(&sql).len();
+        let enough = ParseOptions { max_tokens: Some(tokens), ..Default::default() };
+        let result = parse_with_options(&sql, &enough);
+        assert_eq!(result.tree.to_source(&sql), sql);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+    }
+
+    #[test]
+    fn max_query_size_option() {
+        let sql = "SELECT 1";
+        let limited = ParseOptions { max_query_size: Some(4), ..Default::default() };
+        let result = parse_with_options(sql, &limited);
+        assert_eq!(result.errors[0].found, Some(SyntaxKind::ErrorMaxQuerySizeExceeded));
+
+        let unlimited = ParseOptions { max_query_size: None, ..Default::default() };
+        assert!(parse_with_options(sql, &unlimited).errors.is_empty());
+    }
+}
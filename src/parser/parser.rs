@@ -17,6 +17,18 @@ pub struct Parser {
     events: Vec<Event>,
     errors: Vec<SyntaxError>,
     source: String,
+    /// Keywords (uppercase) that `at_keyword` never matches, so they parse as
+    /// identifiers. See `ParseOptions::identifier_keywords`.
+    identifier_keywords: Vec<String>,
+    /// See `ParseOptions::max_errors`.
+    max_errors: Option<usize>,
+    /// Set once `max_errors` is hit; fuel is drained so the parser sees EOF.
+    stopped: bool,
+    /// See `ParseOptions::strict`.
+    strict: bool,
+    /// Tokens `advance` may still consume before the parse stops. See
+    /// `ParseOptions::max_tokens`.
+    token_budget: Option<usize>,
 }
 
 impl Parser {
@@ -28,9 +40,45 @@ impl Parser {
             events: Vec::new(),
             errors: Vec::new(),
             source,
+            identifier_keywords: Vec::new(),
+            max_errors: None,
+            stopped: false,
+            strict: false,
+            token_budget: None,
         }
     }
 
+    /// Stop after consuming `limit` tokens, trivia included. See
+    /// `ParseOptions::max_tokens`.
+    pub fn set_token_budget(&mut self, limit: Option<usize>) {
+        self.token_budget = limit;
+    }
+
+    /// Stop parsing after `limit` errors. See `ParseOptions::max_errors`.
+    pub fn set_max_errors(&mut self, limit: Option<usize>) {
+        self.max_errors = limit;
+    }
+
+    /// Abort at an unknown statement instead of recovering. See
+    /// `ParseOptions::strict`.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Treat the given words as identifiers even where the grammar would
+    /// accept them as keywords. Matching is case-insensitive.
+    pub fn set_identifier_keywords(&mut self, words: &[String]) {
+        self.identifier_keywords = words.iter().map(|w| w.to_ascii_uppercase()).collect();
+    }
+
+    fn is_identifier_keyword(&self, keyword: Keyword) -> bool {
+        self.identifier_keywords.iter().any(|w| w == keyword.as_str())
+    }
+
     /// Returns the byte offset range of the current token,
     /// or the end-of-input position if at EOF.
     fn current_range(&self) -> (usize, usize) {
@@ -44,11 +92,55 @@ impl Parser {
     }
 
     fn push_error(&mut self, message: impl Into<String>) {
+        self.push_error_expecting(message, None);
+    }
+
+    fn push_error_expecting(&mut self, message: impl Into<String>, expected: Option<SyntaxKind>) {
+        if self.stopped {
+            return;
+        }
         let range = self.current_range();
+        let found = self.tokens.get(self.pos).map(|token| token.kind);
+        if self.max_errors.is_some_and(|limit| self.errors.len() >= limit) {
+            self.stop("Too many errors, stopped parsing");
+            return;
+        }
         self.errors.push(SyntaxError {
             message: message.into(),
             range,
+            expected,
+            found,
+        });
+    }
+
+    /// Report one error from the current token to the end of the input and
+    /// stop parsing.
+    fn stop(&mut self, message: &str) {
+        let range = self.current_range();
+        let end = self.tokens.last().map_or(range.1, |last| last.end as usize);
+        self.errors.push(SyntaxError {
+            message: message.to_string(),
+            range: (range.0, end.max(range.0)),
+            expected: None,
+            found: self.tokens.get(self.pos).map(|token| token.kind),
         });
+        // Out of fuel, every lookahead reports EOF and all loops unwind.
+        self.stopped = true;
+        self.fuel.set(0);
+    }
+
+    /// After the error limit or the token budget stops the parser, wrap every token it never got
+    /// to in one Error node so the tree still covers the whole input.
+    pub fn skip_rest_after_error_limit(&mut self) {
+        if !self.stopped {
+            return;
+        }
+        let m = self.start();
+        while self.pos < self.tokens.len() {
+            self.events.push(Event::Advance);
+            self.pos += 1;
+        }
+        self.complete(m, SyntaxKind::Error);
     }
 
     pub fn build_tree(self) -> Parse {
@@ -65,19 +157,17 @@ impl Parser {
         for i in 0..event_count {
             if let Event::Open { forward_parent: Some(_), .. } = &events[i] {
                 let mut cur = i;
-                loop {
-                    match &events[cur] {
-                        Event::Open { forward_parent: Some(next), .. } => {
-                            opened_via_fp[*next as usize] = true;
-                            cur = *next as usize;
-                        }
-                        _ => break,
-                    }
+                while let Event::Open { forward_parent: Some(next), .. } = &events[cur] {
+                    opened_via_fp[*next as usize] = true;
+                    cur = *next as usize;
                 }
             }
         }
 
         let mut stack: Vec<SyntaxTree> = Vec::new();
+        // End of the last token attached so far, where a node without
+        // tokens sits.
+        let mut offset = 0;
         for i in 0..event_count {
             match &events[i] {
                 Event::Open { kind, forward_parent } => {
@@ -90,14 +180,9 @@ impl Parser {
                         // Collect the chain: self -> fp1 -> fp2 -> ... -> last
                         let mut chain = vec![i];
                         let mut cur = i;
-                        loop {
-                            match &events[cur] {
-                                Event::Open { forward_parent: Some(next), .. } => {
-                                    chain.push(*next as usize);
-                                    cur = *next as usize;
-                                }
-                                _ => break,
-                            }
+                        while let Event::Open { forward_parent: Some(next), .. } = &events[cur] {
+                            chain.push(*next as usize);
+                            cur = *next as usize;
                         }
                         // Open wrapper nodes from outermost (end of chain) to innermost,
                         // then the original node last
@@ -127,9 +212,13 @@ impl Parser {
                     if i == event_count - 1 {
                         continue;
                     }
-                    let Some(tree) = stack.pop() else {
+                    let Some(mut tree) = stack.pop() else {
                         continue;
                     };
+                    if tree.start > tree.end {
+                        tree.start = offset;
+                        tree.end = offset;
+                    }
                     let Some(parent) = stack.last_mut() else {
                         stack.push(tree);
                         continue;
@@ -147,6 +236,7 @@ impl Parser {
                     let Some(token) = tokens.next() else {
                         continue;
                     };
+                    offset = token.end;
                     let Some(parent) = stack.last_mut() else {
                         continue;
                     };
@@ -216,35 +306,23 @@ impl Parser {
             forward_parent: None,
         });
         // Point the original node's Open event to the new wrapper
-        match &mut self.events[m.index] {
-            Event::Open { forward_parent: _, .. } => {
-                // Follow any existing chain to the end
-                let mut target = m.index;
-                loop {
-                    match &self.events[target] {
-                        Event::Open { forward_parent: Some(next), .. } => {
-                            target = *next as usize;
-                        }
-                        _ => break,
-                    }
-                }
-                match &mut self.events[target] {
-                    Event::Open { forward_parent, .. } => {
-                        *forward_parent = Some(new_index as u32);
-                    }
-                    _ => {}
-                }
+        if let Event::Open { forward_parent: _, .. } = &mut self.events[m.index] {
+            // Follow any existing chain to the end
+            let mut target = m.index;
+            while let Event::Open { forward_parent: Some(next), .. } = &self.events[target] {
+                target = *next as usize;
+            }
+            if let Event::Open { forward_parent, .. } = &mut self.events[target] {
+                *forward_parent = Some(new_index as u32);
             }
-            _ => {}
         }
         Marker { index: new_index }
     }
 
     /// Retroactively change the SyntaxKind of an already-completed node.
     pub fn change_kind(&mut self, m: CompletedMarker, kind: SyntaxKind) {
-        match &mut self.events[m.index] {
-            Event::Open { kind: k, .. } => *k = kind,
-            _ => {}
+        if let Event::Open { kind: k, .. } = &mut self.events[m.index] {
+            *k = kind;
         }
     }
 
@@ -257,9 +335,8 @@ impl Parser {
     }
 
     pub fn complete(&mut self, m: Marker, kind: SyntaxKind) -> CompletedMarker {
-        match &mut self.events[m.index] {
-            Event::Open { kind: k, .. } => *k = kind,
-            _ => {}
+        if let Event::Open { kind: k, .. } = &mut self.events[m.index] {
+            *k = kind;
         }
         self.events.push(Event::Close);
         CompletedMarker { index: m.index }
@@ -275,6 +352,13 @@ impl Parser {
         if self.eof() {
             return;
         }
+        if let Some(budget) = &mut self.token_budget {
+            if *budget == 0 {
+                self.stop("Token budget exceeded, stopped parsing");
+                return;
+            }
+            *budget -= 1;
+        }
         self.fuel.set(FUEL_LIMIT);
         self.events.push(Event::Advance);
         self.pos += 1;
@@ -295,12 +379,48 @@ impl Parser {
         self.complete(m, SyntaxKind::Error);
     }
 
+    /// Wrap every token up to (not including) the next `;` in one Error node,
+    /// reporting a single error that spans all of them.
+    pub fn advance_to_semicolon_with_error(&mut self, error: &str) {
+        let m = self.start();
+        self.push_error(error);
+        let index = self.errors.len() - 1;
+        while !self.eof() && !self.at_statement_separator() {
+            self.errors[index].range.1 = self.current_range().1;
+            self.advance();
+        }
+        self.complete(m, SyntaxKind::Error);
+    }
+
+    /// Wrap every remaining token in one Error node, reporting a single error
+    /// that spans all of them.
+    pub fn advance_to_end_with_error(&mut self, error: &str) {
+        let m = self.start();
+        self.push_error(error);
+        let index = self.errors.len() - 1;
+        loop {
+            self.skip_trivia();
+            if self.eof() {
+                break;
+            }
+            self.errors[index].range.1 = self.current_range().1;
+            self.advance();
+        }
+        self.complete(m, SyntaxKind::Error);
+    }
+
     pub fn eof(&self) -> bool {
         self.pos == self.tokens.len() || self.fuel.get() == 0
     }
 
     pub fn end_of_statement(&mut self) -> bool {
-        self.at(SyntaxKind::ClosingRoundBracket) || self.at(SyntaxKind::Semicolon) || self.eof()
+        self.at(SyntaxKind::ClosingRoundBracket) || self.at_statement_separator() || self.eof()
+    }
+
+    /// `;`, or `\G` which also ends a statement and asks the client for
+    /// vertical output.
+    pub fn at_statement_separator(&mut self) -> bool {
+        self.at(SyntaxKind::Semicolon) || self.at(SyntaxKind::VerticalDelimiter)
     }
 
     pub fn nth(&mut self, lookahead: usize) -> SyntaxKind {
@@ -380,7 +500,7 @@ impl Parser {
         if self.eat(kind) {
             return;
         }
-        self.push_error(format!("expected {kind}"));
+        self.push_error_expecting(format!("expected {kind}"), Some(kind));
     }
 
     pub fn nth_text(&mut self, lookahead: usize) -> &str {
@@ -424,12 +544,26 @@ impl Parser {
     pub fn at_keyword(&mut self, keyword: Keyword) -> bool {
         self.nth(0) == SyntaxKind::BareWord
             && self.nth_text(0).eq_ignore_ascii_case(keyword.as_str())
+            && !self.is_identifier_keyword(keyword)
+    }
+
+    /// True if the current token is any of `keywords`. Reads the token once,
+    /// so a long list costs the same lookahead fuel as a single keyword.
+    pub fn at_any_keyword(&mut self, keywords: &[Keyword]) -> bool {
+        if self.nth(0) != SyntaxKind::BareWord {
+            return false;
+        }
+        let text = self.nth_text(0).to_ascii_uppercase();
+        keywords
+            .iter()
+            .any(|kw| text == kw.as_str() && !self.is_identifier_keyword(*kw))
     }
 
     /// True if the token at the given lookahead offset matches the given keyword.
     pub fn nth_keyword(&mut self, n: usize, keyword: Keyword) -> bool {
         self.nth(n) == SyntaxKind::BareWord
             && self.nth_text(n).eq_ignore_ascii_case(keyword.as_str())
+            && !self.is_identifier_keyword(keyword)
     }
 
     /// True if the current (non-trivia) token is followed by '('.
@@ -1,7 +1,7 @@
 use std::fmt;
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u16)]
 #[allow(dead_code)]
 pub enum SyntaxKind {
@@ -63,10 +63,12 @@ pub enum SyntaxKind {
     LimitClause,
     SettingsClause,
     FormatClause,
+    IntoOutfileClause,
     UnionClause,
     WindowClause,
     WindowDefinition,
     WindowFrame,
+    WindowFrameBound,
     WindowSpec,
     QualifyClause,
     SampleClause,
@@ -210,6 +212,7 @@ pub enum SyntaxKind {
     TypedJsonAccessExpression,
     MapExpression,
     QueryParameterExpression,
+    PositionalParameter,
     SubqueryExpression,
     LambdaExpression,
     IntervalExpression,
@@ -222,6 +225,7 @@ pub enum SyntaxKind {
     ColumnTransformer,
     GroupingSetsClause,
     GroupingSet,
+    GroupByAll,
     WithTotalsClause,
 
     // =======================================================================
@@ -351,6 +355,8 @@ pub enum SyntaxKind {
     Slash,
     Percent,
     Arrow,
+    /// `->>` (MySQL-compatible JSON extract, not a lambda)
+    JsonExtractArrow,
     QuestionMark,
     Colon,
     Caret,
@@ -378,6 +384,7 @@ pub enum SyntaxKind {
     ErrorSingleQuoteIsNotClosed,
     ErrorDoubleQuoteIsNotClosed,
     ErrorBackQuoteIsNotClosed,
+    ErrorHereDocIsNotClosed,
     ErrorSingleExclamationMark,
     ErrorSinglePipeMark,
     ErrorWrongNumber,
@@ -2,18 +2,21 @@ use crate::lexer::token::Token;
 use crate::parser::syntax_kind::SyntaxKind;
 use std::fmt::Write;
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone)]
 pub struct SyntaxTree {
     pub kind: SyntaxKind,
     pub children: Vec<SyntaxChild>,
-    /// Byte offset of the first token in this subtree (u32::MAX if empty).
+    /// Byte offset of the first token in this subtree. A parsed node without
+    /// tokens has `start == end` at its position in the source; a node built
+    /// with [`new`](Self::new) has `u32::MAX` until something is pushed.
     pub start: u32,
-    /// Byte offset of the end of the last token in this subtree (0 if empty).
+    /// Byte offset of the end of the last token in this subtree (0 until
+    /// something is pushed).
     pub end: u32,
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone)]
 pub enum SyntaxChild {
     Token(Token),
@@ -29,6 +32,26 @@ impl SyntaxChild {
         matches!(self, SyntaxChild::Tree(_))
     }
 
+    /// True for a whitespace or comment token.
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, SyntaxChild::Token(token)
+            if matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Comment))
+    }
+
+    pub fn as_token(&self) -> Option<&Token> {
+        match self {
+            SyntaxChild::Token(token) => Some(token),
+            SyntaxChild::Tree(_) => None,
+        }
+    }
+
+    pub fn as_tree(&self) -> Option<&SyntaxTree> {
+        match self {
+            SyntaxChild::Tree(tree) => Some(tree),
+            SyntaxChild::Token(_) => None,
+        }
+    }
+
     pub fn get_token_with_kind(&self, kind: SyntaxKind) -> Option<&Token> {
         match self {
             SyntaxChild::Token(token) if token.kind == kind => Some(token),
@@ -67,6 +90,246 @@ impl SyntaxChildExt for Option<&SyntaxChild> {
 }
 
 impl SyntaxTree {
+    /// An empty node of the given kind, for building trees by hand (codegen,
+    /// test fixtures). Fill it with [`push_token`](Self::push_token) and
+    /// [`push_tree`](Self::push_tree); `start`/`end` track the children the
+    /// same way the parser's tree builder does.
+    pub fn new(kind: SyntaxKind) -> Self {
+        SyntaxTree {
+            kind,
+            children: Vec::new(),
+            start: u32::MAX,
+            end: 0,
+        }
+    }
+
+    /// Append a token and widen this node's range to cover it.
+    pub fn push_token(&mut self, token: Token) -> &mut Self {
+        self.extend_range(token.start, token.end);
+        self.children.push(SyntaxChild::Token(token));
+        self
+    }
+
+    /// Append a subtree and widen this node's range to cover it. Empty
+    /// subtrees don't affect the range.
+    pub fn push_tree(&mut self, tree: SyntaxTree) -> &mut Self {
+        self.extend_range(tree.start, tree.end);
+        self.children.push(SyntaxChild::Tree(tree));
+        self
+    }
+
+    /// Replace the child at `index`, returning the old one, and recompute this
+    /// node's range from its children. Panics if `index` is out of bounds.
+    pub fn replace_child(&mut self, index: usize, child: SyntaxChild) -> SyntaxChild {
+        let old = std::mem::replace(&mut self.children[index], child);
+        self.recompute_range();
+        old
+    }
+
+    /// Replace the subtree at `path`, a list of child indexes from this node
+    /// down, returning the old one; ranges are recomputed along the path.
+    /// This is how an incremental reparse splices a freshly parsed statement
+    /// into an existing tree. The replacement's token offsets must refer to
+    /// the same source as the rest of the tree. Panics if `path` is empty or
+    /// doesn't lead through subtrees to an existing child.
+    pub fn graft(&mut self, path: &[usize], replacement: SyntaxTree) -> SyntaxChild {
+        let (&index, rest) = path.split_first().expect("graft path must not be empty");
+        if rest.is_empty() {
+            return self.replace_child(index, SyntaxChild::Tree(replacement));
+        }
+        let SyntaxChild::Tree(child) = &mut self.children[index] else {
+            panic!("graft path goes through a token at index {index}");
+        };
+        let old = child.graft(rest, replacement);
+        self.recompute_range();
+        old
+    }
+
+    fn recompute_range(&mut self) {
+        self.start = u32::MAX;
+        self.end = 0;
+        let ranges: Vec<(u32, u32)> = self
+            .children
+            .iter()
+            .map(|child| match child {
+                SyntaxChild::Token(token) => (token.start, token.end),
+                SyntaxChild::Tree(tree) => (tree.start, tree.end),
+            })
+            .collect();
+        for (start, end) in ranges {
+            self.extend_range(start, end);
+        }
+    }
+
+    fn extend_range(&mut self, start: u32, end: u32) {
+        if start > end {
+            // Empty subtree (start = u32::MAX, end = 0).
+            return;
+        }
+        if start < self.start {
+            self.start = start;
+        }
+        if end > self.end {
+            self.end = end;
+        }
+    }
+
+    /// Byte range `(start, end)` of this subtree, computed once when the tree
+    /// was built. An empty node built by hand reports `(0, 0)`.
+    pub fn span(&self) -> (u32, u32) {
+        if self.start > self.end {
+            return (0, 0);
+        }
+        (self.start, self.end)
+    }
+
+    /// Byte range from the first to the last token that isn't whitespace or a
+    /// comment. A parsed node's own range includes trailing trivia; this is
+    /// the range to report or highlight. `None` if there is no such token.
+    pub fn significant_span(&self) -> Option<(u32, u32)> {
+        let mut significant = self
+            .tokens_in_order()
+            .into_iter()
+            .filter(|token| !matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Comment));
+        let first = significant.next()?;
+        let last = significant.next_back().unwrap_or(first);
+        Some((first.start, last.end))
+    }
+
+    /// The source text covered by this subtree: every token's text (including
+    /// trivia) concatenated in order. For a parsed tree this reproduces the
+    /// input exactly.
+    pub fn to_source(&self, source: &str) -> String {
+        self.tokens_in_order()
+            .into_iter()
+            .map(|token| token.text(source))
+            .collect()
+    }
+
+    /// Every token in this subtree, trivia included, in source order.
+    pub fn tokens_in_order(&self) -> Vec<&Token> {
+        let mut out = Vec::new();
+        self.collect_tokens(&mut out);
+        out
+    }
+
+    fn collect_tokens<'a>(&'a self, out: &mut Vec<&'a Token>) {
+        for child in &self.children {
+            match child {
+                SyntaxChild::Token(token) => out.push(token),
+                SyntaxChild::Tree(tree) => tree.collect_tokens(out),
+            }
+        }
+    }
+
+    /// This tree without `Whitespace` and `Comment` tokens, for structural
+    /// comparison where layout doesn't matter. Node ranges shrink to the
+    /// remaining tokens, so the result no longer reproduces the source.
+    pub fn retain_significant(self) -> SyntaxTree {
+        let mut out = SyntaxTree::new(self.kind);
+        for child in self.children {
+            match child {
+                SyntaxChild::Token(token) => {
+                    if !matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Comment) {
+                        out.push_token(token);
+                    }
+                }
+                SyntaxChild::Tree(tree) => {
+                    out.push_tree(tree.retain_significant());
+                }
+            }
+        }
+        out
+    }
+
+    /// All nodes of the given kind in this subtree (including `self`), in
+    /// depth-first pre-order. Unlike `SyntaxChild::get_tree_with_kind`, which
+    /// only looks at a single child, this searches every descendant.
+    pub fn find_all(&self, kind: SyntaxKind) -> Vec<&SyntaxTree> {
+        let mut out = Vec::new();
+        self.collect_kind(kind, &mut out);
+        out
+    }
+
+    /// The first node of the given kind in this subtree (including `self`),
+    /// in depth-first pre-order.
+    pub fn find_first(&self, kind: SyntaxKind) -> Option<&SyntaxTree> {
+        if self.kind == kind {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| match child {
+            SyntaxChild::Tree(tree) => tree.find_first(kind),
+            SyntaxChild::Token(_) => None,
+        })
+    }
+
+    /// Kinds of the nodes containing byte `offset`, from `self` down to the
+    /// deepest one, for hover context and breadcrumbs. A node contains the
+    /// offsets `start..end`, so at a boundary the node that starts there wins.
+    /// Empty if `self` doesn't contain the offset.
+    pub fn kind_path_to(&self, offset: u32) -> Vec<SyntaxKind> {
+        let mut path = Vec::new();
+        let mut node = self;
+        if !(node.start <= offset && offset < node.end) {
+            return path;
+        }
+        loop {
+            path.push(node.kind);
+            let next = node.children.iter().find_map(|child| match child {
+                SyntaxChild::Tree(tree) if tree.start <= offset && offset < tree.end => Some(tree),
+                _ => None,
+            });
+            match next {
+                Some(tree) => node = tree,
+                None => return path,
+            }
+        }
+    }
+
+    fn collect_kind<'a>(&'a self, kind: SyntaxKind, out: &mut Vec<&'a SyntaxTree>) {
+        if self.kind == kind {
+            out.push(self);
+        }
+        for child in &self.children {
+            if let SyntaxChild::Tree(tree) = child {
+                tree.collect_kind(kind, out);
+            }
+        }
+    }
+
+    /// The tree on a single line without trivia, e.g.
+    /// `SelectStatement(SelectClause("SELECT" ColumnList(ColumnReference("a"))))`,
+    /// for log lines and short test assertions. See `print` for the
+    /// multi-line form.
+    pub fn to_compact_string(&self, source: &str) -> String {
+        let mut buf = String::new();
+        self.write_compact(&mut buf, source);
+        buf
+    }
+
+    fn write_compact(&self, buf: &mut String, source: &str) {
+        let _ = write!(buf, "{:?}(", self.kind);
+        let mut first = true;
+        for child in &self.children {
+            if let SyntaxChild::Token(token) = child {
+                if matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Comment) {
+                    continue;
+                }
+            }
+            if !first {
+                buf.push(' ');
+            }
+            first = false;
+            match child {
+                SyntaxChild::Token(token) => {
+                    let _ = write!(buf, "{:?}", token.text(source));
+                }
+                SyntaxChild::Tree(tree) => tree.write_compact(buf, source),
+            }
+        }
+        buf.push(')');
+    }
+
     pub fn print(&self, buf: &mut String, level: usize, source: &str) {
         let indent = "  ".repeat(level);
         let _ = writeln!(buf, "{indent}{:?}", self.kind);
@@ -87,3 +350,293 @@ impl SyntaxTree {
         debug_assert!(buf.ends_with('\n'));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{SyntaxChild, SyntaxTree};
+    use crate::lexer::token::Token;
+    use crate::parser::parse;
+    use crate::parser::syntax_kind::SyntaxKind;
+
+    #[test]
+    fn tokens_in_order_match_tokenizer() {
+        let sql = "SELECT a, /* c */ f(b) FROM t WHERE x IN (1, 2);";
+        let result = parse(sql);
+        let from_tree: Vec<_> = result
+            .tree
+            .tokens_in_order()
+            .iter()
+            .map(|token| (token.kind, token.start, token.end))
+            .collect();
+        let from_lexer: Vec<_> = crate::lexer::tokenizer::tokenize_with_whitespace(sql)
+            .iter()
+            .filter(|token| token.kind != SyntaxKind::EndOfStream)
+            .map(|token| (token.kind, token.start, token.end))
+            .collect();
+        assert_eq!(from_tree, from_lexer);
+    }
+
+    #[test]
+    fn retain_significant_drops_trivia() {
+        let sql = "SELECT a, -- note\n  f(b)  FROM t /* end */";
+        let result = parse(sql);
+        let significant = result
+            .tree
+            .tokens_in_order()
+            .iter()
+            .filter(|token| !matches!(token.kind, SyntaxKind::Whitespace | SyntaxKind::Comment))
+            .count();
+        let stripped = result.tree.clone().retain_significant();
+        assert_eq!(stripped.tokens_in_order().len(), significant);
+        assert_eq!(stripped.to_compact_string(sql), result.tree.to_compact_string(sql));
+        let from = stripped.find_first(SyntaxKind::FromClause).unwrap();
+        assert_eq!(&sql[from.start as usize..from.end as usize], "FROM t");
+    }
+
+    #[test]
+    fn significant_span_skips_trivia() {
+        let sql = "SELECT f( a ) /* note */ -- end\n FROM t";
+        let result = parse(sql);
+        let column = result.tree.find_first(SyntaxKind::ColumnList).unwrap();
+        let (start, end) = column.significant_span().unwrap();
+        assert_eq!(&sql[start as usize..end as usize], "f( a )");
+        assert_eq!(SyntaxTree::new(SyntaxKind::ColumnList).significant_span(), None);
+    }
+
+    #[test]
+    fn compact_string_omits_trivia() {
+        let result = parse("SELECT a /* note */ FROM  t");
+        assert_eq!(
+            result.tree.to_compact_string(&result.source),
+            r#"File(SelectStatement(SelectClause("SELECT" ColumnList(ColumnReference("a"))) FromClause("FROM" TableIdentifier("t"))))"#
+        );
+    }
+
+    #[test]
+    fn find_all_collects_nested_function_calls() {
+        let result = parse("SELECT f(g(x), h(1)) FROM t WHERE k(y) > 0");
+        let names: Vec<&str> = result
+            .tree
+            .find_all(SyntaxKind::FunctionCall)
+            .iter()
+            .map(|call| {
+                let name = call.find_first(SyntaxKind::Identifier).unwrap();
+                result.source[name.start as usize..name.end as usize].trim()
+            })
+            .collect();
+        assert_eq!(names, ["f", "g", "h", "k"]);
+    }
+
+    #[test]
+    fn find_first_includes_self_and_searches_depth_first() {
+        let result = parse("SELECT a FROM (SELECT b FROM t)");
+        let stmt = result.tree.find_first(SyntaxKind::SelectStatement).unwrap();
+        let again = stmt.find_first(SyntaxKind::SelectStatement).unwrap();
+        assert_eq!((again.start, again.end), (stmt.start, stmt.end));
+
+        let column = stmt.find_first(SyntaxKind::ColumnReference).unwrap();
+        assert_eq!(result.source[column.start as usize..column.end as usize].trim(), "a");
+        assert_eq!(stmt.find_all(SyntaxKind::ColumnReference).len(), 2);
+    }
+
+    #[test]
+    fn span_covers_tokens() {
+        let sql = "SELECT a FROM t WHERE";
+        let result = parse(sql);
+        assert_eq!(result.tree.span(), (0, 21));
+        let from = result.tree.find_first(SyntaxKind::FromClause).unwrap();
+        assert_eq!(from.span(), (9, 16));
+        // The missing WHERE condition is an error node with no tokens.
+        let error = result.tree.find_first(SyntaxKind::Error).unwrap();
+        assert!(error.children.is_empty());
+        assert_eq!(error.span(), (21, 21));
+        assert_eq!(SyntaxTree::new(SyntaxKind::Error).span(), (0, 0));
+    }
+
+    #[test]
+    fn find_first_missing_kind() {
+        let result = parse("SELECT 1");
+        assert!(result.tree.find_first(SyntaxKind::JoinClause).is_none());
+        assert!(result.tree.find_all(SyntaxKind::JoinClause).is_empty());
+    }
+
+    #[test]
+    fn kind_path_to_column_in_function_call() {
+        let source = "SELECT f(a) FROM t";
+        let result = parse(source);
+        assert_eq!(
+            result.tree.kind_path_to(9),
+            [
+                SyntaxKind::File,
+                SyntaxKind::SelectStatement,
+                SyntaxKind::SelectClause,
+                SyntaxKind::ColumnList,
+                SyntaxKind::FunctionCall,
+                SyntaxKind::ExpressionList,
+                SyntaxKind::Expression,
+                SyntaxKind::ColumnReference,
+            ]
+        );
+        let from = result.tree.kind_path_to(12);
+        assert_eq!(from.last(), Some(&SyntaxKind::FromClause));
+        assert!(result.tree.kind_path_to(100).is_empty());
+    }
+
+    #[test]
+    fn graft_replaces_a_statement() {
+        let old = parse("SELECT 1; SELECT 2");
+        let mut tree = old.tree.clone();
+        let new_source = "SELECT 1; SELECT 2 + x";
+        let new = parse(new_source);
+        let statement = |tree: &SyntaxTree| {
+            tree.children
+                .iter()
+                .rposition(|child| child.as_tree().is_some())
+                .unwrap()
+        };
+        let index = statement(&tree);
+        let replacement = new.tree.children[statement(&new.tree)].as_tree().unwrap().clone();
+
+        let removed = tree.graft(&[index], replacement);
+        assert_eq!(removed.as_tree().unwrap().to_source(&old.source), "SELECT 2");
+        assert_eq!(tree.to_source(new_source), new_source);
+        assert_eq!(tree.end as usize, new_source.len());
+    }
+
+    #[test]
+    fn graft_nested_path_updates_ranges() {
+        let sql = "SELECT a FROM t";
+        let mut tree = parse(sql).tree;
+        let replacement = parse("SELECT a FROM longer").tree;
+        let from = replacement.find_first(SyntaxKind::FromClause).unwrap().clone();
+        // File > SelectStatement > FromClause
+        let select = tree.children.iter().position(SyntaxChild::is_tree).unwrap();
+        let SyntaxChild::Tree(statement) = &tree.children[select] else {
+            unreachable!()
+        };
+        let from_index = statement
+            .children
+            .iter()
+            .position(|child| child.get_tree_with_kind(SyntaxKind::FromClause).is_some())
+            .unwrap();
+
+        tree.graft(&[select, from_index], from);
+        assert_eq!(tree.to_source("SELECT a FROM longer"), "SELECT a FROM longer");
+        assert_eq!(tree.end, 20);
+        assert_eq!(tree.children[select].as_tree().unwrap().end, 20);
+    }
+
+    #[test]
+    fn child_accessors() {
+        let result = parse("SELECT a");
+        let select = result.tree.find_first(SyntaxKind::SelectClause).unwrap();
+        let keyword = &select.children[0];
+        assert_eq!(keyword.as_token().map(|t| t.kind), Some(SyntaxKind::BareWord));
+        assert!(keyword.as_tree().is_none());
+
+        let list = select.children.iter().find(|c| c.is_tree()).unwrap();
+        assert_eq!(list.as_tree().map(|t| t.kind), Some(SyntaxKind::ColumnList));
+        assert!(list.as_token().is_none());
+    }
+
+    /// `SELECT a FROM t`, built by hand with the same shape the parser produces.
+    fn hand_built_select() -> SyntaxTree {
+        let tok = |kind, start, end| Token::new(kind, start, end);
+
+        let mut column = SyntaxTree::new(SyntaxKind::ColumnReference);
+        column.push_token(tok(SyntaxKind::BareWord, 7, 8));
+        let mut column_list = SyntaxTree::new(SyntaxKind::ColumnList);
+        column_list.push_tree(column);
+        let mut select = SyntaxTree::new(SyntaxKind::SelectClause);
+        select
+            .push_token(tok(SyntaxKind::BareWord, 0, 6))
+            .push_token(tok(SyntaxKind::Whitespace, 6, 7))
+            .push_tree(column_list)
+            .push_token(tok(SyntaxKind::Whitespace, 8, 9));
+
+        let mut stmt = SyntaxTree::new(SyntaxKind::SelectStatement);
+        stmt.push_tree(select).push_tree(from_clause(14, 15));
+        stmt
+    }
+
+    /// `FROM <table>` where the table name spans `start..end`.
+    fn from_clause(start: u32, end: u32) -> SyntaxTree {
+        let mut table = SyntaxTree::new(SyntaxKind::TableIdentifier);
+        table.push_token(Token::new(SyntaxKind::BareWord, start, end));
+        let mut from = SyntaxTree::new(SyntaxKind::FromClause);
+        from.push_token(Token::new(SyntaxKind::BareWord, 9, 13))
+            .push_token(Token::new(SyntaxKind::Whitespace, 13, 14))
+            .push_tree(table);
+        from
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let source = "SELECT f(a), 'x' FROM db.t -- done";
+        let result = parse(source);
+        let json = serde_json::to_string(&result.tree).unwrap();
+        let tree: SyntaxTree = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&tree).unwrap(), json);
+
+        let (mut original, mut restored) = (String::new(), String::new());
+        result.tree.print(&mut original, 0, source);
+        tree.print(&mut restored, 0, source);
+        assert_eq!(restored, original);
+        assert_eq!(tree.to_source(source), source);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_tags_children_and_keeps_trivia() {
+        let result = parse("SELECT 1");
+        let json = serde_json::to_value(&result.tree).unwrap();
+        let select = &json["children"][0]["Tree"];
+        assert_eq!(select["kind"], "SelectStatement");
+        let clause = &select["children"][0]["Tree"]["children"];
+        assert_eq!(clause[0]["Token"]["kind"], "BareWord");
+        let whitespace = serde_json::json!({ "kind": "Whitespace", "start": 6, "end": 7 });
+        assert_eq!(clause[1]["Token"], whitespace);
+    }
+
+    #[test]
+    fn hand_built_tree_prints_like_parsed_tree() {
+        let source = "SELECT a FROM t";
+        let stmt = hand_built_select();
+        assert_eq!((stmt.start, stmt.end), (0, 15));
+        assert_eq!(stmt.to_source(source), source);
+
+        let mut built = String::new();
+        stmt.print(&mut built, 0, source);
+        let parsed = parse(source);
+        let mut expected = String::new();
+        parsed
+            .tree
+            .find_first(SyntaxKind::SelectStatement)
+            .unwrap()
+            .print(&mut expected, 0, source);
+        assert_eq!(built, expected);
+    }
+
+    #[test]
+    fn replace_child_swaps_subtree_and_updates_range() {
+        // Token offsets index into this source, so `b` sits after the query.
+        let source = "SELECT a FROM t b";
+        let mut stmt = hand_built_select();
+
+        let from = from_clause(16, 17);
+        let old = stmt.replace_child(1, SyntaxChild::Tree(from));
+        assert!(old.get_tree_with_kind(SyntaxKind::FromClause).is_some());
+        assert_eq!((stmt.start, stmt.end), (0, 17));
+        assert_eq!(stmt.to_source(source), "SELECT a FROM b");
+    }
+
+    #[test]
+    fn empty_subtree_does_not_affect_range() {
+        let mut tree = SyntaxTree::new(SyntaxKind::File);
+        tree.push_tree(SyntaxTree::new(SyntaxKind::Error));
+        assert_eq!((tree.start, tree.end), (u32::MAX, 0));
+        tree.push_token(Token::new(SyntaxKind::Number, 3, 4));
+        assert_eq!((tree.start, tree.end), (3, 4));
+    }
+}
@@ -1,11 +1,13 @@
-//! Validate the parser against the ClickHouse test query corpus.
+//! Validate the parser against query corpora.
 //!
-//! This test parses all .sql files from the ClickHouse repo's test suite
-//! and reports coverage. It does NOT fail on parse errors — it reports them.
-//!
-//! Run with:
+//! `parse_clickhouse_test_corpus` parses all .sql files from the ClickHouse
+//! repo's test suite and reports coverage. It does NOT fail on parse errors —
+//! it reports them. Run with:
 //!   CLICKHOUSE_QUERIES_PATH=/Users/al/ch/ClickHouse/tests/queries \
 //!     cargo test --test corpus -- --nocapture
+//!
+//! `corpus_round_trips` always runs over the checked-in files in
+//! `tests/inputs` and fails if any of them doesn't round-trip losslessly.
 
 use std::collections::HashMap;
 use std::fs;
@@ -13,6 +15,41 @@ use std::path::PathBuf;
 
 use clickhouse_analyzer::parse;
 
+/// The .sql files under `dir`, sorted for stable output.
+fn sql_files(dir: &std::path::Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "sql"))
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn corpus_round_trips() {
+    let inputs = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/inputs");
+    let files = sql_files(&inputs);
+    assert!(!files.is_empty(), "no .sql files in {}", inputs.display());
+
+    let mut lossy = Vec::new();
+    for path in &files {
+        let source = fs::read_to_string(path).unwrap();
+        let result = parse(&source);
+        let printed = result.tree.to_source(&result.source);
+        if printed != source {
+            let at = printed
+                .bytes()
+                .zip(source.bytes())
+                .position(|(a, b)| a != b)
+                .unwrap_or(printed.len().min(source.len()));
+            lossy.push(format!("{} (first difference at byte {at})", path.display()));
+        }
+    }
+    assert!(lossy.is_empty(), "not lossless:\n{}", lossy.join("\n"));
+}
+
 fn get_corpus_path() -> Option<PathBuf> {
     std::env::var("CLICKHOUSE_QUERIES_PATH")
         .ok()
@@ -27,17 +64,7 @@ fn parse_clickhouse_test_corpus() {
         return;
     };
 
-    let sql_files: Vec<PathBuf> = walkdir::WalkDir::new(&corpus_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .map(|ext| ext == "sql")
-                .unwrap_or(false)
-        })
-        .map(|e| e.path().to_path_buf())
-        .collect();
+    let sql_files = sql_files(&corpus_path);
 
     eprintln!("Found {} .sql files in {}", sql_files.len(), corpus_path.display());
 
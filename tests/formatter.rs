@@ -1,4 +1,4 @@
-use clickhouse_analyzer::{format, parse, FormatConfig};
+use clickhouse_analyzer::{format, format_minimal, parse, FormatConfig};
 use expect_test::{expect, Expect};
 
 fn check_format(input: &str, expected: Expect) {
@@ -357,6 +357,19 @@ fn tuple_literal() {
     );
 }
 
+#[test]
+fn trailing_comma_in_literals() {
+    check_format(
+        "select (1,), [1,2, ], ()",
+        expect![[r#"
+            SELECT
+                (1,),
+                [1, 2,],
+                ()
+        "#]],
+    );
+}
+
 #[test]
 fn unary_minus() {
     check_format(
@@ -370,6 +383,31 @@ fn unary_minus() {
     );
 }
 
+#[test]
+fn column_transformers_keep_their_spaces() {
+    check_format(
+        "select t.* except secret apply toString, columns('a.*') apply(toString) from t",
+        expect![[r#"
+            SELECT
+                t.* EXCEPT secret APPLY toString,
+                COLUMNS('a.*') APPLY(toString)
+            FROM t
+        "#]],
+    );
+}
+
+#[test]
+fn double_unary_minus_is_not_a_comment() {
+    check_format(
+        "select - -x, +y",
+        expect![[r#"
+            SELECT
+                - -x,
+                +y
+        "#]],
+    );
+}
+
 // ---------------------------------------------------------------------------
 // Multiple statements
 // ---------------------------------------------------------------------------
@@ -410,6 +448,37 @@ fn keyword_uppercase() {
     );
 }
 
+#[test]
+fn parser_keywords_uppercased_except_name_like_ones() {
+    check_format(
+        "select max(id), isNull(level) from t limit 1 with ties",
+        expect![[r#"
+            SELECT
+                max(id),
+                isNull(level)
+            FROM t
+            LIMIT 1 WITH TIES
+        "#]],
+    );
+}
+
+#[test]
+fn keywords_used_as_names_keep_their_case() {
+    // Identifiers are case-sensitive: `SKIP` would be a different column.
+    check_format(
+        "select skip, t.skip, ties as Sets from t as append order by ties limit 1 with ties",
+        expect![[r#"
+            SELECT
+                skip,
+                t.skip,
+                ties AS Sets
+            FROM t AS append
+            ORDER BY ties
+            LIMIT 1 WITH TIES
+        "#]],
+    );
+}
+
 #[test]
 fn lowercase_keywords() {
     let result = parse("SELECT a FROM t WHERE x > 1");
@@ -483,6 +552,23 @@ fn idempotent_case() {
     check_idempotent("select case when x > 1 then 'a' else 'b' end from t");
 }
 
+#[test]
+fn table_function_arguments() {
+    check_format(
+        "select * from s3('https://b/x.csv',coll, structure = 'a UInt8')",
+        expect![[r#"
+            SELECT
+                *
+            FROM s3('https://b/x.csv', coll, structure = 'a UInt8')
+        "#]],
+    );
+}
+
+#[test]
+fn idempotent_view_if_permitted_else() {
+    check_idempotent("select * from viewIfPermitted(select a from t else null('a UInt8'))");
+}
+
 // ---------------------------------------------------------------------------
 // Error resilience
 // ---------------------------------------------------------------------------
@@ -950,3 +1036,58 @@ ORDER BY tuple()
 SETTINGS index_granularity = 8192",
     );
 }
+
+#[test]
+fn ternary_operator() {
+    check_format(
+        "select x>0?1:y?2:3 from t",
+        expect![[r#"
+            SELECT
+                x > 0 ? 1 : y ? 2 : 3
+            FROM t
+        "#]],
+    );
+    check_idempotent("SELECT number % 2 ? 'odd' : 'even' FROM t WHERE a ? b : c");
+}
+
+// ---------------------------------------------------------------------------
+// Minimal-diff mode
+// ---------------------------------------------------------------------------
+
+fn format_minimal_default(input: &str) -> String {
+    let result = parse(input);
+    format_minimal(&result.tree, &FormatConfig::default(), &result.source)
+}
+
+#[test]
+fn minimal_leaves_formatted_query_untouched() {
+    let sql = "SELECT\n    a,\n    count() AS n -- per key\nFROM  t\nWHERE x IN (1,2)\nGROUP BY a\n";
+    assert_eq!(format_minimal_default(sql), sql);
+}
+
+#[test]
+fn minimal_changes_only_keyword_case() {
+    let sql = "select date,  type\n  from t as x\n\twhere x.key = null and not y   -- keep\n";
+    let formatted = format_minimal_default(sql);
+    assert_eq!(
+        formatted,
+        "SELECT date,  type\n  FROM t AS x\n\tWHERE x.key = null AND NOT y   -- keep\n"
+    );
+    // Byte-for-byte identical apart from case.
+    assert_eq!(formatted.len(), sql.len());
+    assert!(formatted.eq_ignore_ascii_case(sql));
+}
+
+#[test]
+fn minimal_lowercase_keywords() {
+    let result = parse("SELECT a FROM t  WHERE b");
+    let config = FormatConfig {
+        uppercase_keywords: false,
+        ..FormatConfig::default()
+    };
+    assert_eq!(
+        format_minimal(&result.tree, &config, &result.source),
+        "select a from t  where b"
+    );
+}
+
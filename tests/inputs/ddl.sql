@@ -0,0 +1,25 @@
+CREATE TABLE IF NOT EXISTS db.events ON CLUSTER main
+(
+    `event_date` Date DEFAULT today(),
+    user_id UInt64 CODEC(Delta, ZSTD(3)),
+    payload String MATERIALIZED '' COMMENT 'raw JSON',
+    tags Array(LowCardinality(String)),
+    total ALIAS user_id * 2,
+    INDEX idx_user user_id TYPE minmax GRANULARITY 4
+)
+ENGINE = ReplicatedMergeTree('/clickhouse/{shard}/events', '{replica}')
+PARTITION BY toYYYYMM(event_date)
+ORDER BY (user_id, event_date)
+TTL event_date + INTERVAL 1 YEAR
+SETTINGS index_granularity = 8192;
+
+CREATE MATERIALIZED VIEW mv TO db.daily AS
+SELECT event_date, count() AS c FROM db.events GROUP BY event_date;
+
+CREATE TABLE copy AS db.events;
+ALTER TABLE db.events ADD COLUMN IF NOT EXISTS score Float64 AFTER user_id, DROP COLUMN tags;
+ALTER TABLE db.events DELETE WHERE user_id = 0;
+DROP TABLE IF EXISTS tmp SYNC;
+RENAME TABLE a TO b, c TO d;
+CREATE TABLE users (id UInt64 COMMENT 'row id', name String COMMENT 'display name') ENGINE = MergeTree ORDER BY id COMMENT 'registered users';
+CREATE FUNCTION greet AS (name) -> concat($$it's $$, name);
@@ -0,0 +1,10 @@
+INSERT INTO db.events (event_date, user_id) VALUES ('2024-01-01', 1), ('2024-01-02', 2);
+INSERT INTO t SELECT * FROM input('a UInt8') FORMAT CSV;
+SET max_memory_usage = 10000000000;
+SHOW TABLES FROM db LIKE '%event%';
+EXPLAIN PIPELINE SELECT sum(number) FROM numbers(10);
+DESCRIBE TABLE db.events;
+SYSTEM FLUSH LOGS;
+OPTIMIZE TABLE db.events FINAL;
+GRANT SELECT(a, b) ON db.t TO alice;
+SELECT $$here-doc 'body'$$, x::UInt8, {param:String}, -1e-3, 0xFF, 'it''s';
@@ -0,0 +1,6 @@
+-- Inputs with errors must still round-trip byte for byte
+SELECT a, FROM t WHERE;
+SELECT (1, 2 FROM t;
+SELECT 'unterminated string
+CREATE TABLE (x) ENGINE;
+SELECT * FROM t WHERE a = ;  /* unterminated comment
@@ -0,0 +1,41 @@
+-- Analytical queries with the usual clauses
+WITH toStartOfDay(now()) AS today
+SELECT
+    user_id,
+    countIf(event = 'click') AS clicks,
+    uniqExact(session_id) AS sessions,
+    quantile(0.95)(latency_ms) AS p95
+FROM events FINAL
+SAMPLE 1/10
+PREWHERE event_date >= today - INTERVAL 7 DAY
+WHERE country IN ('DE', 'FR') AND NOT is_bot
+GROUP BY user_id WITH TOTALS
+HAVING clicks > 10
+ORDER BY clicks DESC NULLS LAST
+LIMIT 100
+SETTINGS max_threads = 8;
+
+/* window functions and joins */
+SELECT
+    a.id,
+    sum(b.amount) OVER (PARTITION BY a.id ORDER BY b.ts ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS running,
+    arrayMap(x -> x * 2, [1, 2, 3]) AS doubled,
+    CASE WHEN b.amount > 0 THEN 'credit' ELSE 'debit' END AS kind,
+    b.payload.items[1] AS first_item,
+    {'k': 1, 'v': 2} AS m,
+    tup.1
+FROM accounts AS a
+ANY LEFT JOIN transactions AS b ON a.id = b.account_id
+ARRAY JOIN tags AS tag
+WHERE b.ts BETWEEN '2024-01-01' AND '2024-12-31';  -- trailing comment
+SELECT 1 UNION ALL SELECT 2 EXCEPT SELECT 3;
+SELECT name, score FROM results ORDER BY score DESC LIMIT 3 WITH TIES;
+SELECT a FROM t INTO OUTFILE 'out.csv' FORMAT CSV SETTINGS max_threads = 1;
+SELECT * FROM s3('https://bucket/data.csv', 'CSV', 'a UInt8, b String') FORMAT JSONEachRow;
+SELECT arrayJoin([(1, 'a')]) AS (n, s), n FROM t;
+SELECT * FROM cluster('default', db, events) JOIN clusterAllReplicas('default', db.users) USING (id);
+SELECT name, score FROM results ORDER BY score DESC NULLS LAST, name ASC COLLATE 'en';
+SELECT region, product, sum(amount) FROM sales GROUP BY region, product WITH ROLLUP HAVING sum(amount) > 100;
+SELECT * FROM a JOIN b ON a.id = b.id GLOBAL JOIN c USING (id);
+SELECT a, b FROM t ORDER BY a LIMIT 1, 2 BY a, b LIMIT 10;
+SELECT DISTINCT t.* EXCEPT secret APPLY toString, COLUMNS('^a') APPLY(toString) FROM t;
@@ -1,4 +1,7 @@
-use clickhouse_analyzer::{parse, SyntaxChild, SyntaxKind, SyntaxTree};
+use clickhouse_analyzer::{
+    parse, parse_column_list, parse_statements, parse_where_clause, parse_with_options,
+    ParseOptions, SyntaxChild, SyntaxKind, SyntaxTree,
+};
 use expect_test::{expect, Expect};
 
 // ---------------------------------------------------------------------------
@@ -661,7 +664,7 @@ fn empty_parens() {
                 SelectClause
                   'SELECT'
                   ColumnList
-                    Expression
+                    TupleExpression
                       '('
                       ')'
         "#]],
@@ -678,6 +681,89 @@ fn empty_parens_no_errors() {
     check_errors("SELECT ()", expect![[""]]);
 }
 
+#[test]
+fn array_trailing_comma() {
+    check(
+        "SELECT [1, 2,]",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ArrayExpression
+                      '['
+                      NumberLiteral
+                        '1'
+                      ','
+                      NumberLiteral
+                        '2'
+                      ','
+                      ']'
+        "#]],
+    );
+    check_errors("SELECT [1, 2,]", expect![[""]]);
+}
+
+#[test]
+fn tuple_trailing_comma() {
+    check(
+        "SELECT (1,), (1)",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    TupleExpression
+                      '('
+                      NumberLiteral
+                        '1'
+                      ','
+                      ')'
+                    ','
+                    Expression
+                      '('
+                      NumberLiteral
+                        '1'
+                      ')'
+        "#]],
+    );
+    check_errors("SELECT (1, 2,)", expect![[""]]);
+}
+
+#[test]
+fn lone_comma_in_brackets_is_an_error() {
+    check_errors(
+        "SELECT [,], (,)",
+        expect![[r#"
+            8..9: Expected expression
+            13..14: Expected expression
+        "#]],
+    );
+}
+
+#[test]
+fn stray_colon_is_an_error() {
+    check_errors(
+        "SELECT : FROM t",
+        expect![[r#"
+            7..8: Unexpected ':' in expression
+        "#]],
+    );
+    check_errors(
+        "SELECT a[1:2] FROM t",
+        expect![[r#"
+            10..11: Array slices are not supported, use arraySlice(arr, offset, length)
+        "#]],
+    );
+}
+
+#[test]
+fn colon_in_map_and_ternary_is_fine() {
+    check_errors("SELECT {'k': 1}, x ? 1 : 2 FROM t", expect![[""]]);
+}
+
 // ====================================================================
 // 4. Interval edge cases (recent fix)
 // ====================================================================
@@ -1154,6 +1240,58 @@ fn modulo_precedence_same_as_multiply() {
     check_errors("SELECT a + b % c * d", expect![[""]]);
 }
 
+// ====================================================================
+// Number forms with a leading or trailing decimal point
+// ====================================================================
+
+#[test]
+fn leading_and_trailing_dot_numbers() {
+    check(
+        "SELECT .5, 1., -.5e3",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    NumberLiteral
+                      '.5'
+                    ','
+                    NumberLiteral
+                      '1.'
+                    ','
+                    UnaryExpression
+                      '-'
+                      NumberLiteral
+                        '.5e3'
+        "#]],
+    );
+}
+
+#[test]
+fn dot_number_after_keyword_starts_a_number() {
+    check_errors("SELECT x FROM t WHERE .5 < y AND .1 > z LIMIT .5e1", expect![[""]]);
+}
+
+#[test]
+fn dot_number_after_identifier_is_tuple_access() {
+    check(
+        "SELECT a.5",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    DotAccessExpression
+                      ColumnReference
+                        'a'
+                      '.'
+                      '5'
+        "#]],
+    );
+}
+
 // ====================================================================
 // Cast (::) on arbitrary expressions
 // ====================================================================
@@ -2136,6 +2274,44 @@ fn ternary_expression_with_binary_ops() {
     );
 }
 
+#[test]
+fn ternary_expression_in_where_is_right_associative() {
+    check(
+        "SELECT 1 WHERE x > 0 ? 1 : y ? 2 : 3",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    NumberLiteral
+                      '1'
+                WhereClause
+                  'WHERE'
+                  TernaryExpression
+                    BinaryExpression
+                      ColumnReference
+                        'x'
+                      '>'
+                      NumberLiteral
+                        '0'
+                    '?'
+                    NumberLiteral
+                      '1'
+                    ':'
+                    TernaryExpression
+                      ColumnReference
+                        'y'
+                      '?'
+                      NumberLiteral
+                        '2'
+                      ':'
+                      NumberLiteral
+                        '3'
+        "#]],
+    );
+}
+
 #[test]
 fn qualified_asterisk() {
     check(
@@ -2162,20 +2338,22 @@ fn qualified_asterisk() {
                   TableIdentifier
                     't1'
                 JoinClause
-                  'JOIN'
+                  JoinType
+                    'JOIN'
                   TableIdentifier
                     't2'
-                  'ON'
-                  BinaryExpression
-                    ColumnReference
-                      't1'
-                      '.'
-                      'id'
-                    '='
-                    ColumnReference
-                      't2'
-                      '.'
-                      'id'
+                  JoinConstraint
+                    'ON'
+                    BinaryExpression
+                      ColumnReference
+                        't1'
+                        '.'
+                        'id'
+                      '='
+                      ColumnReference
+                        't2'
+                        '.'
+                        'id'
         "#]],
     );
 }
@@ -3056,12 +3234,21 @@ fn group_by_all() {
                 GroupByClause
                   'GROUP'
                   'BY'
-                  ColumnReference
+                  GroupByAll
                     'ALL'
         "#]],
     );
 }
 
+#[test]
+fn group_by_all_function_call_is_not_the_marker() {
+    // all(x) as a grouping key is still a function call.
+    let result = parse("SELECT a FROM t GROUP BY all(a)");
+    assert!(result.errors.is_empty());
+    assert!(result.tree.find_first(SyntaxKind::GroupByAll).is_none());
+    assert!(result.tree.find_first(SyntaxKind::FunctionCall).is_some());
+}
+
 // ====================================================================
 // FILTER(WHERE ...) on aggregate functions
 // ====================================================================
@@ -3218,3 +3405,1285 @@ fn with_totals_in_subquery_test() {
         expect![[""]],
     );
 }
+
+// ====================================================================
+// SETTINGS inside subqueries
+// ====================================================================
+
+#[test]
+fn settings_in_from_subquery() {
+    check(
+        "SELECT * FROM (SELECT 1 SETTINGS max_threads=1)",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  SubqueryExpression
+                    '('
+                    SelectStatement
+                      SelectClause
+                        'SELECT'
+                        ColumnList
+                          NumberLiteral
+                            '1'
+                      SettingsClause
+                        'SETTINGS'
+                        SettingItem
+                          'max_threads'
+                          '='
+                          NumberLiteral
+                            '1'
+                    ')'
+        "#]],
+    );
+}
+
+#[test]
+fn settings_in_scalar_and_join_subqueries() {
+    check_errors("SELECT (SELECT 1 SETTINGS a = 1) AS x", expect![[""]]);
+    check_errors(
+        "SELECT * FROM (SELECT 1 SETTINGS a = 1) AS s JOIN (SELECT 2 SETTINGS b = 2) AS u ON 1 SETTINGS c = 3",
+        expect![[""]],
+    );
+    check_errors("WITH c AS (SELECT 1 SETTINGS a = 1) SELECT * FROM c", expect![[""]]);
+}
+
+#[test]
+fn settings_after_table_function() {
+    check_errors("SELECT * FROM numbers(10) SETTINGS max_threads = 1", expect![[""]]);
+}
+
+// ====================================================================
+// Subqueries inside WHERE / PREWHERE predicates
+// ====================================================================
+
+#[test]
+fn where_with_in_subquery_and_scalar_subquery() {
+    check(
+        "SELECT * FROM t WHERE id IN (SELECT id FROM t2) AND x > (SELECT avg(x) FROM t3)",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    Asterisk
+                      '*'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                WhereClause
+                  'WHERE'
+                  BinaryExpression
+                    InExpression
+                      ColumnReference
+                        'id'
+                      'IN'
+                      '('
+                      SubqueryExpression
+                        SelectStatement
+                          SelectClause
+                            'SELECT'
+                            ColumnList
+                              ColumnReference
+                                'id'
+                          FromClause
+                            'FROM'
+                            TableIdentifier
+                              't2'
+                      ')'
+                    'AND'
+                    BinaryExpression
+                      ColumnReference
+                        'x'
+                      '>'
+                      Expression
+                        '('
+                        SubqueryExpression
+                          SelectStatement
+                            SelectClause
+                              'SELECT'
+                              ColumnList
+                                FunctionCall
+                                  Identifier
+                                    'avg'
+                                  ExpressionList
+                                    '('
+                                    Expression
+                                      ColumnReference
+                                        'x'
+                                    ')'
+                            FromClause
+                              'FROM'
+                              TableIdentifier
+                                't3'
+                        ')'
+        "#]],
+    );
+}
+
+#[test]
+fn prewhere_with_in_subquery() {
+    check_errors(
+        "SELECT * FROM t PREWHERE id IN (SELECT id FROM t2) WHERE x > (SELECT max(x) FROM t3) OR NOT y IN (SELECT 1)",
+        expect![[""]],
+    );
+}
+
+// ====================================================================
+// parse_statements: per-statement trees and spans
+// ====================================================================
+
+#[test]
+fn parse_statements_splits_script() {
+    let sql = "SELECT 1;\nINSERT INTO t VALUES (1) ;  -- trailing\nDROP TABLE t";
+    let statements = parse_statements(sql);
+
+    let summary: Vec<(SyntaxKind, &str)> = statements
+        .iter()
+        .map(|s| (s.tree.kind, &sql[s.range.0..s.range.1]))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (SyntaxKind::SelectStatement, "SELECT 1"),
+            (SyntaxKind::InsertStatement, "INSERT INTO t VALUES (1)"),
+            (SyntaxKind::DropStatement, "DROP TABLE t"),
+        ]
+    );
+    assert!(statements.iter().all(|s| s.errors.is_empty()));
+}
+
+#[test]
+fn parse_statements_assigns_errors_to_their_statement() {
+    let sql = "SELECT 1; SELECT (1 FROM; SELECT 3";
+    let statements = parse_statements(sql);
+    let error_counts: Vec<usize> = statements.iter().map(|s| s.errors.len()).collect();
+    assert_eq!(error_counts, [0, 2, 0]);
+    assert_eq!(parse(sql).errors.len(), 2);
+}
+
+#[test]
+fn parse_statements_merges_stray_tokens_into_the_statement_before() {
+    let sql = "SELECT 1 ) ; SELECT 2";
+    let statements = parse_statements(sql);
+    let summary: Vec<(SyntaxKind, &str, usize)> = statements
+        .iter()
+        .map(|s| (s.tree.kind, &sql[s.range.0..s.range.1], s.errors.len()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (SyntaxKind::SelectStatement, "SELECT 1 )", 1),
+            (SyntaxKind::SelectStatement, "SELECT 2", 0),
+        ]
+    );
+    assert_eq!(statements[0].errors[0].range, (9, 10));
+}
+
+#[test]
+fn parse_statements_keeps_every_error() {
+    // An unknown statement is its own entry; it never swallows the next one.
+    let sql = "FROBNICATE t; SELECT 1; SELECT 2 FROM";
+    let statements = parse_statements(sql);
+    let summary: Vec<(SyntaxKind, &str, usize)> = statements
+        .iter()
+        .map(|s| (s.tree.kind, &sql[s.range.0..s.range.1], s.errors.len()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (SyntaxKind::Error, "FROBNICATE t", 1),
+            (SyntaxKind::SelectStatement, "SELECT 1", 0),
+            (SyntaxKind::SelectStatement, "SELECT 2 FROM", 1),
+        ]
+    );
+    let total: usize = statements.iter().map(|s| s.errors.len()).sum();
+    assert_eq!(total, parse(sql).errors.len());
+
+    // Garbage after the `;` doesn't stretch the statement before it.
+    let sql = "SELECT 1; garbage";
+    let statements = parse_statements(sql);
+    let summary: Vec<(SyntaxKind, &str, usize)> = statements
+        .iter()
+        .map(|s| (s.tree.kind, &sql[s.range.0..s.range.1], s.errors.len()))
+        .collect();
+    assert_eq!(
+        summary,
+        [(SyntaxKind::SelectStatement, "SELECT 1", 0), (SyntaxKind::Error, "garbage", 1)]
+    );
+
+    let statements = parse_statements("garbage; SELECT 1;");
+    let kinds: Vec<SyntaxKind> = statements.iter().map(|s| s.tree.kind).collect();
+    assert_eq!(kinds, [SyntaxKind::Error, SyntaxKind::SelectStatement]);
+    assert_eq!(statements[1].range, (9, 17));
+
+    let statements = parse_statements("FROBNICATE t");
+    assert_eq!(statements.len(), 1);
+    assert_eq!(statements[0].tree.kind, SyntaxKind::Error);
+    assert_eq!(statements[0].errors.len(), 1);
+}
+
+#[test]
+fn vertical_delimiter_ends_a_statement() {
+    check(
+        r"SELECT 1\G SELECT 2",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    NumberLiteral
+                      '1'
+              '\G'
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    NumberLiteral
+                      '2'
+        "#]],
+    );
+}
+
+#[test]
+fn parse_statements_records_vertical_output() {
+    let sql = r"SELECT 1\G SELECT a FROM t; SELECT 3 \G";
+    let statements = parse_statements(sql);
+    let summary: Vec<(&str, bool)> = statements
+        .iter()
+        .map(|s| (&sql[s.range.0..s.range.1], s.vertical))
+        .collect();
+    assert_eq!(
+        summary,
+        [("SELECT 1", true), ("SELECT a FROM t", false), ("SELECT 3", true)]
+    );
+    assert!(statements.iter().all(|s| s.errors.is_empty()));
+}
+
+// ====================================================================
+// SAMPLE BY in CREATE TABLE vs. query-level SAMPLE
+// ====================================================================
+
+#[test]
+fn create_table_sample_by_is_table_clause() {
+    check(
+        "CREATE TABLE t (id UInt64) ENGINE = MergeTree ORDER BY intHash32(id) SAMPLE BY intHash32(id)",
+        expect![[r#"
+            File
+              CreateStatement
+                'CREATE'
+                TableDefinition
+                  'TABLE'
+                  TableIdentifier
+                    't'
+                  ColumnDefinitionList
+                    '('
+                    ColumnDefinition
+                      'id'
+                      DataType
+                        'UInt64'
+                    ')'
+                  EngineClause
+                    'ENGINE'
+                    '='
+                    'MergeTree'
+                  OrderByDefinition
+                    'ORDER'
+                    'BY'
+                    FunctionCall
+                      Identifier
+                        'intHash32'
+                      ExpressionList
+                        '('
+                        Expression
+                          ColumnReference
+                            'id'
+                        ')'
+                  SampleByDefinition
+                    'SAMPLE'
+                    'BY'
+                    FunctionCall
+                      Identifier
+                        'intHash32'
+                      ExpressionList
+                        '('
+                        Expression
+                          ColumnReference
+                            'id'
+                        ')'
+        "#]],
+    );
+}
+
+#[test]
+fn create_table_as_select_with_sample_keeps_both_forms() {
+    check(
+        "CREATE TABLE t ENGINE = MergeTree ORDER BY id SAMPLE BY id AS SELECT id FROM s SAMPLE 1 / 10",
+        expect![[r#"
+            File
+              CreateStatement
+                'CREATE'
+                TableDefinition
+                  'TABLE'
+                  TableIdentifier
+                    't'
+                  EngineClause
+                    'ENGINE'
+                    '='
+                    'MergeTree'
+                  OrderByDefinition
+                    'ORDER'
+                    'BY'
+                    ColumnReference
+                      'id'
+                  SampleByDefinition
+                    'SAMPLE'
+                    'BY'
+                    ColumnReference
+                      'id'
+                  AsClause
+                    'AS'
+                    SelectStatement
+                      SelectClause
+                        'SELECT'
+                        ColumnList
+                          ColumnReference
+                            'id'
+                      FromClause
+                        'FROM'
+                        TableIdentifier
+                          's'
+                        SampleClause
+                          'SAMPLE'
+                          BinaryExpression
+                            NumberLiteral
+                              '1'
+                            '/'
+                            NumberLiteral
+                              '10'
+        "#]],
+    );
+}
+
+// ====================================================================
+// Trailing tokens after a complete statement
+// ====================================================================
+
+#[test]
+fn trailing_tokens_after_select_recover_at_semicolon() {
+    // The SELECT list absorbs `foo` as an implicit alias and reports the
+    // missing comma before `bar`; the next statement still parses cleanly.
+    check_errors("SELECT 1 foo bar; SELECT 2", expect![[r#"
+        13..16: expected Comma
+    "#]]);
+    check_errors("SELECT 1)) garbage; SELECT 2", expect![[r#"
+        8..18: Expected end of statement
+    "#]]);
+}
+
+#[test]
+fn trailing_tokens_are_one_error_node() {
+    check(
+        "DROP TABLE t foo bar; SELECT 1",
+        expect![[r#"
+            File
+              DropStatement
+                'DROP'
+                'TABLE'
+                TableIdentifier
+                  't'
+              Error
+                'foo'
+                'bar'
+              ';'
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    NumberLiteral
+                      '1'
+        "#]],
+    );
+}
+
+#[test]
+fn statement_start_after_statement_is_not_trailing() {
+    check_errors("USE db\nDROP TABLE t\nSELECT 1", expect![[""]]);
+}
+
+#[test]
+fn trailing_whitespace_is_not_trailing_tokens() {
+    check_errors("DESCRIBE t FORMAT TSV\n", expect![[""]]);
+    check_errors("SHOW TABLES  -- done\n", expect![[""]]);
+}
+
+// ====================================================================
+// CASE expressions as GROUP BY / ORDER BY keys
+// ====================================================================
+
+#[test]
+fn case_as_order_by_key() {
+    check(
+        "SELECT a FROM t ORDER BY CASE WHEN x THEN 1 ELSE 2 END DESC, a LIMIT 3",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'a'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                OrderByClause
+                  'ORDER'
+                  'BY'
+                  OrderByItem
+                    CaseExpression
+                      'CASE'
+                      WhenClause
+                        'WHEN'
+                        ColumnReference
+                          'x'
+                        'THEN'
+                        NumberLiteral
+                          '1'
+                      'ELSE'
+                      NumberLiteral
+                        '2'
+                      'END'
+                    'DESC'
+                  ','
+                  OrderByItem
+                    ColumnReference
+                      'a'
+                LimitClause
+                  'LIMIT'
+                  NumberLiteral
+                    '3'
+        "#]],
+    );
+}
+
+#[test]
+fn case_as_group_by_key() {
+    check_errors(
+        "SELECT CASE k WHEN 1 THEN 'one' END AS c, count() FROM t GROUP BY CASE k WHEN 1 THEN 'one' END, b HAVING count() > 1 ORDER BY c",
+        expect![[""]],
+    );
+}
+
+// ====================================================================
+// ParseOptions: keywords allowed as identifiers
+// ====================================================================
+
+fn parse_allowing(input: &str, words: &[&str]) -> clickhouse_analyzer::Parse {
+    let options = ParseOptions {
+        identifier_keywords: words.iter().map(|w| w.to_string()).collect(),
+        ..Default::default()
+    };
+    parse_with_options(input, &options)
+}
+
+#[test]
+fn soft_keyword_columns_parse_with_allowlist() {
+    let result = parse_allowing("SELECT date, value FROM t", &["date", "value"]);
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(result.tree.find_all(SyntaxKind::ColumnReference).len(), 2);
+}
+
+#[test]
+fn allowlisted_keywords_become_column_references() {
+    let sql = "SELECT interval, left FROM t WHERE LEFT = 1";
+    assert!(!parse(sql).errors.is_empty());
+
+    let result = parse_allowing(sql, &["INTERVAL", "left"]);
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let columns: Vec<&str> = result
+        .tree
+        .find_all(SyntaxKind::ColumnReference)
+        .iter()
+        .map(|c| sql[c.start as usize..c.end as usize].trim())
+        .collect();
+    assert_eq!(columns, ["interval", "left", "LEFT"]);
+}
+
+#[test]
+fn unlisted_keywords_keep_their_role() {
+    let result = parse_allowing("SELECT interval FROM t LEFT JOIN u USING id", &["interval"]);
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert!(result.tree.find_first(SyntaxKind::JoinClause).is_some());
+}
+
+// ====================================================================
+// ParseOptions: error limit
+// ====================================================================
+
+fn parse_with_max_errors(input: &str, max_errors: usize) -> clickhouse_analyzer::Parse {
+    let options = ParseOptions {
+        max_errors: Some(max_errors),
+        ..Default::default()
+    };
+    parse_with_options(input, &options)
+}
+
+#[test]
+fn error_limit_stops_parsing() {
+    let input = "SELECT (; SELECT (; SELECT (; SELECT (; SELECT (; SELECT 1";
+    assert!(parse(input).errors.len() > 4);
+
+    let result = parse_with_max_errors(input, 3);
+    let actual: String = result
+        .errors
+        .iter()
+        .map(|e| format!("{}..{}: {}\n", e.range.0, e.range.1, e.message))
+        .collect();
+    expect![[r#"
+        8..9: Expected expression
+        10..16: expected )
+        18..19: Expected expression
+        20..58: Too many errors, stopped parsing
+    "#]].assert_eq(&actual);
+    assert_eq!(collect_text(&result.tree, &result.source), input);
+}
+
+#[test]
+fn error_limit_not_reached() {
+    let result = parse_with_max_errors("SELECT a FROM t; SELECT (", 5);
+    assert_eq!(result.errors.len(), parse("SELECT a FROM t; SELECT (").errors.len());
+    assert!(result.errors.iter().all(|e| !e.message.starts_with("Too many")));
+}
+
+// ====================================================================
+// ParseOptions: strict mode
+// ====================================================================
+
+fn error_lines(result: &clickhouse_analyzer::Parse) -> String {
+    result
+        .errors
+        .iter()
+        .map(|e| format!("{}..{}: {}\n", e.range.0, e.range.1, e.message))
+        .collect()
+}
+
+#[test]
+fn tolerant_mode_resumes_after_unknown_statement() {
+    let input = "FROBNICATE t; SELECT 1";
+    let result = parse(input);
+    expect![[r#"
+        0..12: Unknown statement
+    "#]].assert_eq(&error_lines(&result));
+    assert!(result.tree.find_first(SyntaxKind::SelectStatement).is_some());
+    assert_eq!(collect_text(&result.tree, &result.source), input);
+}
+
+#[test]
+fn unknown_statement_is_one_error_node() {
+    let input = "garbage; SELECT 1;";
+    let result = parse(input);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.tree.find_all(SyntaxKind::Error).len(), 1);
+    let select = result.tree.find_first(SyntaxKind::SelectStatement).unwrap();
+    assert!(select.find_first(SyntaxKind::Error).is_none());
+    assert_eq!(collect_text(&result.tree, &result.source), input);
+}
+
+#[test]
+fn strict_mode_aborts_at_unknown_statement() {
+    let input = "SELECT 1; FROBNICATE t; SELECT 2";
+    let options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let result = parse_with_options(input, &options);
+    expect![[r#"
+        10..32: Unknown statement
+    "#]].assert_eq(&error_lines(&result));
+    assert_eq!(result.tree.find_all(SyntaxKind::SelectStatement).len(), 1);
+    assert_eq!(collect_text(&result.tree, &result.source), input);
+}
+
+#[test]
+fn strict_mode_accepts_known_statements() {
+    let options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let result = parse_with_options("SELECT 1; SHOW TABLES", &options);
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+}
+
+// ====================================================================
+// Every SELECT clause in one query
+// ====================================================================
+
+/// Kinds of the direct children of the first statement, in order.
+fn clause_kinds(input: &str) -> Vec<SyntaxKind> {
+    let result = parse(input);
+    assert!(result.errors.is_empty(), "{input}: {:?}", result.errors);
+    match result.tree.children.first() {
+        Some(SyntaxChild::Tree(stmt)) => stmt
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                SyntaxChild::Tree(tree) => Some(tree.kind),
+                SyntaxChild::Token(_) => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[test]
+fn full_select_clause_order() {
+    let sql = "WITH 1 AS one \
+        SELECT DISTINCT a, count() AS c \
+        FROM db.t AS x FINAL SAMPLE 0.5 \
+        ARRAY JOIN arr AS el \
+        LEFT JOIN u ON x.id = u.id \
+        PREWHERE a > 0 \
+        WHERE b < 10 \
+        GROUP BY a WITH ROLLUP WITH TOTALS \
+        HAVING c > 1 \
+        WINDOW w AS (PARTITION BY a), w2 AS (ORDER BY b) \
+        QUALIFY row_number() OVER w = 1 \
+        ORDER BY a DESC WITH FILL INTERPOLATE (b AS b + 1), c \
+        LIMIT 1 BY a \
+        LIMIT 10 OFFSET 5 \
+        SETTINGS max_threads = 4 \
+        FORMAT JSON";
+    assert_eq!(
+        clause_kinds(sql),
+        [
+            SyntaxKind::WithClause,
+            SyntaxKind::SelectClause,
+            SyntaxKind::FromClause,
+            SyntaxKind::ArrayJoinClause,
+            SyntaxKind::JoinClause,
+            SyntaxKind::PrewhereClause,
+            SyntaxKind::WhereClause,
+            SyntaxKind::GroupByClause,
+            SyntaxKind::WithTotalsClause,
+            SyntaxKind::HavingClause,
+            SyntaxKind::WindowClause,
+            SyntaxKind::QualifyClause,
+            SyntaxKind::OrderByClause,
+            SyntaxKind::LimitByClause,
+            SyntaxKind::LimitClause,
+            SyntaxKind::SettingsClause,
+            SyntaxKind::FormatClause,
+        ]
+    );
+}
+
+#[test]
+fn clause_lists_stop_at_set_operations() {
+    check_errors("SELECT a FROM t ORDER BY a UNION ALL SELECT 1", expect![[""]]);
+    check_errors("SELECT a FROM t GROUP BY a EXCEPT SELECT 1", expect![[""]]);
+    check_errors("SELECT a FROM t GROUP BY a WITH CUBE INTERSECT SELECT 1", expect![[""]]);
+}
+
+#[test]
+fn window_clause_stops_at_later_clauses() {
+    assert_eq!(
+        clause_kinds("SELECT a FROM t WINDOW w AS (PARTITION BY a) QUALIFY x = 1 ORDER BY a"),
+        [
+            SyntaxKind::SelectClause,
+            SyntaxKind::FromClause,
+            SyntaxKind::WindowClause,
+            SyntaxKind::QualifyClause,
+            SyntaxKind::OrderByClause,
+        ]
+    );
+    check_errors("SELECT a FROM t WINDOW 1", expect![[r#"
+        23..24: Expected window name
+    "#]]);
+}
+
+// ====================================================================
+// Mutations: DELETE FROM, ALTER TABLE ... DELETE / UPDATE
+// ====================================================================
+
+#[test]
+fn lightweight_delete_in_partition() {
+    check(
+        "DELETE FROM db.t ON CLUSTER c IN PARTITION 202401 WHERE x = 1",
+        expect![[r#"
+            File
+              DeleteStatement
+                'DELETE'
+                'FROM'
+                TableIdentifier
+                  'db'
+                  '.'
+                  't'
+                OnClusterClause
+                  'ON'
+                  'CLUSTER'
+                  'c'
+                'IN'
+                'PARTITION'
+                PartitionExpression
+                  NumberLiteral
+                    '202401'
+                WhereClause
+                  'WHERE'
+                  BinaryExpression
+                    ColumnReference
+                      'x'
+                    '='
+                    NumberLiteral
+                      '1'
+        "#]],
+    );
+}
+
+#[test]
+fn alter_delete_in_partition() {
+    check(
+        "ALTER TABLE t DELETE IN PARTITION ID '2024' WHERE a = 1",
+        expect![[r#"
+            File
+              AlterStatement
+                'ALTER'
+                'TABLE'
+                TableIdentifier
+                  't'
+                AlterCommandList
+                  AlterDeleteWhere
+                    'DELETE'
+                    'IN'
+                    'PARTITION'
+                    PartitionExpression
+                      'ID'
+                      StringLiteral
+                        ''2024''
+                    'WHERE'
+                    BinaryExpression
+                      ColumnReference
+                        'a'
+                      '='
+                      NumberLiteral
+                        '1'
+        "#]],
+    );
+}
+
+#[test]
+fn alter_update_in_partition() {
+    check(
+        "ALTER TABLE t ON CLUSTER c UPDATE b = b + 1, c = 'x' IN PARTITION p WHERE a IN (1, 2)",
+        expect![[r#"
+            File
+              AlterStatement
+                'ALTER'
+                'TABLE'
+                TableIdentifier
+                  't'
+                OnClusterClause
+                  'ON'
+                  'CLUSTER'
+                  'c'
+                AlterCommandList
+                  AlterUpdateWhere
+                    'UPDATE'
+                    AssignmentList
+                      Assignment
+                        'b'
+                        '='
+                        BinaryExpression
+                          ColumnReference
+                            'b'
+                          '+'
+                          NumberLiteral
+                            '1'
+                      ','
+                      Assignment
+                        'c'
+                        '='
+                        StringLiteral
+                          ''x''
+                    'IN'
+                    'PARTITION'
+                    PartitionExpression
+                      ColumnReference
+                        'p'
+                    'WHERE'
+                    InExpression
+                      ColumnReference
+                        'a'
+                      'IN'
+                      '('
+                      NumberLiteral
+                        '1'
+                      ','
+                      NumberLiteral
+                        '2'
+                      ')'
+        "#]],
+    );
+}
+
+#[test]
+fn mutations_without_partition() {
+    check_errors("DELETE FROM t WHERE a IN (SELECT id FROM u)", expect![[""]]);
+    check_errors("ALTER TABLE t DELETE WHERE a = 1 SETTINGS mutations_sync = 2", expect![[""]]);
+    check_errors("ALTER TABLE t UPDATE a = 1 WHERE b IN (1, 2)", expect![[""]]);
+}
+
+#[test]
+fn mutation_missing_where() {
+    check_errors("DELETE FROM t IN PARTITION 1", expect![[r#"
+        28..28: Expected WHERE clause in DELETE statement
+    "#]]);
+    check_errors("ALTER TABLE t UPDATE a = 1", expect![[r#"
+        26..26: Expected WHERE after UPDATE assignments
+    "#]]);
+}
+
+// ====================================================================
+// WITH clause mixing scalar expressions and CTEs
+// ====================================================================
+
+#[test]
+fn with_scalar_and_subquery_items() {
+    check(
+        "WITH 1 AS x, cte AS (SELECT 2) SELECT x FROM cte",
+        expect![[r#"
+            File
+              SelectStatement
+                WithClause
+                  'WITH'
+                  ColumnList
+                    NumberLiteral
+                      '1'
+                    ColumnAlias
+                      'AS'
+                      'x'
+                    ','
+                    WithExpressionItem
+                      'cte'
+                      'AS'
+                      '('
+                      SubqueryExpression
+                        SelectStatement
+                          SelectClause
+                            'SELECT'
+                            ColumnList
+                              NumberLiteral
+                                '2'
+                      ')'
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'x'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    'cte'
+        "#]],
+    );
+}
+
+// ====================================================================
+// ORDER BY tuple key vs. several keys
+// ====================================================================
+
+/// Number of OrderByItem nodes in the query's ORDER BY clause.
+fn order_by_item_count(input: &str) -> usize {
+    let result = parse(input);
+    assert!(result.errors.is_empty(), "{input}: {:?}", result.errors);
+    let order_by = result.tree.find_first(SyntaxKind::OrderByClause).unwrap();
+    order_by
+        .children
+        .iter()
+        .filter(|child| child.get_tree_with_kind(SyntaxKind::OrderByItem).is_some())
+        .count()
+}
+
+#[test]
+fn order_by_tuple_is_one_key() {
+    check(
+        "SELECT a FROM t ORDER BY (a, b)",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'a'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                OrderByClause
+                  'ORDER'
+                  'BY'
+                  OrderByItem
+                    TupleExpression
+                      '('
+                      ColumnReference
+                        'a'
+                      ','
+                      ColumnReference
+                        'b'
+                      ')'
+        "#]],
+    );
+    assert_eq!(order_by_item_count("SELECT a FROM t ORDER BY (a, b)"), 1);
+    assert_eq!(order_by_item_count("SELECT a FROM t ORDER BY (a, b) DESC, c"), 2);
+}
+
+#[test]
+fn order_by_comma_list_is_several_keys() {
+    check(
+        "SELECT a FROM t ORDER BY a, b",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    ColumnReference
+                      'a'
+                FromClause
+                  'FROM'
+                  TableIdentifier
+                    't'
+                OrderByClause
+                  'ORDER'
+                  'BY'
+                  OrderByItem
+                    ColumnReference
+                      'a'
+                  ','
+                  OrderByItem
+                    ColumnReference
+                      'b'
+        "#]],
+    );
+    assert_eq!(order_by_item_count("SELECT a FROM t ORDER BY (a), b"), 2);
+}
+
+// ====================================================================
+// WITH before a UNION: parsed into the first SELECT, as in ClickHouse's
+// AST, while scope resolution applies it to every branch
+// ====================================================================
+
+#[test]
+fn with_clause_before_union_stays_in_first_select() {
+    check(
+        "WITH c AS (SELECT 1) SELECT * FROM c UNION ALL SELECT * FROM c",
+        expect![[r#"
+            File
+              UnionClause
+                SelectStatement
+                  WithClause
+                    'WITH'
+                    ColumnList
+                      WithExpressionItem
+                        'c'
+                        'AS'
+                        '('
+                        SubqueryExpression
+                          SelectStatement
+                            SelectClause
+                              'SELECT'
+                              ColumnList
+                                NumberLiteral
+                                  '1'
+                        ')'
+                  SelectClause
+                    'SELECT'
+                    ColumnList
+                      Asterisk
+                        '*'
+                  FromClause
+                    'FROM'
+                    TableIdentifier
+                      'c'
+                'UNION'
+                'ALL'
+                SelectStatement
+                  SelectClause
+                    'SELECT'
+                    ColumnList
+                      Asterisk
+                        '*'
+                  FromClause
+                    'FROM'
+                    TableIdentifier
+                      'c'
+        "#]],
+    );
+}
+
+// ====================================================================
+// Fragment entry points: a standalone WHERE condition or column list
+// ====================================================================
+
+/// Snapshot a fragment parse: the printed tree followed by any errors.
+fn check_fragment(result: clickhouse_analyzer::Parse, expected: Expect) {
+    let mut buf = String::new();
+    result.tree.print(&mut buf, 0, &result.source);
+    for error in &result.errors {
+        buf.push_str(&format!("{}..{}: {}\n", error.range.0, error.range.1, error.message));
+    }
+    expected.assert_eq(&buf);
+}
+
+#[test]
+fn standalone_where_condition() {
+    check_fragment(
+        parse_where_clause("a > 1 AND b = 'x'"),
+        expect![[r#"
+            File
+              WhereClause
+                BinaryExpression
+                  BinaryExpression
+                    ColumnReference
+                      'a'
+                    '>'
+                    NumberLiteral
+                      '1'
+                  'AND'
+                  BinaryExpression
+                    ColumnReference
+                      'b'
+                    '='
+                    StringLiteral
+                      ''x''
+        "#]],
+    );
+}
+
+#[test]
+fn standalone_where_condition_with_keyword() {
+    let result = parse_where_clause("WHERE a IN (1, 2)");
+    assert!(result.errors.is_empty());
+    assert!(result.tree.find_first(SyntaxKind::WhereClause).is_some());
+}
+
+#[test]
+fn standalone_where_condition_rejects_trailing_input() {
+    check_fragment(
+        parse_where_clause("a = 1 ORDER BY a"),
+        expect![[r#"
+            File
+              WhereClause
+                BinaryExpression
+                  ColumnReference
+                    'a'
+                  '='
+                  NumberLiteral
+                    '1'
+              Error
+                'ORDER'
+                'BY'
+                'a'
+            6..16: Unexpected input after condition
+        "#]],
+    );
+}
+
+#[test]
+fn standalone_column_list() {
+    check_fragment(
+        parse_column_list("a, b AS c, count(*)"),
+        expect![[r#"
+            File
+              ColumnList
+                ColumnReference
+                  'a'
+                ','
+                ColumnReference
+                  'b'
+                ColumnAlias
+                  'AS'
+                  'c'
+                ','
+                FunctionCall
+                  Identifier
+                    'count'
+                  ExpressionList
+                    '('
+                    Expression
+                      Asterisk
+                        '*'
+                    ')'
+        "#]],
+    );
+}
+
+#[test]
+fn standalone_column_list_is_lossless() {
+    let input = "  a,  b AS c  ";
+    let result = parse_column_list(input);
+    assert!(result.errors.is_empty());
+    assert_eq!(collect_text(&result.tree, &result.source), input);
+}
+
+// ====================================================================
+// CREATE TABLE ... AS: another table, a table function, or a SELECT
+// ====================================================================
+
+#[test]
+fn create_table_as_other_table() {
+    check(
+        "CREATE TABLE t AS db.t2 ENGINE = Memory",
+        expect![[r#"
+            File
+              CreateStatement
+                'CREATE'
+                TableDefinition
+                  'TABLE'
+                  TableIdentifier
+                    't'
+                  AsClause
+                    'AS'
+                    TableIdentifier
+                      'db'
+                      '.'
+                      't2'
+                  EngineClause
+                    'ENGINE'
+                    '='
+                    'Memory'
+        "#]],
+    );
+}
+
+#[test]
+fn create_table_as_table_function() {
+    check(
+        "CREATE TABLE t AS s3('https://b/x.csv', 'CSV')",
+        expect![[r#"
+            File
+              CreateStatement
+                'CREATE'
+                TableDefinition
+                  'TABLE'
+                  TableIdentifier
+                    't'
+                  AsClause
+                    'AS'
+                    TableFunction
+                      's3'
+                      '('
+                      StringLiteral
+                        ''https://b/x.csv''
+                      ','
+                      StringLiteral
+                        ''CSV''
+                      ')'
+        "#]],
+    );
+}
+
+#[test]
+fn create_table_as_select() {
+    check(
+        "CREATE TABLE t ENGINE = Memory AS SELECT 1",
+        expect![[r#"
+            File
+              CreateStatement
+                'CREATE'
+                TableDefinition
+                  'TABLE'
+                  TableIdentifier
+                    't'
+                  EngineClause
+                    'ENGINE'
+                    '='
+                    'Memory'
+                  AsClause
+                    'AS'
+                    SelectStatement
+                      SelectClause
+                        'SELECT'
+                        ColumnList
+                          NumberLiteral
+                            '1'
+        "#]],
+    );
+}
+
+// ====================================================================
+// Here-doc strings: $$...$$ bodies are one opaque string token
+// ====================================================================
+
+#[test]
+fn heredoc_string_literal() {
+    check(
+        "SELECT $$it's a 'body'$$ AS body",
+        expect![[r#"
+            File
+              SelectStatement
+                SelectClause
+                  'SELECT'
+                  ColumnList
+                    StringLiteral
+                      '$$it's a 'body'$$'
+                    ColumnAlias
+                      'AS'
+                      'body'
+        "#]],
+    );
+}
+
+#[test]
+fn heredoc_round_trips() {
+    let input = "SELECT $tag$ -- not a comment\n $$ 'x' $tag$, 1";
+    let result = parse(input);
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(collect_text(&result.tree, &result.source), input);
+}
+
+#[test]
+fn create_function_with_heredoc_body() {
+    check(
+        "CREATE FUNCTION f AS (x) -> concat(x, $$it's raw$$)",
+        expect![[r#"
+            File
+              CreateStatement
+                'CREATE'
+                FunctionDefinition
+                  'FUNCTION'
+                  'f'
+                  'AS'
+                  LambdaExpression
+                    Expression
+                      '('
+                      ColumnReference
+                        'x'
+                      ')'
+                    '->'
+                    FunctionCall
+                      Identifier
+                        'concat'
+                      ExpressionList
+                        '('
+                        Expression
+                          ColumnReference
+                            'x'
+                        ','
+                        Expression
+                          StringLiteral
+                            '$$it's raw$$'
+                        ')'
+        "#]],
+    );
+}
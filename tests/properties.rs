@@ -1,10 +1,20 @@
-use clickhouse_analyzer::{format, parse, FormatConfig, SyntaxChild, SyntaxKind, SyntaxTree};
+use clickhouse_analyzer::{
+    format, parse, reserved_keywords, FormatConfig, SyntaxChild, SyntaxKind, SyntaxTree,
+};
 use proptest::prelude::*;
 
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Names matching `pattern` that aren't reserved keywords, which can't be
+/// bare column or table names.
+fn identifier(pattern: &'static str) -> impl Strategy<Value = String> {
+    pattern.prop_filter("reserved keyword", |name| {
+        !reserved_keywords().contains(&name.to_uppercase().as_str())
+    })
+}
+
 /// Recursively collect all token text from a syntax tree, preserving order.
 /// If the CST is complete, this reconstructs the original input exactly.
 fn collect_text(tree: &SyntaxTree, source: &str) -> String {
@@ -114,8 +124,8 @@ proptest! {
     /// Tests with well-formed SQL queries to verify the formatter stabilizes.
     #[test]
     fn formatter_idempotent(
-        cols in prop::collection::vec("[a-z][a-z0-9_]{0,5}", 1..5),
-        table in "[a-z][a-z0-9_]{0,8}",
+        cols in prop::collection::vec(identifier("[a-z][a-z0-9_]{0,5}"), 1..5),
+        table in identifier("[a-z][a-z0-9_]{0,8}"),
         has_where in proptest::bool::ANY,
         limit in prop::option::of(1u32..1000),
     ) {